@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TABLE attachments (
+  id VARCHAR(255) PRIMARY KEY,
+  post_id VARCHAR(255) NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  file_name VARCHAR(255) NOT NULL,
+  content_type VARCHAR(255) NOT NULL,
+  size BIGINT NOT NULL,
+  storage_key VARCHAR(255) NOT NULL,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX attachments_post_id_idx ON attachments (post_id);
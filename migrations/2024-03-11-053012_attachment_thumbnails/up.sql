@@ -0,0 +1,11 @@
+-- Your SQL goes here
+CREATE TABLE attachment_thumbnails (
+  attachment_id VARCHAR(255) NOT NULL REFERENCES attachments(id) ON DELETE CASCADE,
+  size VARCHAR(32) NOT NULL,
+  width INTEGER NOT NULL,
+  height INTEGER NOT NULL,
+  content_type VARCHAR(255) NOT NULL,
+  storage_key VARCHAR(255) NOT NULL,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (attachment_id, size)
+)
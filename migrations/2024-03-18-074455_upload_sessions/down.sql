@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE upload_chunks;
+DROP TABLE upload_sessions
@@ -0,0 +1,23 @@
+-- Your SQL goes here
+CREATE TABLE upload_sessions (
+  id VARCHAR(255) PRIMARY KEY,
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  post_id VARCHAR(255) NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  file_name VARCHAR(255) NOT NULL,
+  content_type VARCHAR(255) NOT NULL,
+  upload_length BIGINT NOT NULL,
+  upload_offset BIGINT NOT NULL DEFAULT 0,
+  attachment_id VARCHAR(255) REFERENCES attachments(id) ON DELETE SET NULL,
+  -- 첨부파일을 만들었으면 true (첨부파일을 지워 attachment_id가 비어도 다시 조립하지 않음)
+  completed BOOLEAN NOT NULL DEFAULT FALSE,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE upload_chunks (
+  upload_id VARCHAR(255) NOT NULL REFERENCES upload_sessions(id) ON DELETE CASCADE,
+  chunk_offset BIGINT NOT NULL,
+  size BIGINT NOT NULL,
+  storage_key VARCHAR(255) NOT NULL,
+  PRIMARY KEY (upload_id, chunk_offset)
+)
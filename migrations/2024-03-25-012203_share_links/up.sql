@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE share_links (
+  id VARCHAR(255) PRIMARY KEY,
+  post_id VARCHAR(255) NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  token VARCHAR(255) UNIQUE NOT NULL,
+  password_hash VARCHAR(255),
+  expires_at TIMESTAMP,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX share_links_post_id_idx ON share_links (post_id);
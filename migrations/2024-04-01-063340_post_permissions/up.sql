@@ -0,0 +1,26 @@
+-- Your SQL goes here
+CREATE TABLE post_permissions (
+  post_id VARCHAR(255) NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  role VARCHAR(32) NOT NULL CHECK (role IN ('viewer', 'commenter', 'editor', 'owner')),
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (post_id, user_id)
+);
+
+CREATE INDEX post_permissions_user_id_idx ON post_permissions (user_id);
+
+-- 기존 노트에는 작성자 기록이 없으므로 첨부파일을 처음 올린 사용자를,
+-- 첨부파일도 없으면 가장 먼저 가입한 사용자를 owner로 등록 (권한 확인 전에는 모든 사용자가 볼 수 있었음)
+INSERT INTO post_permissions (post_id, user_id, role)
+SELECT posts.id, owner.user_id, 'owner'
+FROM posts
+CROSS JOIN LATERAL (
+  SELECT user_id FROM (
+    (SELECT user_id, 0 AS priority, created_at FROM attachments WHERE attachments.post_id = posts.id)
+    UNION ALL
+    (SELECT id, 1, created_at FROM users)
+  ) AS candidates
+  ORDER BY priority, created_at, user_id
+  LIMIT 1
+) AS owner;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE posts DROP COLUMN workspace_id;
+DROP TABLE workspace_invitations;
+DROP TABLE workspace_members;
+DROP TABLE workspaces
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+CREATE TABLE post_documents (
+  post_id VARCHAR(255) PRIMARY KEY REFERENCES posts(id) ON DELETE CASCADE,
+  state BYTEA NOT NULL,
+  updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+-- 노트 변경 이벤트 로그, 이벤트를 받을 사용자마다 한 줄씩 기록
+CREATE TABLE post_events (
+  id BIGSERIAL PRIMARY KEY,
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  post_id VARCHAR(255) NOT NULL,
+  kind VARCHAR(32) NOT NULL CHECK (kind IN ('created', 'updated', 'deleted')),
+  -- id는 커밋 순서와 다를 수 있어서 기록한 트랜잭션 id로 이어받을 위치를 정함 (post_sync와 같은 방식)
+  xid BIGINT NOT NULL DEFAULT pg_current_xact_id()::text::bigint,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX post_events_user_id_idx ON post_events (user_id, xid, id);
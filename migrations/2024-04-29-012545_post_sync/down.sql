@@ -0,0 +1,12 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE post_tombstones;
+
+DROP TRIGGER posts_sync_version ON posts;
+
+DROP FUNCTION posts_bump_sync_version();
+
+ALTER TABLE posts DROP COLUMN sync_xid;
+
+ALTER TABLE posts DROP COLUMN sync_version;
+
+DROP SEQUENCE post_sync_seq;
@@ -0,0 +1,38 @@
+-- Your SQL goes here
+-- 오프라인 동기화용 단조 증가 버전: 노트가 생성/수정되거나 삭제 기록이 남을 때마다 증가
+CREATE SEQUENCE post_sync_seq;
+
+-- sync_version은 커밋 전에 정해지므로 늦게 커밋된 낮은 버전을 건너뛸 수 있음
+-- 그래서 변경을 쓴 트랜잭션 id(sync_xid)를 함께 기록하고, 동기화는 끝난 트랜잭션의 변경만 (sync_xid, sync_version) 순서로 돌려줌
+ALTER TABLE posts ADD COLUMN sync_version BIGINT NOT NULL DEFAULT nextval('post_sync_seq');
+ALTER TABLE posts ADD COLUMN sync_xid BIGINT NOT NULL DEFAULT pg_current_xact_id()::text::bigint;
+
+CREATE INDEX posts_sync_version_idx ON posts (sync_xid, sync_version);
+
+CREATE FUNCTION posts_bump_sync_version() RETURNS TRIGGER AS $$
+BEGIN
+  NEW.sync_version := nextval('post_sync_seq');
+  NEW.sync_xid := pg_current_xact_id()::text::bigint;
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER posts_sync_version BEFORE UPDATE ON posts
+  FOR EACH ROW EXECUTE FUNCTION posts_bump_sync_version();
+
+-- 삭제된 노트 기록, 삭제 시점에 노트에 접근할 수 있던 사용자마다 한 줄씩
+-- (노트는 남아 있어도 접근 권한을 잃은 사용자에게도 남김)
+CREATE TABLE post_tombstones (
+  post_id VARCHAR(255) NOT NULL,
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  sync_version BIGINT NOT NULL DEFAULT nextval('post_sync_seq'),
+  sync_xid BIGINT NOT NULL DEFAULT pg_current_xact_id()::text::bigint,
+  deleted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (post_id, user_id)
+);
+
+CREATE INDEX post_tombstones_user_id_idx ON post_tombstones (user_id, sync_xid, sync_version);
+
+-- 같은 노트의 삭제 기록을 다시 남기면 버전도 다시 올림
+CREATE TRIGGER post_tombstones_sync_version BEFORE UPDATE ON post_tombstones
+  FOR EACH ROW EXECUTE FUNCTION posts_bump_sync_version();
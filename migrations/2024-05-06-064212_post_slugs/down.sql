@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX posts_author_id_slug_idx;
+
+ALTER TABLE posts DROP COLUMN slug;
+ALTER TABLE posts DROP COLUMN author_id;
@@ -0,0 +1,20 @@
+-- Your SQL goes here
+ALTER TABLE posts ADD COLUMN author_id VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE posts ADD COLUMN slug VARCHAR(255);
+
+-- 기존 노트의 작성자는 가장 먼저 owner가 된 사용자
+UPDATE posts SET author_id = (
+  SELECT user_id FROM post_permissions
+  WHERE post_permissions.post_id = posts.id AND role = 'owner'
+  ORDER BY created_at
+  LIMIT 1
+);
+
+-- 기존 노트는 제목 + id 앞부분으로 slug 생성 (작성자 안에서 겹치지 않도록)
+UPDATE posts SET slug = concat_ws(
+  '-',
+  NULLIF(lower(trim(BOTH '-' FROM regexp_replace(title, '[^[:alnum:]]+', '-', 'g'))), ''),
+  left(id, 8)
+);
+
+CREATE UNIQUE INDEX posts_author_id_slug_idx ON posts (author_id, slug);
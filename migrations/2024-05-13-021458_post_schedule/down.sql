@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX posts_unpublish_at_idx;
+DROP INDEX posts_publish_at_idx;
+
+ALTER TABLE posts DROP COLUMN unpublish_at;
+ALTER TABLE posts DROP COLUMN publish_at;
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+-- 예약 공개/비공개 시각 (UTC), 스케줄러가 처리하면 NULL로 비움
+ALTER TABLE posts ADD COLUMN publish_at TIMESTAMP;
+ALTER TABLE posts ADD COLUMN unpublish_at TIMESTAMP;
+
+CREATE INDEX posts_publish_at_idx ON posts (publish_at) WHERE publish_at IS NOT NULL;
+CREATE INDEX posts_unpublish_at_idx ON posts (unpublish_at) WHERE unpublish_at IS NOT NULL;
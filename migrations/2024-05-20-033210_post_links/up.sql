@@ -0,0 +1,35 @@
+-- Your SQL goes here
+-- 노트 본문의 [[제목]] 링크, 같은 범위(워크스페이스 또는 작성자 개인 노트)에서 제목이 같은 노트로 연결
+CREATE TABLE post_links (
+  source_id VARCHAR(255) NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  target_title VARCHAR(255) NOT NULL,
+  -- 연결되는 노트가 없으면 NULL
+  target_id VARCHAR(255) REFERENCES posts(id) ON DELETE SET NULL,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (source_id, target_title)
+);
+
+CREATE INDEX post_links_target_id_idx ON post_links (target_id);
+CREATE INDEX post_links_target_title_idx ON post_links (lower(target_title));
+
+-- 기존 노트 본문의 링크
+INSERT INTO post_links (source_id, target_title)
+SELECT DISTINCT ON (source_id, lower(target_title)) source_id, target_title
+FROM (
+  SELECT posts.id AS source_id,
+    left(trim(split_part(split_part(m[1], '|', 1), '#', 1)), 255) AS target_title
+  FROM posts, regexp_matches(posts.body, '\[\[([^\[\]\n]+)\]\]', 'g') AS m
+) links
+WHERE target_title <> '';
+
+UPDATE post_links SET target_id = (
+  SELECT target.id FROM posts target, posts source
+  WHERE source.id = post_links.source_id
+    AND lower(target.title) = lower(post_links.target_title)
+    AND (
+      (source.workspace_id IS NOT NULL AND target.workspace_id = source.workspace_id)
+      OR (source.workspace_id IS NULL AND target.workspace_id IS NULL AND target.author_id = source.author_id)
+    )
+  ORDER BY target.created_at
+  LIMIT 1
+);
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+-- 노트 템플릿: workspace_id가 없으면 만든 사용자만 쓰는 개인 템플릿
+CREATE TABLE templates (
+  id VARCHAR(255) PRIMARY KEY,
+  name VARCHAR(255) NOT NULL,
+  -- 만들어질 노트의 제목과 본문, {{변수}} 치환
+  title VARCHAR(255) NOT NULL DEFAULT '',
+  body TEXT NOT NULL DEFAULT '',
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  workspace_id VARCHAR(255) REFERENCES workspaces(id) ON DELETE CASCADE,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX templates_user_id_idx ON templates (user_id) WHERE workspace_id IS NULL;
+CREATE INDEX templates_workspace_id_idx ON templates (workspace_id);
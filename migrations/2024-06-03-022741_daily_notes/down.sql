@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE daily_notes;
+
+ALTER TABLE users DROP COLUMN daily_template_id;
+ALTER TABLE users DROP COLUMN timezone;
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+-- IANA 시간대 (예: Asia/Seoul), 데일리 노트 날짜와 템플릿 날짜 계산에 사용
+ALTER TABLE users ADD COLUMN timezone VARCHAR(64) NOT NULL DEFAULT 'UTC';
+-- 데일리 노트를 만들 때 쓰는 템플릿
+ALTER TABLE users ADD COLUMN daily_template_id VARCHAR(255) REFERENCES templates(id) ON DELETE SET NULL;
+
+-- 사용자별 날짜마다 하나의 데일리 노트
+CREATE TABLE daily_notes (
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  day DATE NOT NULL,
+  post_id VARCHAR(255) NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (user_id, day)
+);
+
+CREATE UNIQUE INDEX daily_notes_post_id_idx ON daily_notes (post_id);
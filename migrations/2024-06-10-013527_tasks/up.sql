@@ -0,0 +1,45 @@
+-- Your SQL goes here
+-- 노트 본문의 "- [ ] 할 일" 항목, 노트를 저장할 때마다 본문에서 다시 읽음
+CREATE TABLE tasks (
+  id VARCHAR(255) PRIMARY KEY,
+  post_id VARCHAR(255) NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  -- 본문에서의 줄 번호 (1부터)
+  line INTEGER NOT NULL,
+  text TEXT NOT NULL,
+  done BOOLEAN NOT NULL DEFAULT FALSE,
+  -- 항목 text의 due:YYYY-MM-DD
+  due_date DATE,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX tasks_post_id_idx ON tasks (post_id, line);
+CREATE INDEX tasks_due_date_idx ON tasks (due_date) WHERE NOT done;
+
+-- 잘못된 날짜는 NULL
+CREATE FUNCTION pg_temp.try_date(value TEXT) RETURNS DATE AS $$
+BEGIN
+  RETURN value::date;
+EXCEPTION WHEN others THEN
+  RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- 기존 노트 본문의 할 일 (``` 코드 블록 안은 제외)
+INSERT INTO tasks (id, post_id, line, text, done, due_date)
+SELECT gen_random_uuid()::text, post_id, line,
+  btrim(item[2], E' \t\r'),
+  item[1] <> ' ',
+  pg_temp.try_date(substring(item[2] from '(?:^|\s)due:(\d{4}-\d{2}-\d{2})(?=\s|$)'))
+FROM (
+  SELECT post_id, line, fence, regexp_match(content, '^\s*[-*+] \[([ xX])\](?:\s+(.*))?$') AS item
+  FROM (
+    SELECT posts.id AS post_id, lines.line, lines.content,
+      count(*) FILTER (WHERE lines.content ~ '^\s*```')
+        OVER (PARTITION BY posts.id ORDER BY lines.line) AS fence
+    FROM posts, regexp_split_to_table(posts.body, '\n') WITH ORDINALITY AS lines(content, line)
+  ) body_lines
+) items
+WHERE item IS NOT NULL
+  AND fence % 2 = 0
+  AND btrim(item[2], E' \t\r') <> '';
@@ -0,0 +1,38 @@
+-- Your SQL goes here
+-- 사용자에게 보이는 알림 (알림함)
+CREATE TABLE notifications (
+  id VARCHAR(255) PRIMARY KEY,
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  -- reminder 등 알림 종류
+  kind VARCHAR(32) NOT NULL,
+  post_id VARCHAR(255) REFERENCES posts(id) ON DELETE CASCADE,
+  message TEXT NOT NULL,
+  read_at TIMESTAMP,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX notifications_user_id_idx ON notifications (user_id, created_at DESC);
+
+-- 노트 리마인더, 시각은 모두 UTC
+CREATE TABLE reminders (
+  id VARCHAR(255) PRIMARY KEY,
+  post_id VARCHAR(255) NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  message TEXT,
+  -- 반복의 기준 시각 (RRULE의 DTSTART)
+  starts_at TIMESTAMP NOT NULL,
+  rrule VARCHAR(255),
+  -- 반복을 계산할 시간대 (만들 때 사용자의 시간대)
+  timezone VARCHAR(64) NOT NULL DEFAULT 'UTC',
+  -- 다음에 울릴 시각
+  fire_at TIMESTAMP NOT NULL,
+  email BOOLEAN NOT NULL DEFAULT FALSE,
+  -- 해제했거나 더 울릴 일이 없으면 FALSE
+  active BOOLEAN NOT NULL DEFAULT TRUE,
+  last_fired_at TIMESTAMP,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX reminders_fire_at_idx ON reminders (fire_at) WHERE active;
+CREATE INDEX reminders_user_id_idx ON reminders (user_id, fire_at);
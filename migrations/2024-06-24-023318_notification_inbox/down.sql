@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX notifications_unread_idx;
+ALTER TABLE notifications DROP COLUMN actor_id;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- 알림을 만든 사용자 (리마인더처럼 시스템이 만든 알림은 NULL)
+ALTER TABLE notifications ADD COLUMN actor_id VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL;
+
+CREATE INDEX notifications_unread_idx ON notifications (user_id) WHERE read_at IS NULL;
@@ -0,0 +1,22 @@
+-- Your SQL goes here
+-- 노트 댓글, 답글은 parent_id에 스레드 첫 댓글을 가짐
+CREATE TABLE comments (
+  id VARCHAR(255) PRIMARY KEY,
+  post_id VARCHAR(255) NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  parent_id VARCHAR(255) REFERENCES comments(id) ON DELETE CASCADE,
+  body TEXT NOT NULL,
+  -- 본문 중 댓글을 단 범위 (글자 단위 [start, end)와 그때의 텍스트)
+  anchor_start INTEGER,
+  anchor_end INTEGER,
+  anchor_text TEXT,
+  resolved_at TIMESTAMP,
+  resolved_by VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL,
+  -- 답글이 있는 댓글은 지워도 스레드를 남김
+  deleted_at TIMESTAMP,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX comments_post_id_idx ON comments (post_id, created_at);
+CREATE INDEX comments_parent_id_idx ON comments (parent_id);
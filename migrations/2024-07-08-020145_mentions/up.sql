@@ -0,0 +1,42 @@
+-- Your SQL goes here
+-- @멘션에 쓰는 사용자 이름 (소문자, 숫자, _)
+ALTER TABLE users ADD COLUMN handle VARCHAR(32);
+
+CREATE UNIQUE INDEX users_handle_key ON users (handle);
+
+-- 기존 사용자는 이메일 앞부분으로, 겹치면 뒤에 _2, _3...
+-- 붙인 이름이 다른 사용자의 이메일 앞부분과 겹칠 수 있어서 비어 있는 이름이 나올 때까지 번호를 올림
+DO $$
+DECLARE
+  target RECORD;
+  base TEXT;
+  candidate TEXT;
+  n INT;
+BEGIN
+  FOR target IN SELECT id, email FROM users ORDER BY created_at, id LOOP
+    base := left(regexp_replace(lower(split_part(target.email, '@', 1)), '[^a-z0-9_]', '', 'g'), 24);
+    base := rpad(base, greatest(length(base), 3), '_');
+    candidate := base;
+    n := 1;
+    WHILE EXISTS (SELECT 1 FROM users WHERE handle = candidate) LOOP
+      n := n + 1;
+      candidate := base || '_' || n;
+    END LOOP;
+    UPDATE users SET handle = candidate WHERE id = target.id;
+  END LOOP;
+END $$;
+
+ALTER TABLE users ALTER COLUMN handle SET NOT NULL;
+
+-- 노트 본문이나 댓글에서 멘션된 사용자
+CREATE TABLE mentions (
+  id VARCHAR(255) PRIMARY KEY,
+  post_id VARCHAR(255) NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  -- 댓글의 멘션이면 댓글, 노트 본문이면 NULL
+  comment_id VARCHAR(255) REFERENCES comments(id) ON DELETE CASCADE,
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX mentions_source_user_key ON mentions (post_id, COALESCE(comment_id, ''), user_id);
+CREATE INDEX mentions_user_id_idx ON mentions (user_id);
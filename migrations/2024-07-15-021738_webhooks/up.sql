@@ -0,0 +1,40 @@
+-- Your SQL goes here
+-- 노트 변경을 외부 URL로 보내는 웹훅 (workspace_id가 없으면 user_id의 개인 노트)
+CREATE TABLE webhooks (
+  id VARCHAR(255) PRIMARY KEY,
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  workspace_id VARCHAR(255) REFERENCES workspaces(id) ON DELETE CASCADE,
+  url TEXT NOT NULL,
+  -- 요청 서명(HMAC-SHA256)에 쓰는 키
+  secret VARCHAR(255) NOT NULL,
+  -- 받을 이벤트 (post.created, post.updated, post.deleted, post.published)
+  events TEXT[] NOT NULL,
+  active BOOLEAN NOT NULL DEFAULT TRUE,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX webhooks_user_id_idx ON webhooks (user_id) WHERE workspace_id IS NULL;
+CREATE INDEX webhooks_workspace_id_idx ON webhooks (workspace_id);
+
+-- 전송 기록, pending이면 next_attempt_at에 (다시) 보냄
+CREATE TABLE webhook_deliveries (
+  id VARCHAR(255) PRIMARY KEY,
+  webhook_id VARCHAR(255) NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+  event VARCHAR(32) NOT NULL,
+  payload TEXT NOT NULL,
+  -- pending, succeeded, failed
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  attempts INTEGER NOT NULL DEFAULT 0,
+  next_attempt_at TIMESTAMP,
+  last_attempt_at TIMESTAMP,
+  response_status INTEGER,
+  response_body TEXT,
+  error TEXT,
+  -- 다시 보내기로 만든 전송이면 원래 전송
+  redelivery_of VARCHAR(255) REFERENCES webhook_deliveries(id) ON DELETE SET NULL,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX webhook_deliveries_webhook_id_idx ON webhook_deliveries (webhook_id, created_at DESC);
+CREATE INDEX webhook_deliveries_pending_idx ON webhook_deliveries (next_attempt_at) WHERE status = 'pending';
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+-- 로그인 없이 POST로 노트를 만드는 주소 (workspace_id가 없으면 개인 노트)
+CREATE TABLE inboxes (
+  id VARCHAR(255) PRIMARY KEY,
+  user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  workspace_id VARCHAR(255) REFERENCES workspaces(id) ON DELETE CASCADE,
+  name VARCHAR(255) NOT NULL,
+  token VARCHAR(255) UNIQUE NOT NULL,
+  -- 만든 노트를 바로 공개할지
+  published BOOLEAN NOT NULL DEFAULT FALSE,
+  active BOOLEAN NOT NULL DEFAULT TRUE,
+  last_used_at TIMESTAMP,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX inboxes_user_id_idx ON inboxes (user_id);
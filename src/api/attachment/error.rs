@@ -0,0 +1,70 @@
+use actix_web::{
+    error::ResponseError,
+    http::{header::ContentType, StatusCode},
+    HttpResponse,
+};
+use derive_more::{Display, Error};
+use serde::Serialize;
+
+use crate::api::permission::authorize::AuthorizeError;
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    code: u16,
+    message: String,
+}
+
+#[derive(Debug, Display, Error)]
+pub enum AttachmentError {
+    #[display(fmt = "internal error")]
+    InternalError,
+
+    #[display(fmt = "bad request")]
+    BadClientData,
+
+    #[display(fmt = "not found")]
+    NotFound,
+
+    #[display(fmt = "forbidden")]
+    Forbidden,
+
+    #[display(fmt = "attachment exceeds {} bytes", limit)]
+    PayloadTooLarge { limit: usize },
+
+    #[display(fmt = "Validation error on field: {}", field)]
+    ValidationError { field: String },
+}
+
+impl ResponseError for AttachmentError {
+    fn error_response(&self) -> HttpResponse {
+        let error_response = ErrorResponse {
+            code: self.status_code().as_u16(),
+            message: self.to_string(),
+        };
+
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .json(error_response)
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match *self {
+            AttachmentError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            AttachmentError::BadClientData => StatusCode::BAD_REQUEST,
+            AttachmentError::NotFound => StatusCode::NOT_FOUND,
+            AttachmentError::Forbidden => StatusCode::FORBIDDEN,
+            AttachmentError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            AttachmentError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<AuthorizeError> for AttachmentError {
+    fn from(err: AuthorizeError) -> Self {
+        match err {
+            AuthorizeError::NotFound => AttachmentError::NotFound,
+            AuthorizeError::Forbidden => AttachmentError::Forbidden,
+            AuthorizeError::InternalError => AttachmentError::InternalError,
+        }
+    }
+}
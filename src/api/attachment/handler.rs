@@ -0,0 +1,396 @@
+use std::env;
+use std::str::FromStr;
+
+use super::error::AttachmentError;
+use super::model::{Attachment, AttachmentData, AttachmentResponse, AttachmentThumbnail};
+use super::thumbnail::{self, ThumbnailSize};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::permission::authorize::authorize;
+use crate::api::permission::model::PostRole;
+use crate::database::PgPool;
+use crate::storage::BlobStore;
+use actix_multipart::Multipart;
+use actix_web::http::header::{self, ContentDisposition, DispositionParam, DispositionType, Range};
+use actix_web::{
+    http::header::ContentType,
+    web::{self, Data, Query},
+    HttpRequest, HttpResponse, Result,
+};
+use bytes::BytesMut;
+use futures_util::StreamExt;
+use log::error;
+use serde::Deserialize;
+use serde_json::to_vec;
+use uuid::Uuid;
+
+// 첨부파일 최대 크기 기본값 (10MB)
+const DEFAULT_MAX_ATTACHMENT_SIZE: usize = 10 * 1024 * 1024;
+
+pub fn max_attachment_size() -> usize {
+    env::var("MAX_ATTACHMENT_SIZE")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_MAX_ATTACHMENT_SIZE)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ThumbnailQuery {
+    pub size: Option<ThumbnailSize>,
+}
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, AttachmentError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        AttachmentError::BadClientData
+    })
+}
+
+fn to_response(attachment: Attachment, store: &dyn BlobStore) -> AttachmentResponse {
+    let download_url = store
+        .presigned_download_url(
+            &attachment.storage_key,
+            &attachment.file_name,
+            &attachment.content_type,
+        )
+        .unwrap_or_else(|| {
+            format!(
+                "/api/posts/{}/attachments/{}",
+                attachment.post_id, attachment.id
+            )
+        });
+
+    AttachmentResponse {
+        attachment,
+        download_url,
+    }
+}
+
+// 노트에 required 이상의 권한이 있는 사용자만 첨부파일 접근 허용
+async fn get_authorized_attachment(
+    req: &HttpRequest,
+    auth_guard: &AuthenticationGuard,
+    required: PostRole,
+    pool: &Data<PgPool>,
+) -> Result<Attachment, AttachmentError> {
+    let post_id = path_param(req, "id")?;
+    let attachment_id = path_param(req, "attachment_id")?;
+    authorize(post_id, &auth_guard.user_id, required, pool).await?;
+
+    Attachment::get_attachment_by_id(post_id, attachment_id, pool)
+        .await
+        .map_err(|err| {
+            error!("Error get attachment data: {:?}", err);
+            AttachmentError::NotFound
+        })
+}
+
+pub async fn get_attachments(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+    store: Data<dyn BlobStore>,
+) -> Result<HttpResponse, AttachmentError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Viewer, &pool).await?;
+
+    match Attachment::get_attachments_by_post(post_id, &pool).await {
+        Ok(attachment_list) => {
+            let attachment_list = attachment_list
+                .into_iter()
+                .map(|attachment| to_response(attachment, store.as_ref()))
+                .collect::<Vec<_>>();
+
+            let json_bytes = to_vec(&attachment_list).map_err(|err| {
+                error!("Failed to serialize attachments to JSON: {:?}", err);
+                AttachmentError::InternalError
+            })?;
+
+            Ok(HttpResponse::Ok()
+                .content_type(ContentType::json())
+                .body(json_bytes))
+        }
+        Err(err) => {
+            error!("Error get attachments data: {:?}", err);
+            Err(AttachmentError::InternalError)
+        }
+    }
+}
+
+pub async fn upload_attachment(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    mut payload: Multipart,
+    pool: Data<PgPool>,
+    store: Data<dyn BlobStore>,
+) -> Result<HttpResponse, AttachmentError> {
+    let post_id = path_param(&req, "id")?;
+
+    authorize(post_id, &auth_guard.user_id, PostRole::Editor, &pool).await?;
+
+    let limit = max_attachment_size();
+    let mut upload = None;
+
+    // multipart 중 "file" 필드 하나만 사용
+    while let Some(item) = payload.next().await {
+        let mut field = item.map_err(|err| {
+            error!("Error reading multipart field: {:?}", err);
+            AttachmentError::BadClientData
+        })?;
+
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let file_name = field
+            .content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("file")
+            .to_string();
+
+        let content_type = match field.content_type() {
+            Some(mime) if *mime != mime_guess::mime::APPLICATION_OCTET_STREAM => mime.to_string(),
+            _ => mime_guess::from_path(&file_name)
+                .first_or_octet_stream()
+                .to_string(),
+        };
+
+        let mut data = BytesMut::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|err| {
+                error!("Error reading multipart chunk: {:?}", err);
+                AttachmentError::BadClientData
+            })?;
+
+            if data.len() + chunk.len() > limit {
+                return Err(AttachmentError::PayloadTooLarge { limit });
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        upload = Some((file_name, content_type, data.freeze()));
+        break;
+    }
+
+    let (file_name, content_type, mut data) = upload.ok_or(AttachmentError::ValidationError {
+        field: "file".to_string(),
+    })?;
+
+    let is_image = thumbnail::is_supported_image(&content_type);
+    if is_image {
+        data = web::block(move || thumbnail::strip_metadata(data))
+            .await
+            .map_err(|err| {
+                error!("Error stripping image metadata: {:?}", err);
+                AttachmentError::InternalError
+            })?;
+    }
+
+    let attachment_id = Uuid::new_v4().to_string();
+    let storage_key = format!("attachments/{}/{}", post_id, attachment_id);
+    let size = data.len() as i64;
+
+    if let Err(err) = store.put(&storage_key, data.clone()).await {
+        error!("Error storing attachment blob: {:?}", err);
+        return Err(AttachmentError::InternalError);
+    }
+
+    let attachment_data = AttachmentData {
+        id: attachment_id,
+        post_id: post_id.to_string(),
+        user_id: auth_guard.user_id,
+        file_name,
+        content_type,
+        size,
+        storage_key: storage_key.clone(),
+    };
+
+    match AttachmentData::create_attachment(attachment_data, &pool).await {
+        Ok(attachment) => {
+            if is_image {
+                thumbnail::spawn_thumbnail_job(
+                    attachment.clone(),
+                    data,
+                    pool.clone(),
+                    store.clone(),
+                );
+            }
+
+            Ok(HttpResponse::Created().json(to_response(attachment, store.as_ref())))
+        }
+        Err(err) => {
+            error!("Error created new attachment data: {:?}", err);
+            if let Err(err) = store.delete(&storage_key).await {
+                error!("Error removing orphaned attachment blob: {:?}", err);
+            }
+            Err(AttachmentError::InternalError)
+        }
+    }
+}
+
+pub async fn download_attachment(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+    store: Data<dyn BlobStore>,
+) -> Result<HttpResponse, AttachmentError> {
+    let attachment = get_authorized_attachment(&req, &auth_guard, PostRole::Viewer, &pool).await?;
+
+    // 서명 URL을 지원하는 저장소(S3 등)는 저장소에서 직접 내려받도록 리다이렉트
+    if let Some(url) = store.presigned_download_url(
+        &attachment.storage_key,
+        &attachment.file_name,
+        &attachment.content_type,
+    ) {
+        return Ok(HttpResponse::TemporaryRedirect()
+            .insert_header((header::LOCATION, url))
+            .finish());
+    }
+
+    let size = attachment.size as u64;
+
+    // 단일 구간 Range 요청만 부분 응답, 그 외에는 전체 응답
+    let range = match req
+        .headers()
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| Range::from_str(v).ok())
+    {
+        Some(Range::Bytes(specs)) if specs.len() == 1 => {
+            match specs[0].to_satisfiable_range(size) {
+                Some(range) => Some(range),
+                None => {
+                    return Ok(HttpResponse::RangeNotSatisfiable()
+                        .insert_header((header::CONTENT_RANGE, format!("bytes */{}", size)))
+                        .finish());
+                }
+            }
+        }
+        _ => None,
+    };
+
+    let data = store
+        .get(&attachment.storage_key, range)
+        .await
+        .map_err(|err| {
+            error!("Error reading attachment blob: {:?}", err);
+            AttachmentError::InternalError
+        })?;
+
+    let disposition = ContentDisposition {
+        disposition: DispositionType::Attachment,
+        parameters: vec![DispositionParam::Filename(attachment.file_name)],
+    };
+
+    let mut response = match range {
+        Some((start, end)) => {
+            let mut response = HttpResponse::PartialContent();
+            response.insert_header((
+                header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", start, end, size),
+            ));
+            response
+        }
+        None => HttpResponse::Ok(),
+    };
+
+    Ok(response
+        .content_type(attachment.content_type)
+        .insert_header(disposition)
+        .insert_header((header::ACCEPT_RANGES, "bytes"))
+        .body(data))
+}
+
+pub async fn delete_attachment(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+    store: Data<dyn BlobStore>,
+) -> Result<HttpResponse, AttachmentError> {
+    let attachment = get_authorized_attachment(&req, &auth_guard, PostRole::Editor, &pool).await?;
+    let thumbnails = AttachmentThumbnail::get_thumbnails_by_attachment(&attachment.id, &pool)
+        .await
+        .unwrap_or_default();
+
+    match Attachment::delete_attachment_by_id(&attachment.id, &pool).await {
+        Ok(0) => {
+            error!("Delete failed");
+            Err(AttachmentError::NotFound)
+        }
+        Err(err) => {
+            error!("Error deleted attachment data: {:?}", err);
+            Err(AttachmentError::InternalError)
+        }
+        Ok(_) => {
+            let storage_keys = std::iter::once(attachment.storage_key).chain(
+                thumbnails
+                    .into_iter()
+                    .map(|thumbnail| thumbnail.storage_key),
+            );
+            for storage_key in storage_keys {
+                if let Err(err) = store.delete(&storage_key).await {
+                    error!("Error deleting attachment blob: {:?}", err);
+                }
+            }
+
+            Ok(HttpResponse::Ok()
+                .content_type(ContentType::json())
+                .body("deleted attachment data"))
+        }
+    }
+}
+
+pub async fn get_thumbnail(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    query: Query<ThumbnailQuery>,
+    pool: Data<PgPool>,
+    store: Data<dyn BlobStore>,
+) -> Result<HttpResponse, AttachmentError> {
+    let attachment_id = path_param(&req, "id")?;
+    let size = query.size.unwrap_or(ThumbnailSize::Medium);
+
+    let attachment = Attachment::get_attachment_by_attachment_id(attachment_id, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get attachment data: {:?}", err);
+            AttachmentError::NotFound
+        })?;
+    authorize(
+        &attachment.post_id,
+        &auth_guard.user_id,
+        PostRole::Viewer,
+        &pool,
+    )
+    .await?;
+
+    // 이미지가 아니거나 아직 백그라운드에서 생성 중이면 없음
+    let thumbnail = AttachmentThumbnail::get_thumbnail(&attachment.id, size.as_str(), &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get thumbnail data: {:?}", err);
+            AttachmentError::NotFound
+        })?;
+
+    let file_name = format!("{}-{}", size.as_str(), attachment.file_name);
+    if let Some(url) =
+        store.presigned_download_url(&thumbnail.storage_key, &file_name, &thumbnail.content_type)
+    {
+        return Ok(HttpResponse::TemporaryRedirect()
+            .insert_header((header::LOCATION, url))
+            .finish());
+    }
+
+    let data = store
+        .get(&thumbnail.storage_key, None)
+        .await
+        .map_err(|err| {
+            error!("Error reading thumbnail blob: {:?}", err);
+            AttachmentError::InternalError
+        })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(thumbnail.content_type)
+        .insert_header((header::CACHE_CONTROL, "private, max-age=86400"))
+        .body(data))
+}
@@ -0,0 +1,159 @@
+use crate::database::PgPool;
+use crate::schema::{attachment_thumbnails, attachments};
+use actix_web::web::Data;
+use diesel::prelude::*;
+use diesel::{result::Error, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::attachments)]
+pub struct Attachment {
+    pub id: String,
+    pub post_id: String,
+    pub user_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    // 저장소 내부 경로는 클라이언트에 노출하지 않음
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::attachment_thumbnails)]
+pub struct AttachmentThumbnail {
+    pub attachment_id: String,
+    pub size: String,
+    pub width: i32,
+    pub height: i32,
+    pub content_type: String,
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::attachment_thumbnails)]
+pub struct AttachmentThumbnailData {
+    pub attachment_id: String,
+    pub size: String,
+    pub width: i32,
+    pub height: i32,
+    pub content_type: String,
+    pub storage_key: String,
+}
+
+// 클라이언트 응답용: 저장소 서명 URL 또는 API 다운로드 경로 포함
+#[derive(Serialize, Debug, Clone)]
+pub struct AttachmentResponse {
+    #[serde(flatten)]
+    pub attachment: Attachment,
+    pub download_url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::attachments)]
+pub struct AttachmentData {
+    pub id: String,
+    pub post_id: String,
+    pub user_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub storage_key: String,
+}
+
+impl Attachment {
+    pub async fn get_attachments_by_post(
+        post_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Attachment>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        attachments::table
+            .filter(attachments::post_id.eq(post_id))
+            .order(attachments::created_at.asc())
+            .load::<Attachment>(conn)
+    }
+
+    pub async fn get_attachment_by_id(
+        post_id: &str,
+        attachment_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Attachment, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        attachments::table
+            .find(attachment_id)
+            .filter(attachments::post_id.eq(post_id))
+            .get_result::<Attachment>(conn)
+    }
+
+    pub async fn get_attachment_by_attachment_id(
+        attachment_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Attachment, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        attachments::table
+            .find(attachment_id)
+            .get_result::<Attachment>(conn)
+    }
+
+    pub async fn delete_attachment_by_id(
+        attachment_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::delete(attachments::table.find(attachment_id)).execute(conn)
+    }
+}
+
+impl AttachmentData {
+    pub async fn create_attachment(
+        attachment_data: AttachmentData,
+        pool: &Data<PgPool>,
+    ) -> Result<Attachment, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::insert_into(attachments::table)
+            .values(attachment_data)
+            .get_result::<Attachment>(conn)
+    }
+}
+
+impl AttachmentThumbnail {
+    pub async fn get_thumbnail(
+        attachment_id: &str,
+        thumbnail_size: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<AttachmentThumbnail, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        attachment_thumbnails::table
+            .find((attachment_id, thumbnail_size))
+            .get_result::<AttachmentThumbnail>(conn)
+    }
+
+    pub async fn get_thumbnails_by_attachment(
+        attachment_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<AttachmentThumbnail>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        attachment_thumbnails::table
+            .filter(attachment_thumbnails::attachment_id.eq(attachment_id))
+            .load::<AttachmentThumbnail>(conn)
+    }
+}
+
+impl AttachmentThumbnailData {
+    pub async fn create_thumbnail(
+        thumbnail_data: AttachmentThumbnailData,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::insert_into(attachment_thumbnails::table)
+            .values(thumbnail_data)
+            .on_conflict_do_nothing()
+            .execute(conn)
+    }
+}
@@ -0,0 +1,20 @@
+use crate::api::attachment::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(handler::get_attachments))
+            .route(web::post().to(handler::upload_attachment)),
+    );
+
+    cfg.service(
+        web::resource("/{attachment_id}")
+            .route(web::get().to(handler::download_attachment))
+            .route(web::delete().to(handler::delete_attachment)),
+    );
+}
+
+pub fn configure_attachments(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/{id}/thumbnail").route(web::get().to(handler::get_thumbnail)));
+}
@@ -0,0 +1,202 @@
+use std::io::Cursor;
+
+use super::model::{Attachment, AttachmentThumbnailData};
+use crate::database::PgPool;
+use crate::storage::BlobStore;
+use actix_web::rt::task::spawn_blocking;
+use actix_web::web::Data;
+use bytes::Bytes;
+use image::codecs::jpeg::JpegEncoder;
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, ImageResult};
+use img_parts::{jpeg::markers, DynImage, ImageEXIF};
+use log::{error, info};
+use serde::Deserialize;
+
+// 썸네일을 만들 수 있는 이미지 타입
+const SUPPORTED_CONTENT_TYPES: [&str; 4] = ["image/jpeg", "image/png", "image/gif", "image/webp"];
+
+const JPEG_QUALITY: u8 = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbnailSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl ThumbnailSize {
+    pub const ALL: [ThumbnailSize; 3] = [
+        ThumbnailSize::Small,
+        ThumbnailSize::Medium,
+        ThumbnailSize::Large,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThumbnailSize::Small => "small",
+            ThumbnailSize::Medium => "medium",
+            ThumbnailSize::Large => "large",
+        }
+    }
+
+    // 긴 변 기준 최대 픽셀
+    pub fn max_dimension(&self) -> u32 {
+        match self {
+            ThumbnailSize::Small => 128,
+            ThumbnailSize::Medium => 512,
+            ThumbnailSize::Large => 1024,
+        }
+    }
+}
+
+pub struct RenderedThumbnail {
+    pub size: ThumbnailSize,
+    pub width: u32,
+    pub height: u32,
+    pub content_type: &'static str,
+    pub data: Vec<u8>,
+}
+
+pub fn is_supported_image(content_type: &str) -> bool {
+    SUPPORTED_CONTENT_TYPES.contains(&content_type)
+}
+
+fn decode_with_orientation(data: &[u8]) -> ImageResult<(DynamicImage, Orientation)> {
+    let mut decoder = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()?
+        .into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let image = DynamicImage::from_decoder(decoder)?;
+
+    Ok((image, orientation))
+}
+
+// EXIF(촬영 위치, 기기 정보 등) 메타데이터 제거
+// 회전 정보가 있으면 픽셀에 회전을 적용해 다시 인코딩하고, 없으면 무손실로 메타데이터만 제거
+pub fn strip_metadata(data: Bytes) -> Bytes {
+    if let Ok((mut image, orientation)) = decode_with_orientation(&data) {
+        if orientation != Orientation::NoTransforms {
+            image.apply_orientation(orientation);
+
+            match image::guess_format(&data) {
+                Ok(ImageFormat::Jpeg) | Err(_) => {
+                    if let Ok(encoded) = encode_jpeg(&image, 90) {
+                        return Bytes::from(encoded);
+                    }
+                }
+                Ok(format) => {
+                    let mut buf = Cursor::new(Vec::new());
+                    if image.write_to(&mut buf, format).is_ok() {
+                        return Bytes::from(buf.into_inner());
+                    }
+                }
+            }
+        }
+    }
+
+    match DynImage::from_bytes(data.clone()) {
+        Ok(Some(DynImage::Jpeg(mut jpeg))) => {
+            // APP1 세그먼트에 EXIF와 XMP가 들어있음
+            jpeg.remove_segments_by_marker(markers::APP1);
+            jpeg.encoder().bytes()
+        }
+        Ok(Some(mut image)) => {
+            image.set_exif(None);
+            image.encoder().bytes()
+        }
+        _ => data,
+    }
+}
+
+fn encode_jpeg(image: &DynamicImage, quality: u8) -> ImageResult<Vec<u8>> {
+    let mut buf = Vec::new();
+    let encoder = JpegEncoder::new_with_quality(&mut buf, quality);
+    DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(encoder)?;
+    Ok(buf)
+}
+
+pub fn render_thumbnails(data: &[u8]) -> ImageResult<Vec<RenderedThumbnail>> {
+    let (mut image, orientation) = decode_with_orientation(data)?;
+    image.apply_orientation(orientation);
+
+    ThumbnailSize::ALL
+        .iter()
+        .map(|size| {
+            let max = size.max_dimension();
+            let resized = if image.width() <= max && image.height() <= max {
+                image.clone()
+            } else {
+                image.thumbnail(max, max)
+            };
+
+            // 투명도가 있으면 PNG, 아니면 JPEG
+            let (content_type, data) = if resized.color().has_alpha() {
+                let mut buf = Cursor::new(Vec::new());
+                resized.write_to(&mut buf, ImageFormat::Png)?;
+                ("image/png", buf.into_inner())
+            } else {
+                ("image/jpeg", encode_jpeg(&resized, JPEG_QUALITY)?)
+            };
+
+            Ok(RenderedThumbnail {
+                size: *size,
+                width: resized.width(),
+                height: resized.height(),
+                content_type,
+                data,
+            })
+        })
+        .collect()
+}
+
+// 업로드 응답을 막지 않도록 썸네일 생성은 백그라운드에서 처리
+pub fn spawn_thumbnail_job(
+    attachment: Attachment,
+    data: Bytes,
+    pool: Data<PgPool>,
+    store: Data<dyn BlobStore>,
+) {
+    actix_web::rt::spawn(async move {
+        let thumbnails = match spawn_blocking(move || render_thumbnails(&data)).await {
+            Ok(Ok(thumbnails)) => thumbnails,
+            Ok(Err(err)) => {
+                error!(
+                    "Error rendering thumbnails for {}: {:?}",
+                    attachment.id, err
+                );
+                return;
+            }
+            Err(err) => {
+                error!("Thumbnail task for {} panicked: {:?}", attachment.id, err);
+                return;
+            }
+        };
+
+        for thumbnail in thumbnails {
+            let storage_key = format!("thumbnails/{}/{}", attachment.id, thumbnail.size.as_str());
+
+            if let Err(err) = store.put(&storage_key, Bytes::from(thumbnail.data)).await {
+                error!("Error storing thumbnail blob: {:?}", err);
+                continue;
+            }
+
+            let thumbnail_data = AttachmentThumbnailData {
+                attachment_id: attachment.id.clone(),
+                size: thumbnail.size.as_str().to_string(),
+                width: thumbnail.width as i32,
+                height: thumbnail.height as i32,
+                content_type: thumbnail.content_type.to_string(),
+                storage_key,
+            };
+
+            if let Err(err) = AttachmentThumbnailData::create_thumbnail(thumbnail_data, &pool).await
+            {
+                error!("Error created new thumbnail data: {:?}", err);
+            }
+        }
+
+        info!("Generated thumbnails for attachment {}", attachment.id);
+    });
+}
@@ -53,7 +53,7 @@ impl FromRequest for AuthenticationGuard {
             Ok(token) => {
                 let user_id = token.claims.sub.to_owned();
 
-                let user = User::get_users_auth(&user_id, &pool);
+                let user = User::get_users_auth(&user_id, pool);
 
                 if user.is_err() {
                     return ready(Err(ErrorUnauthorized(
@@ -61,7 +61,7 @@ impl FromRequest for AuthenticationGuard {
                     )));
                 }
 
-                ready(Ok(AuthenticationGuard { user_id: user_id }))
+                ready(Ok(AuthenticationGuard { user_id }))
             }
             Err(_) => ready(Err(ErrorUnauthorized(
                 json!({"status": "fail", "message": "Invalid token or usre doesn't exists"}),
@@ -58,8 +58,7 @@ pub async fn google_oauth_handler(
     let user_id: String;
 
     // if 문으로 유저가 존재하면 update, 없으면 insert
-    if user.is_ok() {
-        let user = user.unwrap();
+    if let Ok(user) = user {
         user_id = user.id;
 
         let user_data = UpdateUserData {
@@ -93,6 +92,7 @@ pub async fn google_oauth_handler(
             verified: google_user.verified_email,
             provider: "Google".to_string(),
             photo: google_user.picture,
+            handle: None,
         };
 
         let create_res = UserData::create_users(user_data, &pool).await;
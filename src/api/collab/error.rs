@@ -0,0 +1,66 @@
+use actix_web::{
+    error::ResponseError,
+    http::{header::ContentType, StatusCode},
+    HttpResponse,
+};
+use derive_more::{Display, Error};
+use serde::Serialize;
+
+use crate::api::permission::authorize::AuthorizeError;
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    code: u16,
+    message: String,
+}
+
+#[derive(Debug, Display, Error)]
+pub enum CollabError {
+    #[display(fmt = "internal error")]
+    InternalError,
+
+    #[display(fmt = "bad request")]
+    BadClientData,
+
+    #[display(fmt = "not found")]
+    NotFound,
+
+    #[display(fmt = "forbidden")]
+    Forbidden,
+
+    #[display(fmt = "unauthorized")]
+    Unauthorized,
+}
+
+impl ResponseError for CollabError {
+    fn error_response(&self) -> HttpResponse {
+        let error_response = ErrorResponse {
+            code: self.status_code().as_u16(),
+            message: self.to_string(),
+        };
+
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .json(error_response)
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match *self {
+            CollabError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            CollabError::BadClientData => StatusCode::BAD_REQUEST,
+            CollabError::NotFound => StatusCode::NOT_FOUND,
+            CollabError::Forbidden => StatusCode::FORBIDDEN,
+            CollabError::Unauthorized => StatusCode::UNAUTHORIZED,
+        }
+    }
+}
+
+impl From<AuthorizeError> for CollabError {
+    fn from(err: AuthorizeError) -> Self {
+        match err {
+            AuthorizeError::NotFound => CollabError::NotFound,
+            AuthorizeError::Forbidden => CollabError::Forbidden,
+            AuthorizeError::InternalError => CollabError::InternalError,
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::error::CollabError;
+use super::presence::{Presence, PresenceCommand};
+use super::room::{Frame, Room, Rooms};
+use super::ticket::{TicketResponse, Tickets, TICKET_EXPIRES_SECS};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::permission::authorize::authorize;
+use crate::api::permission::model::PostRole;
+use crate::api::user::model::User;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::ContentType,
+    web::{self, Data, Query},
+    HttpRequest, HttpResponse, Result,
+};
+use actix_ws::{AggregatedMessage, CloseCode, CloseReason, Session};
+use chrono::Utc;
+use log::{error, warn};
+use serde_json::{from_str, to_string, to_vec};
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+// 한 번에 받을 수 있는 웹소켓 메시지 크기 (기본 8MB)
+const MAX_MESSAGE_SIZE: usize = 8 * 1024 * 1024;
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, CollabError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        CollabError::BadClientData
+    })
+}
+
+// 브라우저 웹소켓은 헤더를 보낼 수 없어서 쿠키가 없으면 ?ticket= 의 일회용 티켓으로 인증
+// JWT는 접근 로그에 남지 않도록 URL로 받지 않음
+fn authenticate(
+    req: &HttpRequest,
+    post_id: &str,
+    auth_guard: Option<AuthenticationGuard>,
+    tickets: &Tickets,
+) -> Result<AuthenticationGuard, CollabError> {
+    if let Some(auth_guard) = auth_guard {
+        return Ok(auth_guard);
+    }
+
+    let query = Query::<HashMap<String, String>>::from_query(req.query_string())
+        .map_err(|_| CollabError::BadClientData)?;
+    let ticket = query.get("ticket").ok_or(CollabError::Unauthorized)?;
+
+    match tickets.redeem(ticket, post_id) {
+        Some(user_id) => Ok(AuthenticationGuard { user_id }),
+        None => Err(CollabError::Unauthorized),
+    }
+}
+
+// POST /api/posts/{id}/ws/ticket: 웹소켓 접속용 일회용 티켓 발급
+pub async fn create_ticket(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+    tickets: Data<Tickets>,
+) -> Result<HttpResponse, CollabError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Viewer, &pool).await?;
+
+    Ok(HttpResponse::Created().json(TicketResponse {
+        ticket: tickets.issue(post_id, &auth_guard.user_id),
+        expires_in: TICKET_EXPIRES_SECS,
+    }))
+}
+
+// GET /api/posts/{id}/ws: y-websocket 프로토콜로 노트 본문 공동 편집
+pub async fn connect(
+    req: HttpRequest,
+    body: web::Payload,
+    auth_guard: Option<AuthenticationGuard>,
+    pool: Data<PgPool>,
+    rooms: Data<Rooms>,
+    tickets: Data<Tickets>,
+) -> Result<HttpResponse, CollabError> {
+    let post_id = path_param(&req, "id")?;
+    let auth_guard = authenticate(&req, post_id, auth_guard, &tickets)?;
+    let role = authorize(post_id, &auth_guard.user_id, PostRole::Viewer, &pool).await?;
+    let user = User::get_users_by_id(&auth_guard.user_id, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get users by id data: {:?}", err);
+            CollabError::InternalError
+        })?;
+
+    let (room, connection_id) = rooms.join(post_id, &pool).await.map_err(|err| {
+        error!("Error opening collaborative document: {:?}", err);
+        CollabError::InternalError
+    })?;
+    let receiver = room.subscribe();
+
+    let (response, session, stream) = match actix_ws::handle(&req, body) {
+        Ok(handshake) => handshake,
+        Err(err) => {
+            rooms.leave(&room, connection_id);
+            error!("Error websocket handshake: {:?}", err);
+            return Err(CollabError::BadClientData);
+        }
+    };
+
+    let stream = stream
+        .max_frame_size(MAX_MESSAGE_SIZE)
+        .aggregate_continuations()
+        .max_continuation_size(MAX_MESSAGE_SIZE);
+
+    let presence = Presence {
+        connection_id,
+        user_id: user.id,
+        user_name: user.user_name,
+        photo: user.photo,
+        cursor: None,
+        joined_at: Utc::now().naive_utc(),
+    };
+
+    actix_web::rt::spawn(async move {
+        let reason = run_session(
+            &room,
+            presence,
+            role >= PostRole::Editor,
+            session.clone(),
+            stream,
+            receiver,
+        )
+        .await;
+        rooms.leave(&room, connection_id);
+        let _ = session.close(reason).await;
+    });
+
+    Ok(response)
+}
+
+async fn run_session(
+    room: &Arc<Room>,
+    presence: Presence,
+    can_edit: bool,
+    mut session: Session,
+    mut stream: actix_ws::AggregatedMessageStream,
+    mut receiver: Receiver<(u64, Frame)>,
+) -> Option<CloseReason> {
+    let connection_id = presence.connection_id;
+    let users = to_string(&room.add_presence(presence)).ok()?;
+    if session.text(users).await.is_err() || session.binary(room.sync_start()).await.is_err() {
+        return None;
+    }
+
+    loop {
+        tokio::select! {
+            message = stream.recv() => match message {
+                Some(Ok(AggregatedMessage::Binary(data))) => {
+                    match room.handle_message(connection_id, &data, can_edit) {
+                        Ok(replies) => {
+                            for reply in replies {
+                                if session.binary(reply).await.is_err() {
+                                    return None;
+                                }
+                            }
+                        }
+                        Err(err) => {
+                            warn!("Invalid collaborative message for post {}: {:?}", room.post_id, err);
+                            return Some(CloseReason::from((CloseCode::Invalid, err.to_string())));
+                        }
+                    }
+                }
+                Some(Ok(AggregatedMessage::Text(text))) => match from_str::<PresenceCommand>(&text) {
+                    Ok(PresenceCommand::Cursor { cursor }) => room.move_cursor(connection_id, cursor),
+                    Err(err) => warn!("Invalid presence message: {:?}", err),
+                },
+                Some(Ok(AggregatedMessage::Ping(data))) => {
+                    if session.pong(&data).await.is_err() {
+                        return None;
+                    }
+                }
+                Some(Ok(AggregatedMessage::Close(reason))) => return reason,
+                Some(Ok(_)) => {}
+                Some(Err(err)) => {
+                    warn!("Websocket protocol error: {:?}", err);
+                    return Some(CloseCode::Protocol.into());
+                }
+                None => return None,
+            },
+            broadcast = receiver.recv() => match broadcast {
+                Ok((sender, _)) if sender == connection_id => {}
+                Ok((_, Frame::Binary(data))) => {
+                    if session.binary(data).await.is_err() {
+                        return None;
+                    }
+                }
+                Ok((_, Frame::Text(text))) => {
+                    if session.text(text).await.is_err() {
+                        return None;
+                    }
+                }
+                // 너무 뒤처진 클라이언트는 끊고 재접속 시 다시 동기화
+                Err(RecvError::Lagged(_)) => return Some(CloseCode::Again.into()),
+                Err(RecvError::Closed) => return None,
+            },
+        }
+    }
+}
+
+// 웹소켓을 쓰지 않는 클라이언트용 현재 접속자 목록
+pub async fn get_presence(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+    rooms: Data<Rooms>,
+) -> Result<HttpResponse, CollabError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Viewer, &pool).await?;
+
+    let users = rooms
+        .get(post_id)
+        .map(|room| room.presence())
+        .unwrap_or_default();
+
+    let json_bytes = to_vec(&users).map_err(|err| {
+        error!("Failed to serialize presence to JSON: {:?}", err);
+        CollabError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
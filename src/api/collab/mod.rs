@@ -0,0 +1,7 @@
+pub mod error;
+pub mod handler;
+pub mod model;
+pub mod presence;
+pub mod room;
+pub mod route;
+pub mod ticket;
@@ -0,0 +1,87 @@
+use crate::database::PgPool;
+use crate::schema::post_documents::{self, dsl::*};
+use crate::schema::posts;
+use actix_web::web::Data;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel::{result::Error, Insertable, Queryable};
+
+// 공동 편집 문서(Yjs 업데이트)의 병합된 상태
+#[derive(Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::post_documents)]
+pub struct PostDocument {
+    pub post_id: String,
+    pub state: Vec<u8>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::post_documents)]
+pub struct PostDocumentData {
+    pub post_id: String,
+    pub state: Vec<u8>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+impl PostDocument {
+    pub async fn get_document(
+        document_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Option<PostDocument>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        post_documents
+            .find(document_id)
+            .get_result::<PostDocument>(conn)
+            .optional()
+    }
+
+    // 문서 상태와 노트 본문을 함께 저장, 노트가 삭제되었으면 false
+    pub async fn save_document(
+        document_data: PostDocumentData,
+        post_body: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<bool, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            let updated = diesel::update(posts::table.find(&document_data.post_id))
+                .set((
+                    posts::body.eq(post_body),
+                    posts::updated_at.eq(document_data.updated_at),
+                ))
+                .execute(conn)?;
+            if updated == 0 {
+                return Ok(false);
+            }
+
+            diesel::insert_into(post_documents)
+                .values(&document_data)
+                .on_conflict(post_documents::post_id)
+                .do_update()
+                .set((
+                    state.eq(excluded(state)),
+                    updated_at.eq(excluded(updated_at)),
+                ))
+                .execute(conn)?;
+
+            Ok(true)
+        })
+    }
+
+    // REST로 본문이 바뀌면 다음 편집 세션은 새 본문에서 시작
+    pub async fn delete_document(document_id: &str, pool: &Data<PgPool>) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::delete(post_documents.find(document_id)).execute(conn)
+    }
+}
+
+impl PostDocumentData {
+    pub fn new(document_id: &str, document_state: Vec<u8>) -> Self {
+        PostDocumentData {
+            post_id: document_id.to_string(),
+            state: document_state,
+            updated_at: Some(Utc::now().naive_utc()),
+        }
+    }
+}
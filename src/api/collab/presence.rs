@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+// 본문(Y.Text "body") 안의 커서/선택 영역, UTF-16 오프셋
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Cursor {
+    pub anchor: u32,
+    pub head: u32,
+}
+
+// 노트를 열어둔 접속 하나 (같은 사용자가 여러 탭을 열면 여러 개)
+#[derive(Serialize, Debug, Clone)]
+pub struct Presence {
+    pub connection_id: u64,
+    pub user_id: String,
+    pub user_name: String,
+    pub photo: String,
+    pub cursor: Option<Cursor>,
+    pub joined_at: chrono::NaiveDateTime,
+}
+
+// 서버 -> 클라이언트 텍스트 메시지
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PresenceEvent {
+    // 접속 직후 현재 접속자 목록
+    Sync {
+        connection_id: u64,
+        users: Vec<Presence>,
+    },
+    Join {
+        presence: Presence,
+    },
+    Leave {
+        connection_id: u64,
+        user_id: String,
+    },
+    Cursor {
+        connection_id: u64,
+        user_id: String,
+        cursor: Option<Cursor>,
+    },
+}
+
+// 클라이언트 -> 서버 텍스트 메시지
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PresenceCommand {
+    Cursor { cursor: Option<Cursor> },
+}
@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::model::{PostDocument, PostDocumentData};
+use super::presence::{Cursor, Presence, PresenceEvent};
+use crate::api::event::bus::EventBus;
+use crate::api::event::model::PostEventKind;
+use crate::api::post::model::Post;
+use crate::api::post::saved::after_post_saved;
+use crate::database::PgPool;
+use actix_web::web::Data;
+use bytes::Bytes;
+use log::{error, info};
+use serde_json::to_string;
+use tokio::sync::broadcast;
+use yrs::encoding::read::Cursor as ReadCursor;
+use yrs::sync::{Message, MessageReader, SyncMessage};
+use yrs::updates::decoder::{Decode, DecoderV1};
+use yrs::updates::encoder::Encode;
+use yrs::{
+    Doc, GetString, OffsetKind, Options, ReadTxn, StateVector, Text, TextRef, Transact, Update,
+};
+
+// 클라이언트는 ydoc.getText("body")로 노트 본문을 편집
+pub const BODY_TEXT: &str = "body";
+
+// 한 노트에 쌓아둘 수 있는 브로드캐스트 메시지 수 (느린 클라이언트는 재접속 후 다시 동기화)
+const ROOM_CHANNEL_CAPACITY: usize = 256;
+
+// REST 수정 등 서버에서 만든 업데이트의 발신자 id
+const SERVER_CONNECTION: u64 = 0;
+
+// 접속들에게 보내는 메시지: y-sync는 바이너리, 접속자 정보는 JSON 텍스트
+#[derive(Debug, Clone)]
+pub enum Frame {
+    Binary(Bytes),
+    Text(String),
+}
+
+// 노트 하나를 편집 중인 접속들이 공유하는 문서
+pub struct Room {
+    pub post_id: String,
+    doc: Doc,
+    body: TextRef,
+    sender: broadcast::Sender<(u64, Frame)>,
+    connections: AtomicUsize,
+    dirty: AtomicBool,
+    presence: Mutex<HashMap<u64, Presence>>,
+}
+
+impl Room {
+    fn new(
+        post_id: &str,
+        document: Option<PostDocument>,
+        post_body: &str,
+    ) -> Result<Self, yrs::sync::Error> {
+        // JS 클라이언트(Yjs)와 같은 UTF-16 오프셋 사용
+        let doc = Doc::with_options(Options {
+            offset_kind: OffsetKind::Utf16,
+            ..Options::default()
+        });
+        let body = doc.get_or_insert_text(BODY_TEXT);
+
+        {
+            let mut txn = doc.transact_mut();
+            match document {
+                Some(document) => txn.apply_update(Update::decode_v1(&document.state)?)?,
+                None => body.insert(&mut txn, 0, post_body),
+            }
+        }
+
+        let (sender, _) = broadcast::channel(ROOM_CHANNEL_CAPACITY);
+
+        Ok(Room {
+            post_id: post_id.to_string(),
+            doc,
+            body,
+            sender,
+            connections: AtomicUsize::new(0),
+            dirty: AtomicBool::new(false),
+            presence: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<(u64, Frame)> {
+        self.sender.subscribe()
+    }
+
+    // 다른 접속들에게 메시지 전달 (받는 쪽에서 자기 메시지는 무시)
+    pub fn broadcast(&self, connection_id: u64, frame: Frame) {
+        // 수신자가 없으면 에러지만 무시해도 됨
+        let _ = self.sender.send((connection_id, frame));
+    }
+
+    fn broadcast_event(&self, connection_id: u64, event: &PresenceEvent) {
+        match to_string(event) {
+            Ok(text) => self.broadcast(connection_id, Frame::Text(text)),
+            Err(err) => error!("Failed to serialize presence event: {:?}", err),
+        }
+    }
+
+    pub fn presence(&self) -> Vec<Presence> {
+        let mut users: Vec<Presence> = self.presence.lock().unwrap().values().cloned().collect();
+        users.sort_by_key(|presence| presence.connection_id);
+        users
+    }
+
+    // 접속자 등록 후 다른 접속들에 join 알림, 새 접속에는 현재 목록(sync) 반환
+    pub fn add_presence(&self, presence: Presence) -> PresenceEvent {
+        let connection_id = presence.connection_id;
+        self.presence
+            .lock()
+            .unwrap()
+            .insert(connection_id, presence.clone());
+        self.broadcast_event(connection_id, &PresenceEvent::Join { presence });
+
+        PresenceEvent::Sync {
+            connection_id,
+            users: self.presence(),
+        }
+    }
+
+    pub fn move_cursor(&self, connection_id: u64, cursor: Option<Cursor>) {
+        let user_id = {
+            let mut presence = self.presence.lock().unwrap();
+            match presence.get_mut(&connection_id) {
+                Some(presence) if presence.cursor != cursor => {
+                    presence.cursor = cursor;
+                    presence.user_id.clone()
+                }
+                _ => return,
+            }
+        };
+
+        self.broadcast_event(
+            connection_id,
+            &PresenceEvent::Cursor {
+                connection_id,
+                user_id,
+                cursor,
+            },
+        );
+    }
+
+    fn remove_presence(&self, connection_id: u64) {
+        let removed = self.presence.lock().unwrap().remove(&connection_id);
+        if let Some(presence) = removed {
+            self.broadcast_event(
+                connection_id,
+                &PresenceEvent::Leave {
+                    connection_id,
+                    user_id: presence.user_id,
+                },
+            );
+        }
+    }
+
+    // 접속 직후 보내는 sync step 1 (서버 상태 벡터)
+    pub fn sync_start(&self) -> Bytes {
+        let state_vector = self.doc.transact().state_vector();
+        Bytes::from(Message::Sync(SyncMessage::SyncStep1(state_vector)).encode_v1())
+    }
+
+    // y-sync 메시지 처리, 보낸 접속에 돌려줄 응답 반환
+    pub fn handle_message(
+        &self,
+        connection_id: u64,
+        data: &[u8],
+        can_edit: bool,
+    ) -> Result<Vec<Bytes>, yrs::sync::Error> {
+        let mut decoder = DecoderV1::new(ReadCursor::new(data));
+        let mut replies = Vec::new();
+
+        for message in MessageReader::new(&mut decoder) {
+            match message? {
+                Message::Sync(SyncMessage::SyncStep1(state_vector)) => {
+                    let update = self.doc.transact().encode_state_as_update_v1(&state_vector);
+                    replies.push(Bytes::from(
+                        Message::Sync(SyncMessage::SyncStep2(update)).encode_v1(),
+                    ));
+                }
+                Message::Sync(SyncMessage::SyncStep2(update))
+                | Message::Sync(SyncMessage::Update(update)) => {
+                    // viewer, commenter는 읽기 전용
+                    if !can_edit {
+                        continue;
+                    }
+                    self.apply_update(connection_id, update)?;
+                }
+                Message::Awareness(update) => {
+                    self.broadcast(
+                        connection_id,
+                        Frame::Binary(Bytes::from(Message::Awareness(update).encode_v1())),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        Ok(replies)
+    }
+
+    fn apply_update(&self, connection_id: u64, update: Vec<u8>) -> Result<(), yrs::sync::Error> {
+        {
+            let mut txn = self.doc.transact_mut();
+            txn.apply_update(Update::decode_v1(&update)?)?;
+        }
+        self.dirty.store(true, Ordering::SeqCst);
+        self.broadcast(
+            connection_id,
+            Frame::Binary(Bytes::from(
+                Message::Sync(SyncMessage::Update(update)).encode_v1(),
+            )),
+        );
+
+        Ok(())
+    }
+
+    // REST 수정으로 본문 전체가 바뀐 경우 편집 중인 문서에도 반영
+    pub fn replace_body(&self, post_body: &str) {
+        let update = {
+            let mut txn = self.doc.transact_mut();
+            let len = self.body.len(&txn);
+            self.body.remove_range(&mut txn, 0, len);
+            self.body.insert(&mut txn, 0, post_body);
+            txn.encode_update_v1()
+        };
+        self.dirty.store(true, Ordering::SeqCst);
+        self.broadcast(
+            SERVER_CONNECTION,
+            Frame::Binary(Bytes::from(
+                Message::Sync(SyncMessage::Update(update)).encode_v1(),
+            )),
+        );
+    }
+
+    // 변경된 문서를 posts.body와 post_documents에 저장, 저장했으면 true
+    async fn persist(&self, pool: &Data<PgPool>) -> bool {
+        if !self.dirty.swap(false, Ordering::SeqCst) {
+            return false;
+        }
+
+        let (document_state, post_body) = {
+            let txn = self.doc.transact();
+            (
+                txn.encode_state_as_update_v1(&StateVector::default()),
+                self.body.get_string(&txn),
+            )
+        };
+
+        match PostDocument::save_document(
+            PostDocumentData::new(&self.post_id, document_state),
+            &post_body,
+            pool,
+        )
+        .await
+        {
+            Ok(true) => true,
+            Ok(false) => {
+                info!("Post {} was deleted while editing", self.post_id);
+                false
+            }
+            Err(err) => {
+                error!("Error saving collaborative document: {:?}", err);
+                self.dirty.store(true, Ordering::SeqCst);
+                false
+            }
+        }
+    }
+}
+
+// 편집 중인 노트 목록 (post_id -> Room)
+#[derive(Clone)]
+pub struct Rooms {
+    rooms: Arc<Mutex<HashMap<String, Arc<Room>>>>,
+    next_connection: Arc<AtomicU64>,
+    events: EventBus,
+}
+
+impl Rooms {
+    pub fn new(events: EventBus) -> Self {
+        Rooms {
+            rooms: Arc::default(),
+            next_connection: Arc::default(),
+            events,
+        }
+    }
+
+    pub fn get(&self, post_id: &str) -> Option<Arc<Room>> {
+        self.rooms.lock().unwrap().get(post_id).cloned()
+    }
+
+    // 노트 문서에 접속, 처음 여는 노트면 DB에서 불러옴
+    pub async fn join(
+        &self,
+        post_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<(Arc<Room>, u64), yrs::sync::Error> {
+        let connection_id = self.next_connection.fetch_add(1, Ordering::SeqCst) + 1;
+
+        if let Some(room) = self.enter(post_id) {
+            return Ok((room, connection_id));
+        }
+
+        let post = Post::get_posts_load_by_id(post_id, pool)
+            .await
+            .map_err(|err| yrs::sync::Error::Other(Box::new(err)))?;
+        let document = PostDocument::get_document(post_id, pool)
+            .await
+            .map_err(|err| yrs::sync::Error::Other(Box::new(err)))?;
+        let loaded = Arc::new(Room::new(post_id, document, &post.body)?);
+
+        // 불러오는 사이 다른 접속이 먼저 만들었으면 그 문서를 사용
+        let (room, created) = {
+            let mut rooms = self.rooms.lock().unwrap();
+            let room = rooms.get(post_id).cloned();
+            let (room, created) = match room {
+                Some(room) => (room, false),
+                None => {
+                    rooms.insert(post_id.to_string(), loaded.clone());
+                    (loaded, true)
+                }
+            };
+            room.connections.fetch_add(1, Ordering::SeqCst);
+            (room, created)
+        };
+
+        if created {
+            actix_web::rt::spawn(self.clone().persist_loop(room.clone(), pool.clone()));
+        }
+
+        Ok((room, connection_id))
+    }
+
+    fn enter(&self, post_id: &str) -> Option<Arc<Room>> {
+        let rooms = self.rooms.lock().unwrap();
+        let room = rooms.get(post_id)?;
+        room.connections.fetch_add(1, Ordering::SeqCst);
+        Some(room.clone())
+    }
+
+    pub fn leave(&self, room: &Room, connection_id: u64) {
+        room.remove_presence(connection_id);
+        room.connections.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    // REST로 본문이 수정되면 열린 문서에 반영하고, 아니면 저장된 문서 상태를 버림
+    pub async fn reset_body(&self, post_id: &str, post_body: &str, pool: &Data<PgPool>) {
+        match self.get(post_id) {
+            Some(room) => room.replace_body(post_body),
+            None => {
+                if let Err(err) = PostDocument::delete_document(post_id, pool).await {
+                    error!("Error deleted collaborative document: {:?}", err);
+                }
+            }
+        }
+    }
+
+    // 주기적으로 변경 내용을 저장하고, 모든 접속이 끊기면 문서를 내림
+    async fn persist_loop(self, room: Arc<Room>, pool: Data<PgPool>) {
+        let interval = env::var("COLLAB_PERSIST_INTERVAL")
+            .ok()
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(2);
+
+        loop {
+            actix_web::rt::time::sleep(Duration::from_secs(interval)).await;
+            if room.persist(&pool).await {
+                // 여러 사람이 함께 고친 본문이라 멘션한 사람은 알 수 없음
+                after_post_saved(
+                    &room.post_id,
+                    None,
+                    PostEventKind::Updated,
+                    false,
+                    &self.events,
+                    &pool,
+                )
+                .await;
+            }
+
+            let mut rooms = self.rooms.lock().unwrap();
+            if room.connections.load(Ordering::SeqCst) == 0 && !room.dirty.load(Ordering::SeqCst) {
+                rooms.remove(&room.post_id);
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use crate::api::collab::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(handler::connect)));
+    cfg.service(web::resource("/ticket").route(web::post().to(handler::create_ticket)));
+}
+
+pub fn configure_presence(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(handler::get_presence)));
+}
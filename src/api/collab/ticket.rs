@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::{rngs::OsRng, RngCore};
+use serde::Serialize;
+
+// 티켓 유효 시간 (초)
+pub const TICKET_EXPIRES_SECS: u64 = 30;
+
+struct Ticket {
+    post_id: String,
+    user_id: String,
+    expires_at: Instant,
+}
+
+// 웹소켓 접속용 일회용 티켓 응답
+#[derive(Serialize, Debug)]
+pub struct TicketResponse {
+    pub ticket: String,
+    pub expires_in: u64,
+}
+
+// 브라우저 웹소켓은 헤더를 보낼 수 없어서 JWT 대신 URL에 실어 보내는 짧은 일회용 티켓
+// 협업 방(Rooms)과 같이 서버 메모리에만 둠
+#[derive(Default)]
+pub struct Tickets {
+    tickets: Mutex<HashMap<String, Ticket>>,
+}
+
+impl Tickets {
+    // 노트 하나, 사용자 하나에만 쓸 수 있는 티켓 발급
+    pub fn issue(&self, post_id: &str, user_id: &str) -> String {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let ticket = URL_SAFE_NO_PAD.encode(bytes);
+
+        let now = Instant::now();
+        let mut tickets = self.tickets.lock().unwrap();
+        // 쓰지 않고 만료된 티켓 정리
+        tickets.retain(|_, ticket| ticket.expires_at > now);
+        tickets.insert(
+            ticket.clone(),
+            Ticket {
+                post_id: post_id.to_string(),
+                user_id: user_id.to_string(),
+                expires_at: now + Duration::from_secs(TICKET_EXPIRES_SECS),
+            },
+        );
+        ticket
+    }
+
+    // 티켓은 한 번 쓰면 사라짐, 만료됐거나 다른 노트의 티켓이면 None
+    pub fn redeem(&self, ticket: &str, post_id: &str) -> Option<String> {
+        let ticket = self.tickets.lock().unwrap().remove(ticket)?;
+        if ticket.expires_at <= Instant::now() || ticket.post_id != post_id {
+            return None;
+        }
+        Some(ticket.user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redeems_a_ticket_once() {
+        let tickets = Tickets::default();
+        let ticket = tickets.issue("post", "user");
+
+        assert_eq!(tickets.redeem(&ticket, "post"), Some("user".to_string()));
+        assert_eq!(tickets.redeem(&ticket, "post"), None);
+    }
+
+    #[test]
+    fn rejects_a_ticket_for_another_post() {
+        let tickets = Tickets::default();
+        let ticket = tickets.issue("post", "user");
+
+        assert_eq!(tickets.redeem(&ticket, "other"), None);
+        assert_eq!(tickets.redeem(&ticket, "post"), None);
+    }
+}
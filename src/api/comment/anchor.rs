@@ -0,0 +1,63 @@
+// 글자 단위 [start, end) 범위의 텍스트, 범위가 잘못되면 None
+pub fn anchor_text(body: &str, start: i32, end: i32) -> Option<String> {
+    if start < 0 || end <= start || end as usize > body.chars().count() {
+        return None;
+    }
+
+    Some(
+        body.chars()
+            .skip(start as usize)
+            .take((end - start) as usize)
+            .collect(),
+    )
+}
+
+// 본문이 바뀐 뒤 댓글을 단 텍스트의 현재 위치, 원래 위치에 가장 가까운 곳
+// 본문에서 사라졌으면 None
+pub fn locate(body: &str, start: i32, end: i32, text: &str) -> Option<(i32, i32)> {
+    if anchor_text(body, start, end).as_deref() == Some(text) {
+        return Some((start, end));
+    }
+    if text.is_empty() {
+        return None;
+    }
+
+    let length = text.chars().count() as i32;
+    body.match_indices(text)
+        .map(|(index, _)| body[..index].chars().count() as i32)
+        .min_by_key(|found| (found - start).abs())
+        .map(|found| (found, found + length))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_characters_not_bytes() {
+        assert_eq!(anchor_text("한글 🎉 text", 3, 4).as_deref(), Some("🎉"));
+        assert_eq!(anchor_text("abc", 1, 1), None);
+        assert_eq!(anchor_text("abc", -1, 2), None);
+        assert_eq!(anchor_text("abc", 1, 4), None);
+    }
+
+    #[test]
+    fn keeps_an_anchor_that_did_not_move() {
+        assert_eq!(locate("hello world", 6, 11, "world"), Some((6, 11)));
+    }
+
+    #[test]
+    fn follows_moved_text_to_the_nearest_match() {
+        // 앞에 글자가 추가되어 뒤로 밀림
+        assert_eq!(locate("새 글 hello world", 6, 11, "world"), Some((10, 15)));
+        // 같은 텍스트가 여러 번 나오면 원래 위치에 가까운 것
+        assert_eq!(locate("ab ab ab", 4, 6, "ab"), Some((3, 5)));
+        assert_eq!(locate("ab\r\n🎉ab", 0, 2, "🎉ab"), Some((4, 7)));
+    }
+
+    #[test]
+    fn returns_none_when_text_is_gone() {
+        assert_eq!(locate("hello", 0, 5, "world"), None);
+        assert_eq!(locate("hello", 0, 0, ""), None);
+    }
+}
@@ -0,0 +1,377 @@
+use std::collections::HashMap;
+
+use super::anchor::{anchor_text, locate};
+use super::error::CommentError;
+use super::model::{
+    Anchor, Comment, CommentAuthor, CommentData, CommentListQuery, CommentRequest, CommentUpdate,
+    CommentView, MAX_COMMENT_LENGTH,
+};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::mention::model::Mention;
+use crate::api::notification::model::NotificationKind;
+use crate::api::notification::notify::{notify, Notice};
+use crate::api::page::template::summarize;
+use crate::api::permission::authorize::authorize;
+use crate::api::permission::model::PostRole;
+use crate::api::post::model::Post;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::ContentType,
+    web::{Data, Json, Query},
+    HttpRequest, HttpResponse, Result,
+};
+use diesel::result::Error as DieselError;
+use log::error;
+use serde_json::to_vec;
+use uuid::Uuid;
+
+// 알림에 넣을 댓글 길이
+const NOTICE_EXCERPT_LENGTH: usize = 100;
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, CommentError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        CommentError::BadClientData
+    })
+}
+
+fn json_response<T: serde::Serialize>(data: &T) -> Result<HttpResponse, CommentError> {
+    let json_bytes = to_vec(data).map_err(|err| {
+        error!("Failed to serialize comments to JSON: {:?}", err);
+        CommentError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
+
+fn internal_error(err: DieselError) -> CommentError {
+    error!("Error comment data: {:?}", err);
+    CommentError::InternalError
+}
+
+fn check_body(body: &str) -> Result<String, CommentError> {
+    let body = body.trim();
+    if body.is_empty() || body.chars().count() > MAX_COMMENT_LENGTH {
+        return Err(CommentError::ValidationError {
+            field: "body".to_string(),
+        });
+    }
+
+    Ok(body.to_string())
+}
+
+async fn load_post(post_id: &str, pool: &Data<PgPool>) -> Result<Post, CommentError> {
+    Post::get_posts_load_by_id(post_id, pool)
+        .await
+        .map_err(internal_error)
+}
+
+// 지운 댓글은 없는 것으로 취급
+async fn load_comment(
+    post_id: &str,
+    comment_id: &str,
+    pool: &Data<PgPool>,
+) -> Result<Comment, CommentError> {
+    match Comment::get_comment_by_id(post_id, comment_id, pool).await {
+        Ok(comment) if comment.deleted_at.is_none() => Ok(comment),
+        Ok(_) | Err(DieselError::NotFound) => Err(CommentError::NotFound),
+        Err(err) => Err(internal_error(err)),
+    }
+}
+
+// 본문이 바뀌었으면 댓글을 단 텍스트를 다시 찾음
+fn to_view(comment: Comment, author: Option<CommentAuthor>, post_body: &str) -> CommentView {
+    let anchor = match (
+        comment.anchor_start,
+        comment.anchor_end,
+        comment.anchor_text,
+    ) {
+        (Some(start), Some(end), Some(text)) => Some(match locate(post_body, start, end, &text) {
+            Some((start, end)) => Anchor {
+                start,
+                end,
+                text,
+                detached: false,
+            },
+            None => Anchor {
+                start,
+                end,
+                text,
+                detached: true,
+            },
+        }),
+        _ => None,
+    };
+    let deleted = comment.deleted_at.is_some();
+
+    CommentView {
+        id: comment.id,
+        post_id: comment.post_id,
+        parent_id: comment.parent_id,
+        body: comment.body,
+        author: if deleted { None } else { author },
+        anchor,
+        resolved_at: comment.resolved_at,
+        resolved_by: comment.resolved_by,
+        deleted,
+        created_at: comment.created_at,
+        updated_at: comment.updated_at,
+        replies: None,
+    }
+}
+
+// 노트를 볼 수 있는 사용자에게만 알림
+async fn notify_comment(
+    post: &Post,
+    comment: &Comment,
+    actor: &CommentAuthor,
+    pool: &Data<PgPool>,
+) {
+    let (recipients, action) = match &comment.parent_id {
+        Some(root_id) => match Comment::get_participants(root_id, pool).await {
+            Ok(participants) => (participants, "replied"),
+            Err(err) => {
+                error!("Error get comment participants: {:?}", err);
+                return;
+            }
+        },
+        None => (post.author_id.iter().cloned().collect(), "commented"),
+    };
+
+    let mut allowed = Vec::with_capacity(recipients.len());
+    for recipient in &recipients {
+        if authorize(&post.id, recipient, PostRole::Viewer, pool)
+            .await
+            .is_ok()
+        {
+            allowed.push(recipient.as_str());
+        }
+    }
+
+    let notice = Notice {
+        kind: NotificationKind::Comment,
+        post_id: Some(&post.id),
+        actor_id: Some(&actor.id),
+        message: format!(
+            "{} {} on \"{}\": {}",
+            actor.user_name,
+            action,
+            post.title,
+            summarize(&comment.body, NOTICE_EXCERPT_LENGTH)
+        ),
+    };
+    notify(&allowed, notice, pool).await;
+}
+
+// GET /api/posts/{id}/comments?resolved=: 스레드 목록 (답글은 replies)
+pub async fn get_comments(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    query: Query<CommentListQuery>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, CommentError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Viewer, &pool).await?;
+
+    let post = load_post(post_id, &pool).await?;
+    let list = Comment::get_comments(post_id, &pool)
+        .await
+        .map_err(internal_error)?;
+
+    let mut threads: Vec<CommentView> = Vec::new();
+    let mut replies: HashMap<String, Vec<CommentView>> = HashMap::new();
+    for (comment, author) in list {
+        match comment.parent_id.clone() {
+            Some(root_id) => {
+                replies
+                    .entry(root_id)
+                    .or_default()
+                    .push(to_view(comment, Some(author), &post.body))
+            }
+            None => {
+                if query
+                    .resolved
+                    .is_some_and(|resolved| resolved != comment.resolved_at.is_some())
+                {
+                    continue;
+                }
+                threads.push(to_view(comment, Some(author), &post.body));
+            }
+        }
+    }
+    for thread in &mut threads {
+        thread.replies = Some(replies.remove(&thread.id).unwrap_or_default());
+    }
+
+    json_response(&threads)
+}
+
+// POST /api/posts/{id}/comments: 댓글 또는 답글 달기 (commenter 이상)
+pub async fn create_comment(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: Json<CommentRequest>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, CommentError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Commenter, &pool).await?;
+
+    let request = _body.into_inner();
+    let comment_body = check_body(&request.body)?;
+    let post = load_post(post_id, &pool).await?;
+
+    // 답글은 스레드 첫 댓글에 달고, 본문 범위는 첫 댓글에만
+    let root_id = match &request.parent_id {
+        Some(parent_id) => {
+            let parent = match Comment::get_comment_by_id(post_id, parent_id, &pool).await {
+                Ok(parent) => parent,
+                Err(DieselError::NotFound) => {
+                    return Err(CommentError::ValidationError {
+                        field: "parent_id".to_string(),
+                    })
+                }
+                Err(err) => return Err(internal_error(err)),
+            };
+            if request.anchor.is_some() {
+                return Err(CommentError::ValidationError {
+                    field: "anchor".to_string(),
+                });
+            }
+            Some(parent.parent_id.unwrap_or(parent.id))
+        }
+        None => None,
+    };
+
+    let (anchor_start, anchor_end, text) = match request.anchor {
+        Some(anchor) => {
+            let text = anchor_text(&post.body, anchor.start, anchor.end).ok_or_else(|| {
+                CommentError::ValidationError {
+                    field: "anchor".to_string(),
+                }
+            })?;
+            (Some(anchor.start), Some(anchor.end), Some(text))
+        }
+        None => (None, None, None),
+    };
+
+    let comment_data = CommentData {
+        id: Uuid::new_v4().to_string(),
+        post_id: post_id.to_string(),
+        user_id: auth_guard.user_id.clone(),
+        parent_id: root_id,
+        body: comment_body,
+        anchor_start,
+        anchor_end,
+        anchor_text: text,
+    };
+    let comment = CommentData::create_comment(comment_data, &pool)
+        .await
+        .map_err(internal_error)?;
+    let author = Comment::get_author(&auth_guard.user_id, &pool)
+        .await
+        .map_err(internal_error)?;
+
+    notify_comment(&post, &comment, &author, &pool).await;
+    Mention::refresh_comment_mentions(&post, &comment, &auth_guard.user_id, &pool).await;
+
+    Ok(HttpResponse::Created().json(to_view(comment, Some(author), &post.body)))
+}
+
+// PUT /api/posts/{id}/comments/{comment_id}: 작성자만 수정
+pub async fn update_comment(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: Json<CommentUpdate>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, CommentError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Commenter, &pool).await?;
+
+    let comment = load_comment(post_id, path_param(&req, "comment_id")?, &pool).await?;
+    if comment.user_id != auth_guard.user_id {
+        return Err(CommentError::Forbidden);
+    }
+    let comment_body = check_body(&_body.body)?;
+
+    let comment = Comment::update_comment(&comment.id, &comment_body, &pool)
+        .await
+        .map_err(internal_error)?;
+    let author = Comment::get_author(&auth_guard.user_id, &pool)
+        .await
+        .map_err(internal_error)?;
+    let post = load_post(post_id, &pool).await?;
+    // 수정으로 새로 멘션된 사용자에게만 알림
+    Mention::refresh_comment_mentions(&post, &comment, &auth_guard.user_id, &pool).await;
+
+    json_response(&to_view(comment, Some(author), &post.body))
+}
+
+// DELETE /api/posts/{id}/comments/{comment_id}: 작성자만 삭제
+pub async fn delete_comment(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, CommentError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Viewer, &pool).await?;
+
+    let comment = load_comment(post_id, path_param(&req, "comment_id")?, &pool).await?;
+    if comment.user_id != auth_guard.user_id {
+        return Err(CommentError::Forbidden);
+    }
+
+    match Comment::delete_comment(&comment, &pool).await {
+        Ok(_) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("deleted comment")),
+        Err(err) => Err(internal_error(err)),
+    }
+}
+
+// 답글을 주면 그 스레드 전체를 해결/해결 취소
+async fn set_resolved(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+    resolved: bool,
+) -> Result<HttpResponse, CommentError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Commenter, &pool).await?;
+
+    let comment_id = path_param(&req, "comment_id")?;
+    let comment = match Comment::get_comment_by_id(post_id, comment_id, &pool).await {
+        Ok(comment) => comment,
+        Err(DieselError::NotFound) => return Err(CommentError::NotFound),
+        Err(err) => return Err(internal_error(err)),
+    };
+    let root_id = comment.parent_id.unwrap_or(comment.id);
+
+    let resolver = resolved.then_some(auth_guard.user_id.as_str());
+    let root = Comment::set_resolved(&root_id, resolver, &pool)
+        .await
+        .map_err(internal_error)?;
+    let author = Comment::get_author(&root.user_id, &pool).await.ok();
+    let post = load_post(post_id, &pool).await?;
+
+    json_response(&to_view(root, author, &post.body))
+}
+
+// POST /api/posts/{id}/comments/{comment_id}/resolve
+pub async fn resolve_comment(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, CommentError> {
+    set_resolved(req, auth_guard, pool, true).await
+}
+
+// POST /api/posts/{id}/comments/{comment_id}/unresolve
+pub async fn unresolve_comment(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, CommentError> {
+    set_resolved(req, auth_guard, pool, false).await
+}
@@ -0,0 +1,5 @@
+pub mod anchor;
+pub mod error;
+pub mod handler;
+pub mod model;
+pub mod route;
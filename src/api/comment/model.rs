@@ -0,0 +1,243 @@
+use std::collections::HashSet;
+
+use crate::database::PgPool;
+use crate::schema::comments::dsl::*;
+use crate::schema::users;
+use actix_web::web::Data;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::{result::Error, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+// 댓글 본문 최대 길이 (글자)
+pub const MAX_COMMENT_LENGTH: usize = 10_000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::comments)]
+pub struct Comment {
+    pub id: String,
+    pub post_id: String,
+    pub user_id: String,
+    pub parent_id: Option<String>,
+    pub body: String,
+    pub anchor_start: Option<i32>,
+    pub anchor_end: Option<i32>,
+    pub anchor_text: Option<String>,
+    pub resolved_at: Option<chrono::NaiveDateTime>,
+    pub resolved_by: Option<String>,
+    pub deleted_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::comments)]
+pub struct CommentData {
+    pub id: String,
+    pub post_id: String,
+    pub user_id: String,
+    pub parent_id: Option<String>,
+    pub body: String,
+    pub anchor_start: Option<i32>,
+    pub anchor_end: Option<i32>,
+    pub anchor_text: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Queryable)]
+pub struct CommentAuthor {
+    pub id: String,
+    pub user_name: String,
+    pub photo: String,
+}
+
+// 글자 단위 [start, end)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct AnchorRange {
+    pub start: i32,
+    pub end: i32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CommentRequest {
+    pub body: String,
+    // 답글이면 답할 댓글
+    pub parent_id: Option<String>,
+    pub anchor: Option<AnchorRange>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CommentUpdate {
+    pub body: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CommentListQuery {
+    pub resolved: Option<bool>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Anchor {
+    // 현재 본문에서의 위치, 텍스트가 사라졌으면 저장된 위치
+    pub start: i32,
+    pub end: i32,
+    pub text: String,
+    pub detached: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CommentView {
+    pub id: String,
+    pub post_id: String,
+    pub parent_id: Option<String>,
+    // 지운 댓글은 빈 문자열, author 없음
+    pub body: String,
+    pub author: Option<CommentAuthor>,
+    pub anchor: Option<Anchor>,
+    pub resolved_at: Option<chrono::NaiveDateTime>,
+    pub resolved_by: Option<String>,
+    pub deleted: bool,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replies: Option<Vec<CommentView>>,
+}
+
+impl Comment {
+    // 노트의 댓글과 작성자, 오래된 순
+    pub async fn get_comments(
+        comment_post_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<(Comment, CommentAuthor)>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        comments
+            .inner_join(users::table)
+            .filter(post_id.eq(comment_post_id))
+            .order((created_at.asc(), id.asc()))
+            .select((
+                crate::schema::comments::all_columns,
+                (users::id, users::user_name, users::photo),
+            ))
+            .load::<(Comment, CommentAuthor)>(conn)
+    }
+
+    pub async fn get_comment_by_id(
+        comment_post_id: &str,
+        comment_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Comment, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        comments
+            .find(comment_id)
+            .filter(post_id.eq(comment_post_id))
+            .get_result::<Comment>(conn)
+    }
+
+    pub async fn get_author(author_id: &str, pool: &Data<PgPool>) -> Result<CommentAuthor, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        users::table
+            .find(author_id)
+            .select((users::id, users::user_name, users::photo))
+            .get_result::<CommentAuthor>(conn)
+    }
+
+    pub async fn update_comment(
+        comment_id: &str,
+        comment_body: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Comment, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::update(comments.find(comment_id))
+            .set((body.eq(comment_body), updated_at.eq(Utc::now().naive_utc())))
+            .get_result::<Comment>(conn)
+    }
+
+    // 답글이 남은 스레드 첫 댓글은 내용만 지우고, 마지막 답글을 지우면 지워 둔 첫 댓글도 삭제
+    pub async fn delete_comment(comment: &Comment, pool: &Data<PgPool>) -> Result<(), Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            let replies = comments
+                .filter(parent_id.eq(&comment.id))
+                .count()
+                .get_result::<i64>(conn)?;
+
+            if replies > 0 {
+                let now = Utc::now().naive_utc();
+                diesel::update(comments.find(&comment.id))
+                    .set((body.eq(""), deleted_at.eq(now), updated_at.eq(now)))
+                    .execute(conn)?;
+                return Ok(());
+            }
+
+            diesel::delete(comments.find(&comment.id)).execute(conn)?;
+            if let Some(root_id) = &comment.parent_id {
+                let remaining = comments
+                    .filter(parent_id.eq(root_id))
+                    .count()
+                    .get_result::<i64>(conn)?;
+                if remaining == 0 {
+                    diesel::delete(comments.find(root_id).filter(deleted_at.is_not_null()))
+                        .execute(conn)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    // resolver가 없으면 해결 취소
+    pub async fn set_resolved(
+        comment_id: &str,
+        resolver: Option<&str>,
+        pool: &Data<PgPool>,
+    ) -> Result<Comment, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        let resolved = resolver.map(|_| Utc::now().naive_utc());
+        diesel::update(comments.find(comment_id))
+            .set((
+                resolved_at.eq(resolved),
+                resolved_by.eq(resolver),
+                updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .get_result::<Comment>(conn)
+    }
+
+    // 스레드에 댓글을 단 사용자 (첫 댓글 작성자 먼저)
+    pub async fn get_participants(
+        root_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<String>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        let authors = comments
+            .filter(id.eq(root_id).or(parent_id.eq(root_id)))
+            .filter(deleted_at.is_null())
+            .order(created_at.asc())
+            .select(user_id)
+            .load::<String>(conn)?;
+
+        let mut seen = HashSet::new();
+        Ok(authors
+            .into_iter()
+            .filter(|author| seen.insert(author.clone()))
+            .collect())
+    }
+}
+
+impl CommentData {
+    pub async fn create_comment(
+        comment_data: CommentData,
+        pool: &Data<PgPool>,
+    ) -> Result<Comment, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::insert_into(comments)
+            .values(&comment_data)
+            .get_result::<Comment>(conn)
+    }
+}
@@ -0,0 +1,24 @@
+use crate::api::comment::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(handler::get_comments))
+            .route(web::post().to(handler::create_comment)),
+    );
+
+    cfg.service(
+        web::resource("/{comment_id}")
+            .route(web::put().to(handler::update_comment))
+            .route(web::delete().to(handler::delete_comment)),
+    );
+
+    cfg.service(
+        web::resource("/{comment_id}/resolve").route(web::post().to(handler::resolve_comment)),
+    );
+
+    cfg.service(
+        web::resource("/{comment_id}/unresolve").route(web::post().to(handler::unresolve_comment)),
+    );
+}
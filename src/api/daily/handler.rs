@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use super::error::DailyError;
+use super::model::{Calendar, CalendarQuery, DailyNote, DailyNoteData, DailyQuery};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::event::bus::EventBus;
+use crate::api::event::model::PostEventKind;
+use crate::api::post::model::{Post, PostData};
+use crate::api::post::saved::after_post_saved;
+use crate::api::template::handler::load_template;
+use crate::api::template::variables::Context;
+use crate::api::user::model::User;
+use crate::api::workspace::model::WorkspaceRole;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::ContentType,
+    web::{Data, Query},
+    HttpRequest, HttpResponse, Result,
+};
+use chrono::{Datelike, Months, NaiveDate};
+use log::error;
+use serde_json::to_vec;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, DailyError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        DailyError::BadClientData
+    })
+}
+
+fn json_response<T: serde::Serialize>(data: &T) -> Result<HttpResponse, DailyError> {
+    let json_bytes = to_vec(data).map_err(|err| {
+        error!("Failed to serialize daily note data to JSON: {:?}", err);
+        DailyError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
+
+async fn get_user(user_id: &str, pool: &Data<PgPool>) -> Result<User, DailyError> {
+    User::get_users_by_id(user_id, pool).await.map_err(|err| {
+        error!("Error get users by id data: {:?}", err);
+        DailyError::InternalError
+    })
+}
+
+// today는 사용자 시간대 기준 오늘
+fn resolve_day(date: &str, user: &User) -> Result<NaiveDate, DailyError> {
+    if date == "today" {
+        return Ok(user.local_now().date());
+    }
+
+    NaiveDate::parse_from_str(date, DATE_FORMAT).map_err(|_| DailyError::ValidationError {
+        field: "date".to_string(),
+    })
+}
+
+// GET /api/daily/{YYYY-MM-DD|today}: 그날의 데일리 노트, 없으면 템플릿으로 만듦
+pub async fn get_daily_note(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    query: Query<DailyQuery>,
+    pool: Data<PgPool>,
+    events: Data<EventBus>,
+) -> Result<HttpResponse, DailyError> {
+    let user = get_user(&auth_guard.user_id, &pool).await?;
+    let day = resolve_day(path_param(&req, "date")?, &user)?;
+
+    let existing = DailyNote::get_daily_post(&user.id, day, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get daily note data: {:?}", err);
+            DailyError::InternalError
+        })?;
+    if let Some(post) = existing {
+        return json_response(&post);
+    }
+
+    // 직접 고른 템플릿은 쓸 수 없으면 에러, 설정된 템플릿은 쓸 수 없으면 빈 노트
+    let template = match (&query.template, &user.daily_template_id) {
+        (Some(template_id), _) => Some(
+            load_template(template_id, &user.id, WorkspaceRole::Viewer, &pool)
+                .await
+                .map_err(|_| DailyError::ValidationError {
+                    field: "template".to_string(),
+                })?,
+        ),
+        (None, Some(template_id)) => {
+            load_template(template_id, &user.id, WorkspaceRole::Viewer, &pool)
+                .await
+                .ok()
+        }
+        (None, None) => None,
+    };
+
+    // 템플릿의 {{date}}는 노트 날짜
+    let fields = HashMap::new();
+    let context = Context {
+        now: day.and_time(user.local_now().time()),
+        user_name: &user.user_name,
+        email: &user.email,
+        fields: &fields,
+    };
+    let default_title = day.format(DATE_FORMAT).to_string();
+    let (title, body) = match &template {
+        Some(template) if !template.title.trim().is_empty() => (
+            context.substitute(&template.title),
+            context.substitute(&template.body),
+        ),
+        Some(template) => (default_title, context.substitute(&template.body)),
+        None => (default_title, String::new()),
+    };
+
+    let post_data = PostData {
+        id: None,
+        title: title.chars().take(255).collect(),
+        body,
+        published: Some(false),
+        updated_at: None,
+        workspace_id: None,
+        author_id: None,
+        slug: None,
+        publish_at: None,
+        unpublish_at: None,
+    };
+    let post_id = PostData::create_posts(post_data, &user.id, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error created new post data: {:?}", err);
+            DailyError::InternalError
+        })?;
+
+    let daily_data = DailyNoteData {
+        user_id: user.id.clone(),
+        day,
+        post_id: post_id.clone(),
+    };
+    match DailyNoteData::create_daily_note(daily_data, &pool).await {
+        Ok(0) => {
+            // 동시에 들어온 요청이 먼저 만듦
+            if let Err(err) = Post::delete_posts_by_id(&post_id, &pool).await {
+                error!("Error deleted duplicate daily note: {:?}", err);
+            }
+            return match DailyNote::get_daily_post(&user.id, day, &pool).await {
+                Ok(Some(post)) => json_response(&post),
+                Ok(None) => Err(DailyError::InternalError),
+                Err(err) => {
+                    error!("Error get daily note data: {:?}", err);
+                    Err(DailyError::InternalError)
+                }
+            };
+        }
+        Ok(_) => {}
+        Err(err) => {
+            error!("Error created daily note data: {:?}", err);
+            return Err(DailyError::InternalError);
+        }
+    }
+
+    after_post_saved(
+        &post_id,
+        Some(&user.id),
+        PostEventKind::Created,
+        false,
+        &events,
+        &pool,
+    )
+    .await;
+
+    match Post::get_posts_load_by_id(&post_id, &pool).await {
+        Ok(post) => Ok(HttpResponse::Created().json(post)),
+        Err(err) => {
+            error!("Error get posts by id data: {:?}", err);
+            Err(DailyError::InternalError)
+        }
+    }
+}
+
+// GET /api/daily?month=YYYY-MM: 그 달에 데일리 노트가 있는 날
+pub async fn get_calendar(
+    auth_guard: AuthenticationGuard,
+    query: Query<CalendarQuery>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, DailyError> {
+    let user = get_user(&auth_guard.user_id, &pool).await?;
+
+    let first =
+        match &query.month {
+            Some(month) => NaiveDate::parse_from_str(&format!("{}-01", month), DATE_FORMAT)
+                .map_err(|_| DailyError::ValidationError {
+                    field: "month".to_string(),
+                })?,
+            None => user.local_now().date().with_day(1).unwrap_or_default(),
+        };
+    let next = first
+        .checked_add_months(Months::new(1))
+        .ok_or(DailyError::ValidationError {
+            field: "month".to_string(),
+        })?;
+
+    match DailyNote::get_calendar(&user.id, first, next, &pool).await {
+        Ok(days) => json_response(&Calendar {
+            month: first.format("%Y-%m").to_string(),
+            days,
+        }),
+        Err(err) => {
+            error!("Error get daily calendar data: {:?}", err);
+            Err(DailyError::InternalError)
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::api::post::model::Post;
+use crate::database::PgPool;
+use crate::schema::daily_notes::dsl::*;
+use crate::schema::posts;
+use actix_web::web::Data;
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel::{result::Error, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::daily_notes)]
+pub struct DailyNote {
+    pub user_id: String,
+    pub day: NaiveDate,
+    pub post_id: String,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::daily_notes)]
+pub struct DailyNoteData {
+    pub user_id: String,
+    pub day: NaiveDate,
+    pub post_id: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DailyQuery {
+    // 설정한 데일리 템플릿 대신 쓸 템플릿
+    pub template: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CalendarQuery {
+    // YYYY-MM, 없으면 사용자 시간대의 이번 달
+    pub month: Option<String>,
+}
+
+#[derive(Serialize, Debug, Queryable)]
+pub struct CalendarDay {
+    pub day: NaiveDate,
+    pub post_id: String,
+    pub title: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Calendar {
+    pub month: String,
+    pub days: Vec<CalendarDay>,
+}
+
+impl DailyNote {
+    pub async fn get_daily_post(
+        daily_user_id: &str,
+        daily_day: NaiveDate,
+        pool: &Data<PgPool>,
+    ) -> Result<Option<Post>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        daily_notes
+            .inner_join(posts::table)
+            .filter(user_id.eq(daily_user_id))
+            .filter(day.eq(daily_day))
+            .select(posts::all_columns)
+            .first::<Post>(conn)
+            .optional()
+    }
+
+    // [from, to) 사이에 데일리 노트가 있는 날
+    pub async fn get_calendar(
+        daily_user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<CalendarDay>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        daily_notes
+            .inner_join(posts::table)
+            .filter(user_id.eq(daily_user_id))
+            .filter(day.ge(from))
+            .filter(day.lt(to))
+            .order(day.asc())
+            .select((day, post_id, posts::title))
+            .load::<CalendarDay>(conn)
+    }
+}
+
+impl DailyNoteData {
+    // 같은 날 노트가 이미 있으면 0 반환
+    pub async fn create_daily_note(
+        daily_data: DailyNoteData,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::insert_into(daily_notes)
+            .values(&daily_data)
+            .on_conflict_do_nothing()
+            .execute(conn)
+    }
+}
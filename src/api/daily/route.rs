@@ -0,0 +1,8 @@
+use crate::api::daily::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(handler::get_calendar)));
+
+    cfg.service(web::resource("/{date}").route(web::get().to(handler::get_daily_note)));
+}
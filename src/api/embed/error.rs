@@ -0,0 +1,66 @@
+use actix_web::{
+    error::ResponseError,
+    http::{header::ContentType, StatusCode},
+    HttpResponse,
+};
+use derive_more::{Display, Error};
+use serde::Serialize;
+
+use crate::api::permission::authorize::AuthorizeError;
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    code: u16,
+    message: String,
+}
+
+#[derive(Debug, Display, Error)]
+pub enum EmbedError {
+    #[display(fmt = "internal error")]
+    InternalError,
+
+    #[display(fmt = "bad request")]
+    BadClientData,
+
+    #[display(fmt = "not found")]
+    NotFound,
+
+    #[display(fmt = "forbidden")]
+    Forbidden,
+
+    #[display(fmt = "Validation error on field: {}", field)]
+    ValidationError { field: String },
+}
+
+impl ResponseError for EmbedError {
+    fn error_response(&self) -> HttpResponse {
+        let error_response = ErrorResponse {
+            code: self.status_code().as_u16(),
+            message: self.to_string(),
+        };
+
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .json(error_response)
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match *self {
+            EmbedError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            EmbedError::BadClientData => StatusCode::BAD_REQUEST,
+            EmbedError::NotFound => StatusCode::NOT_FOUND,
+            EmbedError::Forbidden => StatusCode::FORBIDDEN,
+            EmbedError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<AuthorizeError> for EmbedError {
+    fn from(err: AuthorizeError) -> Self {
+        match err {
+            AuthorizeError::NotFound => EmbedError::NotFound,
+            AuthorizeError::Forbidden => EmbedError::Forbidden,
+            AuthorizeError::InternalError => EmbedError::InternalError,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use super::error::EmbedError;
+use super::model::Viewer;
+use super::render::render_post;
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::permission::authorize::authorize;
+use crate::api::permission::model::PostRole;
+use crate::api::post::model::Post;
+use crate::database::PgPool;
+use actix_web::{http::header::ContentType, web::Data, HttpRequest, HttpResponse, Result};
+use diesel::result::Error as DieselError;
+use log::error;
+use serde_json::to_vec;
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, EmbedError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        EmbedError::BadClientData
+    })
+}
+
+// GET /api/posts/{id}/render: ![[제목]] 임베드를 펼친 본문과 포함된 노트 목록
+pub async fn get_rendered_post(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, EmbedError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Viewer, &pool).await?;
+
+    let post = match Post::get_posts_load_by_id(post_id, &pool).await {
+        Ok(post) => post,
+        Err(DieselError::NotFound) => return Err(EmbedError::NotFound),
+        Err(err) => {
+            error!("Error get posts by id data: {:?}", err);
+            return Err(EmbedError::InternalError);
+        }
+    };
+
+    let rendered = render_post(&post, &Viewer::User(auth_guard.user_id), &pool).await?;
+
+    let json_bytes = to_vec(&rendered).map_err(|err| {
+        error!("Failed to serialize rendered post to JSON: {:?}", err);
+        EmbedError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
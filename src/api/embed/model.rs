@@ -0,0 +1,66 @@
+use serde::Serialize;
+
+// 임베드 안의 임베드를 펼치는 최대 깊이
+pub const MAX_EMBED_DEPTH: usize = 5;
+
+// 노트 하나를 렌더링할 때 펼치는 임베드 수 (같은 노트가 여러 번 나와도 각각 셈)
+pub const MAX_EMBEDS: usize = 50;
+
+// 누가 보는지에 따라 펼칠 수 있는 노트가 다름
+#[derive(Debug, Clone)]
+pub enum Viewer {
+    // 로그인한 사용자: viewer 이상 권한이 있는 노트
+    User(String),
+    // 공개 페이지, 피드: 공개된 노트
+    Public,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UnresolvedReason {
+    // 제목에 맞는 노트가 없거나 볼 수 없음
+    NotFound,
+    // 자기 자신이나 상위 노트를 다시 임베드
+    Cycle,
+    DepthLimit,
+    EmbedLimit,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Segment {
+    Text {
+        text: String,
+    },
+    Embed {
+        id: String,
+        title: String,
+        segments: Vec<Segment>,
+    },
+    // 펼치지 못한 임베드는 원문(![[제목]]) 유지
+    Unresolved {
+        raw: String,
+        title: String,
+        reason: UnresolvedReason,
+    },
+}
+
+// 렌더링 결과에 포함된 노트
+#[derive(Serialize, Debug, Clone)]
+pub struct Dependency {
+    pub id: String,
+    pub title: String,
+    // 1이면 직접 임베드
+    pub depth: usize,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RenderedPost {
+    pub id: String,
+    pub title: String,
+    // 임베드를 펼친 본문
+    pub body: String,
+    pub segments: Vec<Segment>,
+    pub dependencies: Vec<Dependency>,
+}
@@ -0,0 +1,196 @@
+use super::error::EmbedError;
+use super::model::{
+    Dependency, RenderedPost, Segment, UnresolvedReason, Viewer, MAX_EMBEDS, MAX_EMBED_DEPTH,
+};
+use crate::api::link::model::PostLink;
+use crate::api::link::parser::{split_embeds, BodyToken};
+use crate::api::page::template::{escape_html, render_body};
+use crate::api::permission::authorize::{authorize, AuthorizeError};
+use crate::api::permission::model::PostRole;
+use crate::api::post::model::Post;
+use crate::database::PgPool;
+use actix_web::web::Data;
+use diesel::result::Error as DieselError;
+use futures_util::future::{FutureExt, LocalBoxFuture};
+use log::error;
+
+fn internal_error(err: impl std::fmt::Debug) -> EmbedError {
+    error!("Error rendering embedded notes: {:?}", err);
+    EmbedError::InternalError
+}
+
+struct Expansion<'a> {
+    viewer: &'a Viewer,
+    pool: &'a Data<PgPool>,
+    // 지금 펼치고 있는 노트들 (순환 확인)
+    stack: Vec<String>,
+    embeds: usize,
+    dependencies: Vec<Dependency>,
+}
+
+impl Expansion<'_> {
+    async fn can_view(&self, post: &Post) -> Result<bool, EmbedError> {
+        match self.viewer {
+            Viewer::Public => Ok(post.published),
+            Viewer::User(user_id) => {
+                match authorize(&post.id, user_id, PostRole::Viewer, self.pool).await {
+                    Ok(_) => Ok(true),
+                    Err(AuthorizeError::InternalError) => Err(EmbedError::InternalError),
+                    Err(_) => Ok(false),
+                }
+            }
+        }
+    }
+
+    // 제목으로 임베드할 노트를 찾고 볼 수 있는지 확인
+    async fn load_embed(
+        &self,
+        source: &Post,
+        title: &str,
+    ) -> Result<Result<Post, UnresolvedReason>, EmbedError> {
+        let target_id = match PostLink::get_target(&source.id, title, self.pool)
+            .await
+            .map_err(internal_error)?
+        {
+            Some(target_id) => target_id,
+            None => return Ok(Err(UnresolvedReason::NotFound)),
+        };
+        if self.stack.contains(&target_id) {
+            return Ok(Err(UnresolvedReason::Cycle));
+        }
+
+        let target = match Post::get_posts_load_by_id(&target_id, self.pool).await {
+            Ok(target) => target,
+            Err(DieselError::NotFound) => return Ok(Err(UnresolvedReason::NotFound)),
+            Err(err) => return Err(internal_error(err)),
+        };
+        if !self.can_view(&target).await? {
+            return Ok(Err(UnresolvedReason::NotFound));
+        }
+
+        Ok(Ok(target))
+    }
+
+    fn expand<'b>(
+        &'b mut self,
+        post: &'b Post,
+    ) -> LocalBoxFuture<'b, Result<Vec<Segment>, EmbedError>> {
+        async move {
+            let depth = self.stack.len();
+            let mut segments = Vec::new();
+
+            for token in split_embeds(&post.body) {
+                let (raw, title) = match token {
+                    BodyToken::Text(text) => {
+                        segments.push(Segment::Text {
+                            text: text.to_string(),
+                        });
+                        continue;
+                    }
+                    BodyToken::Embed { raw, title } => (raw, title),
+                };
+                let unresolved = |reason| Segment::Unresolved {
+                    raw: raw.to_string(),
+                    title: title.clone(),
+                    reason,
+                };
+
+                if depth >= MAX_EMBED_DEPTH {
+                    segments.push(unresolved(UnresolvedReason::DepthLimit));
+                    continue;
+                }
+                if self.embeds >= MAX_EMBEDS {
+                    segments.push(unresolved(UnresolvedReason::EmbedLimit));
+                    continue;
+                }
+
+                let target = match self.load_embed(post, &title).await? {
+                    Ok(target) => target,
+                    Err(reason) => {
+                        segments.push(unresolved(reason));
+                        continue;
+                    }
+                };
+                self.embeds += 1;
+                if !self.dependencies.iter().any(|d| d.id == target.id) {
+                    self.dependencies.push(Dependency {
+                        id: target.id.clone(),
+                        title: target.title.clone(),
+                        depth: depth + 1,
+                        updated_at: target.updated_at.or(target.created_at),
+                    });
+                }
+
+                self.stack.push(target.id.clone());
+                let inner = self.expand(&target).await?;
+                self.stack.pop();
+
+                segments.push(Segment::Embed {
+                    id: target.id,
+                    title: target.title,
+                    segments: inner,
+                });
+            }
+
+            Ok(segments)
+        }
+        .boxed_local()
+    }
+}
+
+// 노트 본문의 ![[제목]]을 보는 사람이 볼 수 있는 노트 내용으로 펼침
+pub async fn render_post(
+    post: &Post,
+    viewer: &Viewer,
+    pool: &Data<PgPool>,
+) -> Result<RenderedPost, EmbedError> {
+    let mut expansion = Expansion {
+        viewer,
+        pool,
+        stack: vec![post.id.clone()],
+        embeds: 0,
+        dependencies: Vec::new(),
+    };
+    let segments = expansion.expand(post).await?;
+
+    Ok(RenderedPost {
+        id: post.id.clone(),
+        title: post.title.clone(),
+        body: segments_to_text(&segments),
+        segments,
+        dependencies: expansion.dependencies,
+    })
+}
+
+pub fn segments_to_text(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Text { text } => text.clone(),
+            Segment::Embed { segments, .. } => segments_to_text(segments),
+            Segment::Unresolved { raw, .. } => raw.clone(),
+        })
+        .collect()
+}
+
+// 공개 페이지/피드용 HTML, 임베드된 노트는 <section>으로 감쌈
+pub fn segments_to_html(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Text { text } => render_body(text),
+            Segment::Embed {
+                title, segments, ..
+            } => format!(
+                "<section class=\"embed\">\n<h2>{}</h2>\n{}\n</section>",
+                escape_html(title),
+                segments_to_html(segments)
+            ),
+            Segment::Unresolved { raw, .. } => {
+                format!("<p class=\"embed-missing\">{}</p>", escape_html(raw))
+            }
+        })
+        .filter(|html| !html.is_empty())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
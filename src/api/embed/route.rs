@@ -0,0 +1,6 @@
+use crate::api::embed::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(handler::get_rendered_post)));
+}
@@ -0,0 +1,80 @@
+use super::model::{PostEvent, PostEventData, PostEventKind};
+use crate::api::post::model::Post;
+use crate::api::webhook::dispatch;
+use crate::database::PgPool;
+use actix_web::web::Data;
+use log::error;
+use tokio::sync::broadcast;
+
+// 연결된 SSE 클라이언트 수와 무관하게 쌓아둘 수 있는 실시간 이벤트 수
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+// 노트 변경 이벤트를 로그에 기록하고 연결된 SSE 스트림과 웹훅에 전달
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<PostEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        EventBus { sender }
+    }
+}
+
+impl EventBus {
+    pub fn subscribe(&self) -> broadcast::Receiver<PostEvent> {
+        self.sender.subscribe()
+    }
+
+    // 이벤트 기록 실패가 노트 저장을 실패시키지 않도록 에러는 로그만 남김
+    pub async fn publish(&self, post_id: &str, kind: PostEventKind, pool: &Data<PgPool>) {
+        let recipients = match PostEvent::get_recipients(post_id, pool).await {
+            Ok(recipients) => recipients,
+            Err(err) => {
+                error!("Error get post event recipients: {:?}", err);
+                return;
+            }
+        };
+
+        match Post::get_posts_load_by_id(post_id, pool).await {
+            Ok(post) => dispatch::enqueue(&post, kind, &recipients, pool).await,
+            Err(err) => error!("Error get posts by id data: {:?}", err),
+        }
+        if kind.is_streamed() {
+            self.record(post_id, kind, recipients, pool).await;
+        }
+    }
+
+    // 삭제처럼 권한 정보와 노트가 사라지는 경우 미리 구한 수신자와 노트로 기록
+    pub async fn publish_to(
+        &self,
+        post: &Post,
+        kind: PostEventKind,
+        recipients: Vec<String>,
+        pool: &Data<PgPool>,
+    ) {
+        dispatch::enqueue(post, kind, &recipients, pool).await;
+        if kind.is_streamed() {
+            self.record(&post.id, kind, recipients, pool).await;
+        }
+    }
+
+    async fn record(
+        &self,
+        post_id: &str,
+        kind: PostEventKind,
+        recipients: Vec<String>,
+        pool: &Data<PgPool>,
+    ) {
+        match PostEventData::create_events(post_id, kind, recipients, pool).await {
+            Ok(events) => {
+                for event in events {
+                    // 구독자가 없으면 에러지만 무시해도 됨
+                    let _ = self.sender.send(event);
+                }
+            }
+            Err(err) => error!("Error created post event data: {:?}", err),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use actix_web::{
+    error::ResponseError,
+    http::{header::ContentType, StatusCode},
+    HttpResponse,
+};
+use derive_more::{Display, Error};
+use serde::Serialize;
+
+use crate::api::permission::authorize::AuthorizeError;
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    code: u16,
+    message: String,
+}
+
+#[derive(Debug, Display, Error)]
+pub enum EventError {
+    #[display(fmt = "internal error")]
+    InternalError,
+
+    #[display(fmt = "bad request")]
+    BadClientData,
+
+    #[display(fmt = "not found")]
+    NotFound,
+
+    #[display(fmt = "forbidden")]
+    Forbidden,
+
+    #[display(fmt = "Validation error on field: {}", field)]
+    ValidationError { field: String },
+}
+
+impl ResponseError for EventError {
+    fn error_response(&self) -> HttpResponse {
+        let error_response = ErrorResponse {
+            code: self.status_code().as_u16(),
+            message: self.to_string(),
+        };
+
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .json(error_response)
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match *self {
+            EventError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            EventError::BadClientData => StatusCode::BAD_REQUEST,
+            EventError::NotFound => StatusCode::NOT_FOUND,
+            EventError::Forbidden => StatusCode::FORBIDDEN,
+            EventError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<AuthorizeError> for EventError {
+    fn from(err: AuthorizeError) -> Self {
+        match err {
+            AuthorizeError::NotFound => EventError::NotFound,
+            AuthorizeError::Forbidden => EventError::Forbidden,
+            AuthorizeError::InternalError => EventError::InternalError,
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+
+use super::bus::EventBus;
+use super::error::EventError;
+use super::model::{EventCursor, PostEvent, EVENT_BACKLOG_LIMIT};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::{CacheControl, CacheDirective},
+    web::{Data, Query},
+    HttpRequest, HttpResponse, Result,
+};
+use bytes::Bytes;
+use futures_util::stream;
+use log::error;
+use serde_json::to_string;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+// 프록시가 연결을 끊지 않도록 보내는 주석 메시지 간격
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+const LAST_EVENT_ID_HEADER: &str = "Last-Event-ID";
+
+// 중복 전송을 막으려고 기억하는 최근 전송 이벤트 수
+const DELIVERED_LIMIT: usize = 4096;
+
+struct EventStream {
+    user_id: String,
+    // 이 위치 이전의 이벤트는 모두 보냄 (재접속하면 여기부터 다시 받음)
+    cursor: EventCursor,
+    // 지난 이벤트를 다 보내면 cursor를 옮길 위치
+    horizon: EventCursor,
+    backlog: VecDeque<PostEvent>,
+    // 로그에 아직 불러오지 않은 이벤트가 남아있는지
+    has_more: bool,
+    // id는 커밋 순서와 다를 수 있어 가장 큰 id 대신 최근에 보낸 id로 중복을 거름
+    delivered: HashSet<i64>,
+    delivered_order: VecDeque<i64>,
+    receiver: Receiver<PostEvent>,
+    pool: Data<PgPool>,
+}
+
+// 재접속한 EventSource는 Last-Event-ID 헤더를, 처음 접속하는 클라이언트는 ?last_event_id= 사용
+fn last_event_id(req: &HttpRequest) -> Result<Option<EventCursor>, EventError> {
+    let header = req
+        .headers()
+        .get(LAST_EVENT_ID_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_string);
+    let query = Query::<HashMap<String, String>>::from_query(req.query_string())
+        .ok()
+        .and_then(|query| query.get("last_event_id").cloned());
+
+    match header.or(query) {
+        Some(value) => {
+            value
+                .trim()
+                .parse::<EventCursor>()
+                .map(Some)
+                .map_err(|_| EventError::ValidationError {
+                    field: LAST_EVENT_ID_HEADER.to_string(),
+                })
+        }
+        None => Ok(None),
+    }
+}
+
+fn format_event(event: &PostEvent, cursor: EventCursor) -> Option<Bytes> {
+    match to_string(event) {
+        Ok(data) => Some(Bytes::from(format!(
+            "id: {}\nevent: {}\ndata: {}\n\n",
+            cursor, event.kind, data
+        ))),
+        Err(err) => {
+            error!("Failed to serialize post event to JSON: {:?}", err);
+            None
+        }
+    }
+}
+
+impl EventStream {
+    async fn load_backlog(&mut self) -> Result<(), EventError> {
+        let horizon = PostEvent::get_event_horizon(&self.pool)
+            .await
+            .map_err(|err| {
+                error!("Error get event horizon: {:?}", err);
+                EventError::InternalError
+            })?;
+        let events = PostEvent::get_events_since(&self.user_id, self.cursor, horizon, &self.pool)
+            .await
+            .map_err(|err| {
+                error!("Error get post events data: {:?}", err);
+                EventError::InternalError
+            })?;
+        self.has_more = events.len() as i64 == EVENT_BACKLOG_LIMIT;
+        self.horizon = match events.last() {
+            Some(event) if self.has_more => EventCursor::from(event),
+            _ => EventCursor::horizon(horizon),
+        };
+        self.backlog.extend(events);
+
+        Ok(())
+    }
+
+    // 처음 보내는 이벤트면 true
+    fn mark_delivered(&mut self, event_id: i64) -> bool {
+        if !self.delivered.insert(event_id) {
+            return false;
+        }
+        self.delivered_order.push_back(event_id);
+        if self.delivered_order.len() > DELIVERED_LIMIT {
+            if let Some(oldest) = self.delivered_order.pop_front() {
+                self.delivered.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+async fn next_event(mut state: EventStream) -> Option<(Result<Bytes, EventError>, EventStream)> {
+    loop {
+        if let Some(event) = state.backlog.pop_front() {
+            state.cursor = EventCursor::from(&event);
+            if state.backlog.is_empty() {
+                state.cursor = state.cursor.max(state.horizon);
+            }
+            // 지난 이벤트를 불러오는 사이 실시간으로도 받은 이벤트는 한 번만 전송
+            if !state.mark_delivered(event.id) {
+                continue;
+            }
+            match format_event(&event, state.cursor) {
+                Some(message) => return Some((Ok(message), state)),
+                None => continue,
+            }
+        }
+
+        if state.has_more {
+            if state.load_backlog().await.is_err() {
+                return None;
+            }
+            continue;
+        }
+
+        match actix_web::rt::time::timeout(KEEP_ALIVE_INTERVAL, state.receiver.recv()).await {
+            Err(_) => {
+                // 실시간으로 받은 이벤트가 모두 커밋됐는지 로그로 확인하고 이어받을 위치를 앞으로 옮김
+                if state.load_backlog().await.is_err() {
+                    return None;
+                }
+                return Some((Ok(Bytes::from_static(b": keep-alive\n\n")), state));
+            }
+            // 실시간 이벤트는 커밋 순서와 다르게 올 수 있어 cursor는 로그를 읽을 때만 옮김
+            Ok(Ok(event)) => {
+                if event.user_id == state.user_id && state.mark_delivered(event.id) {
+                    if let Some(message) = format_event(&event, state.cursor) {
+                        return Some((Ok(message), state));
+                    }
+                }
+            }
+            // 실시간 이벤트를 놓쳤으면 로그에서 다시 불러옴
+            Ok(Err(RecvError::Lagged(_))) => {
+                if state.load_backlog().await.is_err() {
+                    return None;
+                }
+            }
+            Ok(Err(RecvError::Closed)) => return None,
+        }
+    }
+}
+
+// GET /api/events: 접근 가능한 노트의 생성/수정/삭제 이벤트 (Server-Sent Events)
+pub async fn get_events(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+    bus: Data<EventBus>,
+) -> Result<HttpResponse, EventError> {
+    let last_event_id = last_event_id(&req)?;
+
+    // 지난 이벤트 조회 중에 생긴 이벤트를 놓치지 않도록 먼저 구독
+    let receiver = bus.subscribe();
+    let start = EventCursor::horizon(0);
+    let mut state = EventStream {
+        user_id: auth_guard.user_id,
+        cursor: last_event_id.unwrap_or(start),
+        horizon: start,
+        backlog: VecDeque::new(),
+        has_more: false,
+        delivered: HashSet::new(),
+        delivered_order: VecDeque::new(),
+        receiver,
+        pool,
+    };
+
+    match last_event_id {
+        Some(_) => state.load_backlog().await?,
+        // 처음 접속하면 지금 끝나지 않은 트랜잭션의 이벤트부터 받음
+        None => {
+            let horizon = PostEvent::get_event_horizon(&state.pool)
+                .await
+                .map_err(|err| {
+                    error!("Error get event horizon: {:?}", err);
+                    EventError::InternalError
+                })?;
+            state.cursor = EventCursor::horizon(horizon);
+            state.horizon = state.cursor;
+        }
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(stream::unfold(state, next_event)))
+}
@@ -0,0 +1,196 @@
+use crate::database::PgPool;
+use crate::schema::post_events::{self, dsl::*};
+use crate::schema::{post_permissions, posts, workspace_members};
+use actix_web::web::Data;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use diesel::{result::Error, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+// 한 번에 다시 보내주는 지난 이벤트 수
+pub const EVENT_BACKLOG_LIMIT: i64 = 1000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::post_events)]
+pub struct PostEvent {
+    pub id: i64,
+    #[serde(skip_serializing)]
+    pub user_id: String,
+    pub post_id: String,
+    pub kind: String,
+    #[serde(skip)]
+    pub xid: i64,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+// 이벤트 로그에서 이어받을 위치 (SSE id로 보내고 Last-Event-ID로 돌려받음)
+// 동기화 토큰처럼 (트랜잭션 id, 이벤트 id) 순서라 늦게 커밋된 이벤트도 건너뛰지 않음
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EventCursor {
+    pub xid: i64,
+    pub id: i64,
+}
+
+impl EventCursor {
+    // 끝나지 않은 트랜잭션이 없는 지점까지 모두 받았을 때의 위치
+    pub fn horizon(horizon: i64) -> Self {
+        EventCursor {
+            xid: horizon,
+            id: 0,
+        }
+    }
+}
+
+impl fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.xid, self.id)
+    }
+}
+
+impl FromStr for EventCursor {
+    type Err = ();
+
+    fn from_str(cursor: &str) -> Result<Self, Self::Err> {
+        let (event_xid, event_id) = cursor.split_once('.').ok_or(())?;
+        Ok(EventCursor {
+            xid: event_xid.parse().map_err(|_| ())?,
+            id: event_id.parse().map_err(|_| ())?,
+        })
+    }
+}
+
+impl From<&PostEvent> for EventCursor {
+    fn from(event: &PostEvent) -> Self {
+        EventCursor {
+            xid: event.xid,
+            id: event.id,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::post_events)]
+pub struct PostEventData {
+    pub user_id: String,
+    pub post_id: String,
+    pub kind: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostEventKind {
+    Created,
+    Updated,
+    Deleted,
+    // 비공개였던 노트가 공개됨 (updated와 함께 발생, 웹훅으로만 전달)
+    Published,
+}
+
+impl PostEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostEventKind::Created => "created",
+            PostEventKind::Updated => "updated",
+            PostEventKind::Deleted => "deleted",
+            PostEventKind::Published => "published",
+        }
+    }
+
+    // SSE와 이벤트 로그는 created, updated, deleted만 (published는 웹훅 전용)
+    pub fn is_streamed(&self) -> bool {
+        !matches!(self, PostEventKind::Published)
+    }
+}
+
+impl PostEvent {
+    // 이 값보다 작은 트랜잭션 id는 모두 끝났으므로 그 이벤트는 더 늦게 나타나지 않음
+    pub async fn get_event_horizon(pool: &Data<PgPool>) -> Result<i64, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::select(sql::<BigInt>(
+            "pg_snapshot_xmin(pg_current_snapshot())::text::bigint",
+        ))
+        .get_result::<i64>(conn)
+    }
+
+    // since 이후 horizon 전에 끝난 트랜잭션이 남긴 이벤트
+    pub async fn get_events_since(
+        event_user_id: &str,
+        since: EventCursor,
+        horizon: i64,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<PostEvent>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        post_events
+            .filter(user_id.eq(event_user_id))
+            .filter(xid.gt(since.xid).or(xid.eq(since.xid).and(id.gt(since.id))))
+            .filter(xid.lt(horizon))
+            .order((xid.asc(), id.asc()))
+            .limit(EVENT_BACKLOG_LIMIT)
+            .load::<PostEvent>(conn)
+    }
+
+    // 노트에 접근할 수 있는 사용자 (노트 권한 + 워크스페이스 멤버)
+    pub async fn get_recipients(
+        event_post_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<String>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        let mut recipients = post_permissions::table
+            .filter(post_permissions::post_id.eq(event_post_id))
+            .select(post_permissions::user_id)
+            .load::<String>(conn)?;
+
+        let workspace = posts::table
+            .find(event_post_id)
+            .select(posts::workspace_id)
+            .get_result::<Option<String>>(conn)
+            .optional()?
+            .flatten();
+
+        if let Some(workspace) = workspace {
+            recipients.extend(
+                workspace_members::table
+                    .filter(workspace_members::workspace_id.eq(workspace))
+                    .select(workspace_members::user_id)
+                    .load::<String>(conn)?,
+            );
+        }
+
+        recipients.sort();
+        recipients.dedup();
+
+        Ok(recipients)
+    }
+}
+
+impl PostEventData {
+    pub async fn create_events(
+        event_post_id: &str,
+        event_kind: PostEventKind,
+        recipients: Vec<String>,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<PostEvent>, Error> {
+        let rows: Vec<PostEventData> = recipients
+            .into_iter()
+            .map(|recipient| PostEventData {
+                user_id: recipient,
+                post_id: event_post_id.to_string(),
+                kind: event_kind.as_str().to_string(),
+            })
+            .collect();
+
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::insert_into(post_events::table)
+            .values(&rows)
+            .get_results::<PostEvent>(conn)
+    }
+}
@@ -0,0 +1,155 @@
+use std::env;
+use std::time::{Duration, SystemTime};
+
+use super::error::FeedError;
+use super::model::{FeedFormat, FeedQuery, FEED_SIZE};
+use super::render::{post_updated, Feed};
+use crate::api::embed::model::Viewer;
+use crate::api::embed::render::render_post;
+use crate::api::post::model::Post;
+use crate::api::user::model::User;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::{
+        self, CacheControl, CacheDirective, ETag, EntityTag, Header, HttpDate, IfModifiedSince,
+        IfNoneMatch, LastModified,
+    },
+    web::{Data, Query},
+    HttpRequest, HttpResponse, Result,
+};
+use diesel::result::Error as DieselError;
+use log::error;
+use sha2::{Digest, Sha256};
+
+// 피드 리더가 다시 확인하기 전까지 캐시해도 되는 시간 (초)
+const FEED_MAX_AGE: u32 = 300;
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, FeedError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        FeedError::BadClientData
+    })
+}
+
+// If-None-Match가 있으면 ETag로만, 없으면 If-Modified-Since로 판단
+fn is_not_modified(req: &HttpRequest, etag: &EntityTag, last_modified: Option<HttpDate>) -> bool {
+    if req.headers().contains_key(header::IF_NONE_MATCH) {
+        return match IfNoneMatch::parse(req) {
+            Ok(IfNoneMatch::Any) => true,
+            Ok(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(etag)),
+            Err(_) => false,
+        };
+    }
+
+    match (IfModifiedSince::parse(req), last_modified) {
+        (Ok(IfModifiedSince(since)), Some(last_modified)) => last_modified <= since,
+        _ => false,
+    }
+}
+
+// GET /feeds/{user}.rss
+pub async fn get_rss(
+    req: HttpRequest,
+    query: Query<FeedQuery>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, FeedError> {
+    get_feed(req, FeedFormat::Rss, query, pool).await
+}
+
+// GET /feeds/{user}.atom
+pub async fn get_atom(
+    req: HttpRequest,
+    query: Query<FeedQuery>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, FeedError> {
+    get_feed(req, FeedFormat::Atom, query, pool).await
+}
+
+async fn get_feed(
+    req: HttpRequest,
+    format: FeedFormat,
+    query: Query<FeedQuery>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, FeedError> {
+    let user_id = path_param(&req, "user")?;
+    let mode = query.mode()?;
+
+    let author = match User::get_users_by_id_or_handle(user_id, &pool).await {
+        Ok(author) => author,
+        Err(DieselError::NotFound) => return Err(FeedError::NotFound),
+        Err(err) => {
+            error!("Error get users by id data: {:?}", err);
+            return Err(FeedError::InternalError);
+        }
+    };
+    let posts = Post::get_published_posts_by_author(&author.id, FEED_SIZE, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get published posts data: {:?}", err);
+            FeedError::InternalError
+        })?;
+
+    // 피드에도 공개된 노트만 임베드
+    let mut entries = Vec::with_capacity(posts.len());
+    for post in posts {
+        let rendered = render_post(&post, &Viewer::Public, &pool)
+            .await
+            .map_err(|_| FeedError::InternalError)?;
+        entries.push((post, rendered));
+    }
+
+    let updated = entries
+        .iter()
+        .flat_map(|(post, rendered)| {
+            post_updated(post)
+                .into_iter()
+                .chain(rendered.dependencies.iter().filter_map(|d| d.updated_at))
+        })
+        .max()
+        .or(author.updated_at)
+        .or(author.created_at);
+
+    let api_origin = env::var("API_ORIGIN").unwrap_or_else(|_| "http://localhost:8080".to_string());
+    let self_url = format!("{}/feeds/{}.{}", api_origin, author.id, format.extension());
+    let feed = Feed {
+        format,
+        mode,
+        author: &author,
+        entries: &entries,
+        self_url,
+        api_origin,
+        updated,
+    }
+    .render();
+
+    // 같은 내용이면 같은 ETag (모드, 형식이 바뀌면 내용도 바뀜)
+    let etag = EntityTag::new_strong(hex::encode(&Sha256::digest(feed.as_bytes())[..16]));
+    // HTTP 날짜는 초 단위라 If-Modified-Since와 비교하려면 밀리초를 버림
+    let last_modified = updated.map(|updated| {
+        let seconds = updated.and_utc().timestamp().max(0) as u64;
+        HttpDate::from(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+    });
+    let cache_control = CacheControl(vec![
+        CacheDirective::Public,
+        CacheDirective::MaxAge(FEED_MAX_AGE),
+    ]);
+
+    let not_modified = is_not_modified(&req, &etag, last_modified);
+    let mut response = if not_modified {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::Ok()
+    };
+    response
+        .insert_header(ETag(etag))
+        .insert_header(cache_control);
+    if let Some(last_modified) = last_modified {
+        response.insert_header(LastModified(last_modified));
+    }
+
+    if not_modified {
+        return Ok(response.finish());
+    }
+
+    Ok(response.content_type(format.content_type()).body(feed))
+}
@@ -0,0 +1,55 @@
+use super::error::FeedError;
+use serde::Deserialize;
+
+// 피드 하나에 담는 최근 노트 수
+pub const FEED_SIZE: i64 = 20;
+
+// summary 모드에서 보여주는 본문 길이
+pub const SUMMARY_LENGTH: usize = 280;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+}
+
+impl FeedFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "rss",
+            FeedFormat::Atom => "atom",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedMode {
+    // 본문 전체
+    Full,
+    // 본문 앞부분만
+    Summary,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FeedQuery {
+    pub mode: Option<String>,
+}
+
+impl FeedQuery {
+    pub fn mode(&self) -> Result<FeedMode, FeedError> {
+        match self.mode.as_deref() {
+            None | Some("") | Some("full") => Ok(FeedMode::Full),
+            Some("summary") => Ok(FeedMode::Summary),
+            Some(_) => Err(FeedError::ValidationError {
+                field: "mode".to_string(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use super::model::{FeedFormat, FeedMode, SUMMARY_LENGTH};
+use crate::api::embed::model::RenderedPost;
+use crate::api::embed::render::segments_to_html;
+use crate::api::page::template::{escape_html, summarize};
+use crate::api::post::model::Post;
+use crate::api::user::model::User;
+use chrono::NaiveDateTime;
+
+pub struct Feed<'a> {
+    pub format: FeedFormat,
+    pub mode: FeedMode,
+    pub author: &'a User,
+    // 노트와 임베드를 펼친 본문
+    pub entries: &'a [(Post, RenderedPost)],
+    // 피드 자신의 주소
+    pub self_url: String,
+    pub api_origin: String,
+    // 가장 최근에 수정된 노트 시각 (임베드된 노트 포함, 노트가 없으면 작성자 정보 기준)
+    pub updated: Option<NaiveDateTime>,
+}
+
+// 수정된 적 없는 노트는 생성 시각
+pub fn post_updated(post: &Post) -> Option<NaiveDateTime> {
+    post.updated_at.or(post.created_at)
+}
+
+fn rfc2822(date: NaiveDateTime) -> String {
+    date.and_utc().to_rfc2822()
+}
+
+fn rfc3339(date: NaiveDateTime) -> String {
+    date.and_utc().format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+impl Feed<'_> {
+    fn post_url(&self, post: &Post) -> String {
+        format!(
+            "{}/p/{}/{}",
+            self.api_origin,
+            self.author.id,
+            post.slug.as_deref().unwrap_or_default()
+        )
+    }
+
+    // XML 안에 넣는 본문 (HTML은 한 번 더 이스케이프)
+    fn content(&self, rendered: &RenderedPost) -> String {
+        match self.mode {
+            FeedMode::Full => escape_html(&segments_to_html(&rendered.segments)),
+            FeedMode::Summary => escape_html(&summarize(&rendered.body, SUMMARY_LENGTH)),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        match self.format {
+            FeedFormat::Rss => self.render_rss(),
+            FeedFormat::Atom => self.render_atom(),
+        }
+    }
+
+    fn render_rss(&self) -> String {
+        let items: Vec<String> = self
+            .entries
+            .iter()
+            .map(|(post, rendered)| {
+                let mut item = format!(
+                    "<item>\n<title>{title}</title>\n<link>{link}</link>\n<guid isPermaLink=\"false\">urn:uuid:{id}</guid>\n<description>{content}</description>\n",
+                    title = escape_html(&post.title),
+                    link = escape_html(&self.post_url(post)),
+                    id = post.id,
+                    content = self.content(rendered),
+                );
+                if let Some(created_at) = post.created_at {
+                    item.push_str(&format!("<pubDate>{}</pubDate>\n", rfc2822(created_at)));
+                }
+                if let Some(updated) = post_updated(post) {
+                    item.push_str(&format!("<atom:updated>{}</atom:updated>\n", rfc3339(updated)));
+                }
+                item.push_str("</item>");
+                item
+            })
+            .collect();
+
+        let last_build_date = self
+            .updated
+            .map(|updated| format!("<lastBuildDate>{}</lastBuildDate>\n", rfc2822(updated)))
+            .unwrap_or_default();
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n<channel>\n<title>{name}</title>\n<link>{self_url}</link>\n<description>Published notes by {name}</description>\n<atom:link href=\"{self_url}\" rel=\"self\" type=\"application/rss+xml\"/>\n{last_build_date}{items}\n</channel>\n</rss>\n",
+            name = escape_html(&self.author.user_name),
+            self_url = escape_html(&self.self_url),
+            last_build_date = last_build_date,
+            items = items.join("\n"),
+        )
+    }
+
+    fn render_atom(&self) -> String {
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .map(|(post, rendered)| {
+                let content = match self.mode {
+                    FeedMode::Full => format!("<content type=\"html\">{}</content>", self.content(rendered)),
+                    FeedMode::Summary => format!("<summary type=\"text\">{}</summary>", self.content(rendered)),
+                };
+                let mut entry = format!(
+                    "<entry>\n<id>urn:uuid:{id}</id>\n<title type=\"text\">{title}</title>\n<link rel=\"alternate\" type=\"text/html\" href=\"{link}\"/>\n",
+                    id = post.id,
+                    title = escape_html(&post.title),
+                    link = escape_html(&self.post_url(post)),
+                );
+                if let Some(created_at) = post.created_at {
+                    entry.push_str(&format!("<published>{}</published>\n", rfc3339(created_at)));
+                }
+                if let Some(updated) = post_updated(post) {
+                    entry.push_str(&format!("<updated>{}</updated>\n", rfc3339(updated)));
+                }
+                entry.push_str(&content);
+                entry.push_str("\n</entry>");
+                entry
+            })
+            .collect();
+
+        // Atom은 updated가 필수
+        let updated = self
+            .updated
+            .map(rfc3339)
+            .unwrap_or_else(|| "1970-01-01T00:00:00Z".to_string());
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n<id>{self_url}</id>\n<title type=\"text\">{name}</title>\n<subtitle type=\"text\">Published notes by {name}</subtitle>\n<link rel=\"self\" type=\"application/atom+xml\" href=\"{self_url}\"/>\n<updated>{updated}</updated>\n<author>\n<name>{name}</name>\n</author>\n{entries}\n</feed>\n",
+            self_url = escape_html(&self.self_url),
+            name = escape_html(&self.author.user_name),
+            updated = updated,
+            entries = entries.join("\n"),
+        )
+    }
+}
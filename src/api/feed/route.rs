@@ -0,0 +1,7 @@
+use crate::api::feed::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/{user}.rss").route(web::get().to(handler::get_rss)))
+        .service(web::resource("/{user}.atom").route(web::get().to(handler::get_atom)));
+}
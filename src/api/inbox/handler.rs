@@ -0,0 +1,284 @@
+use super::error::InboxError;
+use super::model::{CreateInbox, Inbox, InboxData, InboxNote, InboxResponse, UpdateInbox};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::event::bus::EventBus;
+use crate::api::event::model::PostEventKind;
+use crate::api::permission::authorize::{authorize_workspace, AuthorizeError};
+use crate::api::post::model::{Post, PostData};
+use crate::api::post::saved::after_post_saved;
+use crate::api::workspace::model::WorkspaceRole;
+use crate::api::workspace::selector::WorkspaceSelector;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::ContentType,
+    web::{self, Bytes, Data},
+    HttpMessage, HttpRequest, HttpResponse, Result,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use diesel::result::Error as DieselError;
+use log::error;
+use rand::{rngs::OsRng, RngCore};
+use serde::Deserialize;
+use serde_json::to_vec;
+use uuid::Uuid;
+
+// 제목이 없을 때 본문 첫 줄에서 가져오는 길이
+const DERIVED_TITLE_LENGTH: usize = 100;
+
+// 본문만 보내는 클라이언트(텍스트 POST)는 ?title=로 제목 지정
+#[derive(Deserialize, Debug)]
+pub struct CaptureQuery {
+    pub title: Option<String>,
+}
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, InboxError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        InboxError::BadClientData
+    })
+}
+
+fn internal_error(err: DieselError) -> InboxError {
+    error!("Error inbox data: {:?}", err);
+    InboxError::InternalError
+}
+
+fn json_response<T: serde::Serialize>(data: &T) -> Result<HttpResponse, InboxError> {
+    let json_bytes = to_vec(data).map_err(|err| {
+        error!("Failed to serialize inbox data to JSON: {:?}", err);
+        InboxError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
+
+// 추측할 수 없는 256비트 랜덤 토큰
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn check_name(name: &str) -> Result<String, InboxError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > 255 {
+        return Err(InboxError::ValidationError {
+            field: "name".to_string(),
+        });
+    }
+    Ok(name.to_string())
+}
+
+async fn load_inbox(
+    req: &HttpRequest,
+    user_id: &str,
+    pool: &Data<PgPool>,
+) -> Result<Inbox, InboxError> {
+    match Inbox::get_inbox_by_id(path_param(req, "id")?, user_id, pool).await {
+        Ok(inbox) => Ok(inbox),
+        Err(DieselError::NotFound) => Err(InboxError::NotFound),
+        Err(err) => Err(internal_error(err)),
+    }
+}
+
+// Content-Type에 따라 JSON, 폼, 그 밖에는 본문 전체를 텍스트로
+fn parse_note(req: &HttpRequest, payload: &Bytes) -> Result<InboxNote, InboxError> {
+    let invalid = || InboxError::ValidationError {
+        field: "body".to_string(),
+    };
+
+    match req.content_type() {
+        "application/json" => serde_json::from_slice::<InboxNote>(payload).map_err(|err| {
+            error!("Error parsing inbox JSON: {:?}", err);
+            invalid()
+        }),
+        "application/x-www-form-urlencoded" => serde_urlencoded::from_bytes::<InboxNote>(payload)
+            .map_err(|err| {
+                error!("Error parsing inbox form: {:?}", err);
+                invalid()
+            }),
+        _ => {
+            let text = std::str::from_utf8(payload).map_err(|_| invalid())?;
+            Ok(InboxNote {
+                title: None,
+                body: Some(text.to_string()),
+            })
+        }
+    }
+}
+
+// 제목이 없으면 본문 첫 줄 (마크다운 제목 기호는 뺌)
+fn note_title(title: Option<&str>, body: &str) -> Option<String> {
+    let title = match title.map(str::trim).filter(|title| !title.is_empty()) {
+        Some(title) => title.chars().take(255).collect(),
+        None => body
+            .lines()
+            .map(|line| line.trim_start_matches('#').trim())
+            .find(|line| !line.is_empty())?
+            .chars()
+            .take(DERIVED_TITLE_LENGTH)
+            .collect(),
+    };
+    Some(title)
+}
+
+// GET /api/inboxes: 내 인박스
+pub async fn get_inboxes(
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, InboxError> {
+    let inboxes = Inbox::get_inboxes(&auth_guard.user_id, &pool)
+        .await
+        .map_err(internal_error)?;
+    let inboxes: Vec<InboxResponse> = inboxes.into_iter().map(InboxResponse::from).collect();
+    json_response(&inboxes)
+}
+
+// POST /api/inboxes: X-Workspace-Id가 있으면 그 워크스페이스에 노트를 만드는 인박스 (editor 이상)
+pub async fn create_inbox(
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
+    _body: web::Json<CreateInbox>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, InboxError> {
+    if let Some(workspace_id) = &workspace.0 {
+        authorize_workspace(
+            workspace_id,
+            &auth_guard.user_id,
+            WorkspaceRole::Editor,
+            &pool,
+        )
+        .await?;
+    }
+
+    let request = _body.into_inner();
+    let inbox_data = InboxData {
+        id: Uuid::new_v4().to_string(),
+        user_id: auth_guard.user_id,
+        workspace_id: workspace.0,
+        name: check_name(&request.name)?,
+        token: generate_token(),
+        published: request.published,
+    };
+
+    let inbox = InboxData::create_inbox(inbox_data, &pool)
+        .await
+        .map_err(internal_error)?;
+    Ok(HttpResponse::Created().json(InboxResponse::from(inbox)))
+}
+
+// PUT /api/inboxes/{id}: name, published, active 중 보낸 것만 변경
+pub async fn update_inbox(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: web::Json<UpdateInbox>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, InboxError> {
+    let inbox = load_inbox(&req, &auth_guard.user_id, &pool).await?;
+
+    let mut changes = _body.into_inner();
+    changes.name = changes.name.as_deref().map(check_name).transpose()?;
+
+    let inbox = Inbox::update_inbox(&inbox.id, changes, &pool)
+        .await
+        .map_err(internal_error)?;
+    json_response(&InboxResponse::from(inbox))
+}
+
+// POST /api/inboxes/{id}/token: 새 주소 발급
+pub async fn rotate_token(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, InboxError> {
+    let inbox = load_inbox(&req, &auth_guard.user_id, &pool).await?;
+
+    let inbox = Inbox::rotate_token(&inbox.id, &generate_token(), &pool)
+        .await
+        .map_err(internal_error)?;
+    json_response(&InboxResponse::from(inbox))
+}
+
+pub async fn delete_inbox(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, InboxError> {
+    let inbox = load_inbox(&req, &auth_guard.user_id, &pool).await?;
+
+    match Inbox::delete_inbox(&inbox.id, &pool).await {
+        Ok(0) => Err(InboxError::NotFound),
+        Ok(_) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("deleted inbox")),
+        Err(err) => Err(internal_error(err)),
+    }
+}
+
+// POST /api/inbox/{token}: 로그인 없이 노트 만들기 (토큰이 곧 권한)
+pub async fn capture(
+    req: HttpRequest,
+    query: web::Query<CaptureQuery>,
+    payload: Bytes,
+    pool: Data<PgPool>,
+    events: Data<EventBus>,
+) -> Result<HttpResponse, InboxError> {
+    let inbox = match Inbox::get_inbox_by_token(path_param(&req, "token")?, &pool).await {
+        Ok(inbox) if inbox.active => inbox,
+        Ok(_) | Err(DieselError::NotFound) => return Err(InboxError::NotFound),
+        Err(err) => return Err(internal_error(err)),
+    };
+
+    // 만든 뒤 워크스페이스에서 나갔거나 권한이 줄었으면 받지 않음
+    if let Some(workspace_id) = &inbox.workspace_id {
+        authorize_workspace(workspace_id, &inbox.user_id, WorkspaceRole::Editor, &pool)
+            .await
+            .map_err(|err| match err {
+                AuthorizeError::InternalError => InboxError::InternalError,
+                _ => InboxError::Forbidden,
+            })?;
+    }
+
+    let note = parse_note(&req, &payload)?;
+    let body = note.body.unwrap_or_default();
+    let title = note.title.or_else(|| query.into_inner().title);
+    let title = note_title(title.as_deref(), &body).ok_or(InboxError::ValidationError {
+        field: "body".to_string(),
+    })?;
+
+    let post_data = PostData {
+        id: None,
+        title,
+        body,
+        published: Some(inbox.published),
+        updated_at: None,
+        workspace_id: inbox.workspace_id.clone(),
+        author_id: None,
+        slug: None,
+        publish_at: None,
+        unpublish_at: None,
+    };
+
+    let post_id = PostData::create_posts(post_data, &inbox.user_id, &pool)
+        .await
+        .map_err(internal_error)?;
+    if let Err(err) = Inbox::touch_inbox(&inbox.id, &pool).await {
+        error!("Error updated inbox data: {:?}", err);
+    }
+    after_post_saved(
+        &post_id,
+        Some(&inbox.user_id),
+        PostEventKind::Created,
+        inbox.published,
+        &events,
+        &pool,
+    )
+    .await;
+
+    match Post::get_posts_load_by_id(&post_id, &pool).await {
+        Ok(post) => Ok(HttpResponse::Created().json(post)),
+        Err(err) => Err(internal_error(err)),
+    }
+}
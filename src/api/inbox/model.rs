@@ -0,0 +1,157 @@
+use crate::database::PgPool;
+use crate::schema::inboxes::dsl::*;
+use actix_web::web::Data;
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::{result::Error, AsChangeset, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::inboxes)]
+pub struct Inbox {
+    pub id: String,
+    pub user_id: String,
+    pub workspace_id: Option<String>,
+    pub name: String,
+    pub token: String,
+    pub published: bool,
+    pub active: bool,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::inboxes)]
+pub struct InboxData {
+    pub id: String,
+    pub user_id: String,
+    pub workspace_id: Option<String>,
+    pub name: String,
+    pub token: String,
+    pub published: bool,
+}
+
+// 인박스 생성 요청
+#[derive(Deserialize, Debug)]
+pub struct CreateInbox {
+    pub name: String,
+    #[serde(default)]
+    pub published: bool,
+}
+
+// 보낸 필드만 변경
+#[derive(Deserialize, Debug, AsChangeset)]
+#[diesel(table_name = crate::schema::inboxes)]
+pub struct UpdateInbox {
+    pub name: Option<String>,
+    pub published: Option<bool>,
+    pub active: Option<bool>,
+}
+
+// 클라이언트 응답용: 노트를 보낼 경로 포함
+#[derive(Serialize, Debug)]
+pub struct InboxResponse {
+    #[serde(flatten)]
+    pub inbox: Inbox,
+    pub url: String,
+}
+
+impl From<Inbox> for InboxResponse {
+    fn from(inbox: Inbox) -> Self {
+        InboxResponse {
+            url: format!("/api/inbox/{}", inbox.token),
+            inbox,
+        }
+    }
+}
+
+// 인박스로 들어온 노트 (JSON, 폼 공통)
+#[derive(Deserialize, Debug, Default)]
+pub struct InboxNote {
+    pub title: Option<String>,
+    // Zapier 같은 도구가 많이 쓰는 이름도 받음
+    #[serde(alias = "text", alias = "content")]
+    pub body: Option<String>,
+}
+
+impl Inbox {
+    // 내 인박스 (모든 워크스페이스)
+    pub async fn get_inboxes(
+        inbox_user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Inbox>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        inboxes
+            .filter(user_id.eq(inbox_user_id))
+            .order(created_at.asc())
+            .load::<Inbox>(conn)
+    }
+
+    // 다른 사용자의 인박스는 없는 것으로 취급
+    pub async fn get_inbox_by_id(
+        inbox_id: &str,
+        inbox_user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Inbox, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        inboxes
+            .find(inbox_id)
+            .filter(user_id.eq(inbox_user_id))
+            .get_result::<Inbox>(conn)
+    }
+
+    pub async fn get_inbox_by_token(
+        inbox_token: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Inbox, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        inboxes
+            .filter(token.eq(inbox_token))
+            .get_result::<Inbox>(conn)
+    }
+
+    pub async fn update_inbox(
+        inbox_id: &str,
+        changes: UpdateInbox,
+        pool: &Data<PgPool>,
+    ) -> Result<Inbox, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::update(inboxes.find(inbox_id))
+            .set((changes, updated_at.eq(Utc::now().naive_utc())))
+            .get_result::<Inbox>(conn)
+    }
+
+    // 주소가 새어나갔을 때 새 토큰으로 바꿈 (이전 주소는 바로 막힘)
+    pub async fn rotate_token(
+        inbox_id: &str,
+        new_token: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Inbox, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::update(inboxes.find(inbox_id))
+            .set((token.eq(new_token), updated_at.eq(Utc::now().naive_utc())))
+            .get_result::<Inbox>(conn)
+    }
+
+    pub async fn touch_inbox(inbox_id: &str, pool: &Data<PgPool>) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::update(inboxes.find(inbox_id))
+            .set(last_used_at.eq(Utc::now().naive_utc()))
+            .execute(conn)
+    }
+
+    pub async fn delete_inbox(inbox_id: &str, pool: &Data<PgPool>) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::delete(inboxes.find(inbox_id)).execute(conn)
+    }
+}
+
+impl InboxData {
+    pub async fn create_inbox(inbox_data: InboxData, pool: &Data<PgPool>) -> Result<Inbox, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::insert_into(inboxes)
+            .values(inbox_data)
+            .get_result::<Inbox>(conn)
+    }
+}
@@ -0,0 +1,22 @@
+use crate::api::inbox::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(handler::get_inboxes))
+            .route(web::post().to(handler::create_inbox)),
+    );
+
+    cfg.service(
+        web::resource("/{id}")
+            .route(web::put().to(handler::update_inbox))
+            .route(web::delete().to(handler::delete_inbox)),
+    );
+
+    cfg.service(web::resource("/{id}/token").route(web::post().to(handler::rotate_token)));
+}
+
+pub fn configure_capture(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/{token}").route(web::post().to(handler::capture)));
+}
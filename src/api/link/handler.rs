@@ -0,0 +1,74 @@
+use super::error::LinkError;
+use super::model::PostLink;
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::permission::authorize::{authorize, authorize_workspace};
+use crate::api::permission::model::PostRole;
+use crate::api::workspace::model::WorkspaceRole;
+use crate::api::workspace::selector::WorkspaceSelector;
+use crate::database::PgPool;
+use actix_web::{http::header::ContentType, web::Data, HttpRequest, HttpResponse, Result};
+use log::error;
+use serde_json::to_vec;
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, LinkError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        LinkError::BadClientData
+    })
+}
+
+fn json_response<T: serde::Serialize>(data: &T) -> Result<HttpResponse, LinkError> {
+    let json_bytes = to_vec(data).map_err(|err| {
+        error!("Failed to serialize links to JSON: {:?}", err);
+        LinkError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
+
+// GET /api/posts/{id}/backlinks: 이 노트를 [[제목]]으로 링크한 노트
+pub async fn get_backlinks(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, LinkError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Viewer, &pool).await?;
+
+    let backlinks = PostLink::get_backlinks(post_id, &auth_guard.user_id, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get backlinks data: {:?}", err);
+            LinkError::InternalError
+        })?;
+
+    json_response(&backlinks)
+}
+
+// GET /api/graph: 노트와 링크로 만든 그래프 (워크스페이스를 선택하면 그 워크스페이스만)
+pub async fn get_graph(
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, LinkError> {
+    if let Some(workspace_id) = &workspace.0 {
+        authorize_workspace(
+            workspace_id,
+            &auth_guard.user_id,
+            WorkspaceRole::Viewer,
+            &pool,
+        )
+        .await?;
+    }
+
+    let graph = PostLink::get_graph(&auth_guard.user_id, workspace.0.as_deref(), &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get link graph data: {:?}", err);
+            LinkError::InternalError
+        })?;
+
+    json_response(&graph)
+}
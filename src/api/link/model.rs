@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+
+use super::parser::parse_wiki_links;
+use crate::api::post::model::Post;
+use crate::database::PgPool;
+use crate::schema::post_links::dsl::*;
+use crate::schema::{post_permissions, posts, workspace_members};
+use actix_web::web::Data;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use diesel::{result::Error, Insertable, Queryable};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+diesel::define_sql_function! {
+    fn lower(text: Text) -> Text;
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::post_links)]
+pub struct PostLink {
+    pub source_id: String,
+    pub target_title: String,
+    pub target_id: Option<String>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::post_links)]
+pub struct PostLinkData {
+    pub source_id: String,
+    pub target_title: String,
+}
+
+// 이 노트를 링크한 노트
+#[derive(Serialize, Debug, Queryable)]
+pub struct Backlink {
+    pub id: String,
+    pub title: String,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GraphNode {
+    // 연결되지 않은 링크는 "unresolved:{소문자 제목}"
+    pub id: String,
+    pub title: String,
+    pub resolved: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Graph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+// 링크가 연결되는 범위: 워크스페이스 노트는 같은 워크스페이스, 개인 노트는 같은 작성자의 개인 노트
+fn scope_of(post: &Post) -> posts::BoxedQuery<'static, Pg> {
+    match (&post.workspace_id, &post.author_id) {
+        (Some(workspace), _) => posts::table
+            .filter(posts::workspace_id.eq(workspace.clone()))
+            .into_boxed(),
+        (None, Some(author)) => posts::table
+            .filter(posts::workspace_id.is_null())
+            .filter(posts::author_id.eq(author.clone()))
+            .into_boxed(),
+        (None, None) => posts::table.filter(posts::id.is_null()).into_boxed(),
+    }
+}
+
+// 제목이 같은 노트가 여럿이면 먼저 만든 노트
+fn resolve_target(
+    source: &Post,
+    title: &str,
+    conn: &mut PgConnection,
+) -> Result<Option<String>, Error> {
+    scope_of(source)
+        .filter(lower(posts::title).eq(title.to_lowercase()))
+        .order(posts::created_at.asc())
+        .select(posts::id)
+        .first::<String>(conn)
+        .optional()
+}
+
+impl PostLink {
+    // 노트 본문의 링크를 다시 읽고, 제목이 바뀌었으면 이 노트를 가리키는 링크도 갱신
+    pub async fn sync_links(post_id: &str, pool: &Data<PgPool>) -> Result<(), Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            let post = match posts::table
+                .find(post_id)
+                .get_result::<Post>(conn)
+                .optional()?
+            {
+                Some(post) => post,
+                None => return Ok(()),
+            };
+
+            // 본문의 링크
+            let titles = parse_wiki_links(&post.body);
+            diesel::delete(
+                post_links
+                    .filter(source_id.eq(post_id))
+                    .filter(target_title.ne_all(&titles)),
+            )
+            .execute(conn)?;
+            for title in &titles {
+                let target = resolve_target(&post, title, conn)?;
+                diesel::insert_into(post_links)
+                    .values((
+                        source_id.eq(post_id),
+                        target_title.eq(title),
+                        target_id.eq(&target),
+                    ))
+                    .on_conflict((source_id, target_title))
+                    .do_update()
+                    .set(target_id.eq(&target))
+                    .execute(conn)?;
+            }
+
+            // 제목이 바뀌어 더 이상 맞지 않는 링크는 같은 제목의 다른 노트로
+            let renamed = post_links
+                .filter(target_id.eq(post_id))
+                .filter(lower(target_title).ne(post.title.to_lowercase()))
+                .select((source_id, target_title))
+                .load::<(String, String)>(conn)?;
+            for (link_source_id, title) in renamed {
+                let source = posts::table
+                    .find(&link_source_id)
+                    .get_result::<Post>(conn)?;
+                let target = resolve_target(&source, &title, conn)?;
+                diesel::update(post_links.find((&link_source_id, &title)))
+                    .set(target_id.eq(target))
+                    .execute(conn)?;
+            }
+
+            // 같은 범위에서 이 제목을 가리키던 연결되지 않은 링크 연결
+            diesel::update(
+                post_links
+                    .filter(target_id.is_null())
+                    .filter(lower(target_title).eq(post.title.to_lowercase()))
+                    .filter(source_id.eq_any(scope_of(&post).select(posts::id))),
+            )
+            .set(target_id.eq(post_id))
+            .execute(conn)?;
+
+            Ok(())
+        })
+    }
+
+    // 노트 본문의 [[제목]]이 연결된 노트 id (저장할 때 연결해 둔 결과)
+    pub async fn get_target(
+        link_source_id: &str,
+        title: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Option<String>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        post_links
+            .filter(source_id.eq(link_source_id))
+            .filter(lower(target_title).eq(title.to_lowercase()))
+            .select(target_id)
+            .first::<Option<String>>(conn)
+            .optional()
+            .map(Option::flatten)
+    }
+
+    // 본문이 바뀐 뒤 호출, 실패해도 노트 저장에는 영향 없음
+    pub async fn refresh_links(post_id: &str, pool: &Data<PgPool>) {
+        if let Err(err) = PostLink::sync_links(post_id, pool).await {
+            error!("Error updating post links: {:?}", err);
+        }
+    }
+
+    // 사용자가 볼 수 있는 노트 중 이 노트를 링크한 노트
+    pub async fn get_backlinks(
+        post_id: &str,
+        user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Backlink>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        let permitted = post_permissions::table
+            .filter(post_permissions::user_id.eq(user_id))
+            .select(post_permissions::post_id);
+        let member_of = workspace_members::table
+            .filter(workspace_members::user_id.eq(user_id))
+            .select(workspace_members::workspace_id.nullable());
+
+        post_links
+            .inner_join(posts::table)
+            .filter(target_id.eq(post_id))
+            .filter(
+                posts::id
+                    .eq_any(permitted)
+                    .or(posts::workspace_id.eq_any(member_of)),
+            )
+            .order(posts::updated_at.desc().nulls_last())
+            .select((posts::id, posts::title, posts::updated_at))
+            .load::<Backlink>(conn)
+    }
+
+    // 그래프 뷰: 볼 수 있는 노트와 그 노트들의 링크 (연결되지 않은 링크 포함)
+    pub async fn get_graph(
+        user_id: &str,
+        workspace: Option<&str>,
+        pool: &Data<PgPool>,
+    ) -> Result<Graph, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        let notes = match workspace {
+            Some(workspace) => posts::table
+                .filter(posts::workspace_id.eq(workspace))
+                .select((posts::id, posts::title))
+                .load::<(String, String)>(conn)?,
+            None => {
+                let permitted = post_permissions::table
+                    .filter(post_permissions::user_id.eq(user_id))
+                    .select(post_permissions::post_id);
+                let member_of = workspace_members::table
+                    .filter(workspace_members::user_id.eq(user_id))
+                    .select(workspace_members::workspace_id.nullable());
+                posts::table
+                    .filter(
+                        posts::id
+                            .eq_any(permitted)
+                            .or(posts::workspace_id.eq_any(member_of)),
+                    )
+                    .select((posts::id, posts::title))
+                    .load::<(String, String)>(conn)?
+            }
+        };
+
+        let note_ids: Vec<&String> = notes.iter().map(|(note_id, _)| note_id).collect();
+        let links = post_links
+            .filter(source_id.eq_any(&note_ids))
+            .load::<PostLink>(conn)?;
+
+        let visible: HashSet<&str> = notes.iter().map(|(note_id, _)| note_id.as_str()).collect();
+        let mut nodes: Vec<GraphNode> = notes
+            .iter()
+            .map(|(note_id, note_title)| GraphNode {
+                id: note_id.clone(),
+                title: note_title.clone(),
+                resolved: true,
+            })
+            .collect();
+        let mut unresolved: HashSet<String> = HashSet::new();
+        let mut edges = Vec::with_capacity(links.len());
+
+        for link in links {
+            // 볼 수 없는 노트로 연결된 링크는 연결되지 않은 링크로 표시
+            let target = match link.target_id {
+                Some(target) if visible.contains(target.as_str()) => target,
+                _ => {
+                    let key = format!("unresolved:{}", link.target_title.to_lowercase());
+                    if unresolved.insert(key.clone()) {
+                        nodes.push(GraphNode {
+                            id: key.clone(),
+                            title: link.target_title,
+                            resolved: false,
+                        });
+                    }
+                    key
+                }
+            };
+            edges.push(GraphEdge {
+                source: link.source_id,
+                target,
+            });
+        }
+
+        Ok(Graph { nodes, edges })
+    }
+}
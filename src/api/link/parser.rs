@@ -0,0 +1,156 @@
+// 링크 제목 최대 길이 (post_links.target_title)
+const MAX_TITLE_LENGTH: usize = 255;
+
+// 본문에서 [[제목]], [[제목|표시 이름]], [[제목#소제목]] 형태의 링크 제목 추출 (대소문자 구분 없이 중복 제거)
+pub fn parse_wiki_links(body: &str) -> Vec<String> {
+    let mut titles: Vec<String> = Vec::new();
+    let mut rest = body;
+
+    while let Some(start) = rest.find("[[") {
+        rest = &rest[start + 2..];
+        let end = match rest.find("]]") {
+            Some(end) => end,
+            None => break,
+        };
+        let inner = &rest[..end];
+
+        // 줄을 넘기거나 괄호가 섞인 경우는 링크가 아님
+        if inner.contains(['\n', '[', ']']) {
+            continue;
+        }
+        rest = &rest[end + 2..];
+
+        let title = inner.split(['|', '#']).next().unwrap_or_default().trim();
+        let title: String = title.chars().take(MAX_TITLE_LENGTH).collect();
+        if title.is_empty()
+            || titles
+                .iter()
+                .any(|t| t.to_lowercase() == title.to_lowercase())
+        {
+            continue;
+        }
+        titles.push(title);
+    }
+
+    titles
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BodyToken<'a> {
+    Text(&'a str),
+    // ![[제목]]: raw는 원문 그대로
+    Embed { raw: &'a str, title: String },
+}
+
+// 본문을 일반 텍스트와 ![[제목]] 임베드로 나눔
+pub fn split_embeds(body: &str) -> Vec<BodyToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut text_start = 0;
+    let mut search_from = 0;
+
+    while let Some(found) = body[search_from..].find("![[") {
+        let start = search_from + found;
+        let inner_start = start + 3;
+        let end = match body[inner_start..].find("]]") {
+            Some(end) => inner_start + end,
+            None => break,
+        };
+        let inner = &body[inner_start..end];
+        let title = inner.split(['|', '#']).next().unwrap_or_default().trim();
+
+        if inner.contains(['\n', '[', ']']) || title.is_empty() {
+            search_from = inner_start;
+            continue;
+        }
+
+        if start > text_start {
+            tokens.push(BodyToken::Text(&body[text_start..start]));
+        }
+        tokens.push(BodyToken::Embed {
+            raw: &body[start..end + 2],
+            title: title.chars().take(MAX_TITLE_LENGTH).collect(),
+        });
+        text_start = end + 2;
+        search_from = text_start;
+    }
+
+    if text_start < body.len() {
+        tokens.push(BodyToken::Text(&body[text_start..]));
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_titles_labels_and_headings() {
+        let body = "See [[Alpha]], [[Beta|the beta]] and [[Gamma#Setup]].";
+        assert_eq!(parse_wiki_links(body), vec!["Alpha", "Beta", "Gamma"]);
+    }
+
+    #[test]
+    fn deduplicates_case_insensitively() {
+        let body = "[[Alpha]] [[alpha]] [[ ALPHA ]] [[회의록]] [[회의록|지난주]]";
+        assert_eq!(parse_wiki_links(body), vec!["Alpha", "회의록"]);
+    }
+
+    #[test]
+    fn ignores_broken_and_nested_brackets() {
+        assert_eq!(parse_wiki_links("[[a [[b]] c]]"), vec!["b"]);
+        assert_eq!(
+            parse_wiki_links("[[one\ntwo]] [[]] [[|label]] [[open"),
+            Vec::<String>::new()
+        );
+        assert_eq!(parse_wiki_links("[[[x]]]"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn handles_crlf_and_multibyte_text() {
+        let body = "첫 줄 [[노트 🎉]]\r\n둘째 [[Ünïcode|ü]]\r\n";
+        assert_eq!(parse_wiki_links(body), vec!["노트 🎉", "Ünïcode"]);
+    }
+
+    #[test]
+    fn splits_embeds_from_text() {
+        let body = "앞 ![[회의록|요약]] 중간 ![[Plan#Goals]]";
+        assert_eq!(
+            split_embeds(body),
+            vec![
+                BodyToken::Text("앞 "),
+                BodyToken::Embed {
+                    raw: "![[회의록|요약]]",
+                    title: "회의록".to_string(),
+                },
+                BodyToken::Text(" 중간 "),
+                BodyToken::Embed {
+                    raw: "![[Plan#Goals]]",
+                    title: "Plan".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_invalid_embeds_as_text() {
+        assert_eq!(split_embeds(""), Vec::<BodyToken>::new());
+        assert_eq!(
+            split_embeds("![[]] ![[a\nb]] ![[open"),
+            vec![BodyToken::Text("![[]] ![[a\nb]] ![[open")]
+        );
+        assert_eq!(
+            split_embeds("![[a ![[b]]"),
+            vec![
+                BodyToken::Text("![[a "),
+                BodyToken::Embed {
+                    raw: "![[b]]",
+                    title: "b".to_string(),
+                },
+            ]
+        );
+        // 링크는 임베드가 아님
+        assert_eq!(split_embeds("[[link]]"), vec![BodyToken::Text("[[link]]")]);
+    }
+}
@@ -0,0 +1,10 @@
+use crate::api::link::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(handler::get_backlinks)));
+}
+
+pub fn configure_graph(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(handler::get_graph)));
+}
@@ -0,0 +1,208 @@
+use super::parser::parse_mentions;
+use crate::api::comment::model::Comment;
+use crate::api::notification::model::NotificationKind;
+use crate::api::notification::notify::{notify, Notice};
+use crate::api::page::template::summarize;
+use crate::api::permission::authorize::authorize;
+use crate::api::permission::model::PostRole;
+use crate::api::post::model::Post;
+use crate::api::user::model::User;
+use crate::database::PgPool;
+use crate::schema::mentions::dsl::*;
+use actix_web::web::Data;
+use diesel::prelude::*;
+use diesel::{result::Error, Insertable, Queryable};
+use log::error;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// 알림에 넣을 댓글 길이
+const NOTICE_EXCERPT_LENGTH: usize = 100;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::mentions)]
+pub struct Mention {
+    pub id: String,
+    pub post_id: String,
+    pub comment_id: Option<String>,
+    pub user_id: String,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::mentions)]
+pub struct MentionData {
+    pub id: String,
+    pub post_id: String,
+    pub comment_id: Option<String>,
+    pub user_id: String,
+}
+
+impl Mention {
+    // 본문(comment_id가 None) 또는 댓글 하나의 멘션을 user_ids로 맞춤, 새로 멘션된 사용자를 반환
+    pub async fn sync_mentions(
+        mention_post_id: &str,
+        mention_comment_id: Option<&str>,
+        user_ids: &[String],
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<String>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            let mut query = mentions
+                .filter(post_id.eq(mention_post_id))
+                .select(user_id)
+                .into_boxed();
+            query = match mention_comment_id {
+                Some(mention_comment_id) => query.filter(comment_id.eq(mention_comment_id)),
+                None => query.filter(comment_id.is_null()),
+            };
+            let existing = query.load::<String>(conn)?;
+
+            let removed: Vec<&String> = existing
+                .iter()
+                .filter(|existing_id| !user_ids.contains(existing_id))
+                .collect();
+            if !removed.is_empty() {
+                let mut delete = diesel::delete(mentions)
+                    .filter(post_id.eq(mention_post_id))
+                    .filter(user_id.eq_any(removed))
+                    .into_boxed();
+                delete = match mention_comment_id {
+                    Some(mention_comment_id) => delete.filter(comment_id.eq(mention_comment_id)),
+                    None => delete.filter(comment_id.is_null()),
+                };
+                delete.execute(conn)?;
+            }
+
+            let added: Vec<String> = user_ids
+                .iter()
+                .filter(|mentioned_id| !existing.contains(mentioned_id))
+                .cloned()
+                .collect();
+            let mention_data: Vec<MentionData> = added
+                .iter()
+                .map(|mentioned_id| MentionData {
+                    id: Uuid::new_v4().to_string(),
+                    post_id: mention_post_id.to_string(),
+                    comment_id: mention_comment_id.map(str::to_string),
+                    user_id: mentioned_id.clone(),
+                })
+                .collect();
+            if !mention_data.is_empty() {
+                diesel::insert_into(mentions)
+                    .values(mention_data)
+                    .execute(conn)?;
+            }
+
+            Ok(added)
+        })
+    }
+
+    // 본문의 @handle, @이메일을 사용자 id로
+    async fn resolve(text: &str, pool: &Data<PgPool>) -> Result<Vec<String>, Error> {
+        let parsed = parse_mentions(text);
+        if parsed.handles.is_empty() && parsed.emails.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let users =
+            User::get_users_by_handles_or_emails(&parsed.handles, &parsed.emails, pool).await?;
+        Ok(users.into_iter().map(|user| user.id).collect())
+    }
+
+    // 노트 본문이 바뀐 뒤 호출, 실패해도 노트 저장에는 영향 없음
+    // actor: 저장한 사용자 (협업 편집처럼 알 수 없으면 None)
+    pub async fn refresh_post_mentions(
+        mention_post_id: &str,
+        actor: Option<&str>,
+        pool: &Data<PgPool>,
+    ) {
+        let post = match Post::get_posts_load_by_id(mention_post_id, pool).await {
+            Ok(post) => post,
+            Err(err) => {
+                error!("Error get post data: {:?}", err);
+                return;
+            }
+        };
+
+        let added = match Mention::resolve(&post.body, pool).await {
+            Ok(user_ids) => Mention::sync_mentions(&post.id, None, &user_ids, pool).await,
+            Err(err) => Err(err),
+        };
+        match added {
+            Ok(added) => notify_mentioned(&post, None, &added, actor, pool).await,
+            Err(err) => error!("Error updating post mentions: {:?}", err),
+        }
+    }
+
+    // 댓글 작성, 수정 뒤 호출
+    pub async fn refresh_comment_mentions(
+        post: &Post,
+        comment: &Comment,
+        actor: &str,
+        pool: &Data<PgPool>,
+    ) {
+        let added = match Mention::resolve(&comment.body, pool).await {
+            Ok(user_ids) => {
+                Mention::sync_mentions(&post.id, Some(&comment.id), &user_ids, pool).await
+            }
+            Err(err) => Err(err),
+        };
+        match added {
+            Ok(added) => notify_mentioned(post, Some(comment), &added, Some(actor), pool).await,
+            Err(err) => error!("Error updating comment mentions: {:?}", err),
+        }
+    }
+}
+
+// 새로 멘션된 사용자 중 노트를 볼 수 있는 사용자에게만 알림
+async fn notify_mentioned(
+    post: &Post,
+    comment: Option<&Comment>,
+    added: &[String],
+    actor: Option<&str>,
+    pool: &Data<PgPool>,
+) {
+    let mut allowed = Vec::with_capacity(added.len());
+    for recipient in added {
+        if authorize(&post.id, recipient, PostRole::Viewer, pool)
+            .await
+            .is_ok()
+        {
+            allowed.push(recipient.as_str());
+        }
+    }
+    if allowed.is_empty() {
+        return;
+    }
+
+    let actor_name = match actor {
+        Some(actor) => User::get_users_by_id(actor, pool)
+            .await
+            .map(|user| user.user_name)
+            .ok(),
+        None => None,
+    };
+    let subject = match actor_name {
+        Some(actor_name) => format!("{} mentioned you", actor_name),
+        None => "You were mentioned".to_string(),
+    };
+    let message = match comment {
+        Some(comment) => format!(
+            "{} in a comment on \"{}\": {}",
+            subject,
+            post.title,
+            summarize(&comment.body, NOTICE_EXCERPT_LENGTH)
+        ),
+        None => format!("{} in \"{}\"", subject, post.title),
+    };
+
+    let notice = Notice {
+        kind: NotificationKind::Mention,
+        post_id: Some(&post.id),
+        actor_id: actor,
+        message,
+    };
+    notify(&allowed, notice, pool).await;
+}
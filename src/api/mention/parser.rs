@@ -0,0 +1,108 @@
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ParsedMentions {
+    // 소문자로 정리된 handle, 이메일 (중복 없음)
+    pub handles: Vec<String>,
+    pub emails: Vec<String>,
+}
+
+fn is_handle_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn is_email_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '%' | '+' | '-' | '@')
+}
+
+fn is_fence(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+// "@이름@도메인" 형태면 이메일
+fn as_email(token: &str) -> Option<String> {
+    let (local, domain) = token.split_once('@')?;
+    let domain = domain.trim_end_matches(['.', '-']);
+    if local.is_empty()
+        || domain.contains('@')
+        || !domain.contains('.')
+        || domain.starts_with('.')
+        || domain.ends_with('.')
+    {
+        return None;
+    }
+    Some(format!("{}@{}", local, domain).to_lowercase())
+}
+
+// 본문의 @handle, @이메일 (``` 코드 블록 안, 단어 중간의 @는 제외)
+pub fn parse_mentions(body: &str) -> ParsedMentions {
+    let mut mentions = ParsedMentions::default();
+    let mut in_fence = false;
+
+    for line in body.split('\n') {
+        if is_fence(line) {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let mut previous: Option<char> = None;
+        for (index, c) in line.char_indices() {
+            let at_word_start = previous.is_none_or(|p| !is_handle_char(p) && p != '.');
+            previous = Some(c);
+            if c != '@' || !at_word_start {
+                continue;
+            }
+
+            let rest = &line[index + 1..];
+            let token_end = rest.find(|c| !is_email_char(c)).unwrap_or(rest.len());
+            let token = &rest[..token_end];
+
+            if let Some(email) = as_email(token) {
+                if !mentions.emails.contains(&email) {
+                    mentions.emails.push(email);
+                }
+                continue;
+            }
+
+            let handle_end = rest.find(|c| !is_handle_char(c)).unwrap_or(rest.len());
+            let handle = rest[..handle_end].to_lowercase();
+            if (3..=32).contains(&handle.len()) && !mentions.handles.contains(&handle) {
+                mentions.handles.push(handle);
+            }
+        }
+    }
+
+    mentions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_handles_and_emails() {
+        let mentions = parse_mentions("cc @Alice, @bob_2 and @Carol@Example.com.");
+        assert_eq!(mentions.handles, vec!["alice", "bob_2"]);
+        assert_eq!(mentions.emails, vec!["carol@example.com"]);
+    }
+
+    #[test]
+    fn ignores_mid_word_short_and_duplicate_mentions() {
+        let mentions = parse_mentions("mail me at me@example.com, @ab, @alice @ALICE x.@bob");
+        assert_eq!(mentions.handles, vec!["alice"]);
+        assert!(mentions.emails.is_empty());
+    }
+
+    #[test]
+    fn skips_mentions_inside_code_fences() {
+        let mentions = parse_mentions("```\n@inside\n```\n@outside");
+        assert_eq!(mentions.handles, vec!["outside"]);
+    }
+
+    #[test]
+    fn handles_crlf_and_multibyte_text() {
+        let mentions = parse_mentions("안녕 @alice님\r\n(@bob)\r\n🎉@carol");
+        assert_eq!(mentions.handles, vec!["alice", "bob", "carol"]);
+    }
+}
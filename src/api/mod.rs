@@ -1,3 +1,24 @@
+pub mod attachment;
 pub mod auth;
+pub mod collab;
+pub mod comment;
+pub mod daily;
+pub mod embed;
+pub mod event;
+pub mod feed;
+pub mod inbox;
+pub mod link;
+pub mod mention;
+pub mod notification;
+pub mod page;
+pub mod permission;
 pub mod post;
+pub mod reminder;
+pub mod share;
+pub mod sync;
+pub mod task;
+pub mod template;
+pub mod upload;
 pub mod user;
+pub mod webhook;
+pub mod workspace;
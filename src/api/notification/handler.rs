@@ -0,0 +1,130 @@
+use super::error::NotificationError;
+use super::model::{
+    Notification, NotificationPage, NotificationQuery, MAX_NOTIFICATION_PAGE_SIZE,
+    NOTIFICATION_PAGE_SIZE,
+};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::ContentType,
+    web::{Data, Query},
+    HttpRequest, HttpResponse, Result,
+};
+use diesel::result::Error as DieselError;
+use log::error;
+use serde_json::{json, to_vec};
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, NotificationError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        NotificationError::BadClientData
+    })
+}
+
+fn json_response<T: serde::Serialize>(data: &T) -> Result<HttpResponse, NotificationError> {
+    let json_bytes = to_vec(data).map_err(|err| {
+        error!("Failed to serialize notifications to JSON: {:?}", err);
+        NotificationError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
+
+fn internal_error(err: DieselError) -> NotificationError {
+    error!("Error notification data: {:?}", err);
+    NotificationError::InternalError
+}
+
+async fn count_unread(user_id: &str, pool: &Data<PgPool>) -> Result<i64, NotificationError> {
+    Notification::count_unread(user_id, pool)
+        .await
+        .map_err(internal_error)
+}
+
+// GET /api/notifications?unread=&limit=&before=: 최근 알림과 읽지 않은 알림 수
+pub async fn get_notifications(
+    auth_guard: AuthenticationGuard,
+    query: Query<NotificationQuery>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, NotificationError> {
+    let limit = query.limit.unwrap_or(NOTIFICATION_PAGE_SIZE);
+    if !(1..=MAX_NOTIFICATION_PAGE_SIZE).contains(&limit) {
+        return Err(NotificationError::ValidationError {
+            field: "limit".to_string(),
+        });
+    }
+
+    let before = match &query.before {
+        Some(before) => Some(
+            Notification::get_notification_by_id(before, &auth_guard.user_id, &pool)
+                .await
+                .map_err(|err| match err {
+                    DieselError::NotFound => NotificationError::ValidationError {
+                        field: "before".to_string(),
+                    },
+                    err => internal_error(err),
+                })?,
+        ),
+        None => None,
+    };
+
+    let notifications = Notification::get_notifications(
+        &auth_guard.user_id,
+        query.unread.unwrap_or(false),
+        before.as_ref(),
+        limit,
+        &pool,
+    )
+    .await
+    .map_err(internal_error)?;
+
+    let next_cursor = match notifications.last() {
+        Some(last) if notifications.len() as i64 == limit => Some(last.notification.id.clone()),
+        _ => None,
+    };
+
+    json_response(&NotificationPage {
+        notifications,
+        unread_count: count_unread(&auth_guard.user_id, &pool).await?,
+        next_cursor,
+    })
+}
+
+// GET /api/notifications/unread-count
+pub async fn get_unread_count(
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, NotificationError> {
+    let unread_count = count_unread(&auth_guard.user_id, &pool).await?;
+
+    json_response(&json!({ "unread_count": unread_count }))
+}
+
+// POST /api/notifications/{id}/read
+pub async fn mark_read(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, NotificationError> {
+    let notification_id = path_param(&req, "id")?;
+
+    match Notification::mark_read(notification_id, &auth_guard.user_id, &pool).await {
+        Ok(notification) => json_response(&notification),
+        Err(DieselError::NotFound) => Err(NotificationError::NotFound),
+        Err(err) => Err(internal_error(err)),
+    }
+}
+
+// POST /api/notifications/read-all
+pub async fn mark_all_read(
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, NotificationError> {
+    let updated = Notification::mark_all_read(&auth_guard.user_id, &pool)
+        .await
+        .map_err(internal_error)?;
+
+    json_response(&json!({ "updated": updated }))
+}
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use crate::database::PgPool;
+use crate::schema::notifications::dsl::*;
+use crate::schema::users;
+use actix_web::web::Data;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::{result::Error, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+// 알림함 한 페이지 기본 크기와 최대 크기
+pub const NOTIFICATION_PAGE_SIZE: i64 = 20;
+pub const MAX_NOTIFICATION_PAGE_SIZE: i64 = 100;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationKind {
+    Reminder,
+    Share,
+    Comment,
+    Mention,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::Reminder => "reminder",
+            NotificationKind::Share => "share",
+            NotificationKind::Comment => "comment",
+            NotificationKind::Mention => "mention",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::notifications)]
+pub struct Notification {
+    pub id: String,
+    pub user_id: String,
+    pub kind: String,
+    pub post_id: Option<String>,
+    pub message: String,
+    pub read_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub actor_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::notifications)]
+pub struct NotificationData {
+    pub id: String,
+    pub user_id: String,
+    pub kind: String,
+    pub post_id: Option<String>,
+    pub message: String,
+    pub actor_id: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Queryable)]
+pub struct Actor {
+    pub id: String,
+    pub user_name: String,
+    pub photo: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct NotificationItem {
+    #[serde(flatten)]
+    pub notification: Notification,
+    pub actor: Option<Actor>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct NotificationPage {
+    pub notifications: Vec<NotificationItem>,
+    pub unread_count: i64,
+    // 다음 페이지의 before, 마지막 페이지면 None
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct NotificationQuery {
+    pub unread: Option<bool>,
+    pub limit: Option<i64>,
+    // 이 알림보다 오래된 알림부터
+    pub before: Option<String>,
+}
+
+impl Notification {
+    // 최근 알림부터, before가 있으면 그 알림 다음부터
+    pub async fn get_notifications(
+        notification_user_id: &str,
+        unread: bool,
+        before: Option<&Notification>,
+        limit: i64,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<NotificationItem>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        let mut query = notifications
+            .filter(user_id.eq(notification_user_id.to_string()))
+            .into_boxed();
+        if unread {
+            query = query.filter(read_at.is_null());
+        }
+        if let Some(before) = before {
+            query = query.filter(
+                created_at.lt(before.created_at).or(created_at
+                    .eq(before.created_at)
+                    .and(id.lt(before.id.clone()))),
+            );
+        }
+
+        let list = query
+            .order((created_at.desc(), id.desc()))
+            .limit(limit)
+            .load::<Notification>(conn)?;
+
+        let actor_ids: Vec<&String> = list
+            .iter()
+            .filter_map(|notification| notification.actor_id.as_ref())
+            .collect();
+        let actors: HashMap<String, Actor> = users::table
+            .filter(users::id.eq_any(actor_ids))
+            .select((users::id, users::user_name, users::photo))
+            .load::<Actor>(conn)?
+            .into_iter()
+            .map(|actor| (actor.id.clone(), actor))
+            .collect();
+
+        Ok(list
+            .into_iter()
+            .map(|notification| NotificationItem {
+                actor: notification
+                    .actor_id
+                    .as_ref()
+                    .and_then(|actor| actors.get(actor).cloned()),
+                notification,
+            })
+            .collect())
+    }
+
+    // 다른 사용자의 알림은 없는 것으로 취급
+    pub async fn get_notification_by_id(
+        notification_id: &str,
+        notification_user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Notification, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        notifications
+            .find(notification_id)
+            .filter(user_id.eq(notification_user_id))
+            .get_result::<Notification>(conn)
+    }
+
+    pub async fn count_unread(
+        notification_user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<i64, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        notifications
+            .filter(user_id.eq(notification_user_id))
+            .filter(read_at.is_null())
+            .count()
+            .get_result::<i64>(conn)
+    }
+
+    // 이미 읽은 알림은 처음 읽은 시각 유지
+    pub async fn mark_read(
+        notification_id: &str,
+        notification_user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Notification, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::update(
+            notifications
+                .find(notification_id)
+                .filter(user_id.eq(notification_user_id))
+                .filter(read_at.is_null()),
+        )
+        .set(read_at.eq(Utc::now().naive_utc()))
+        .execute(conn)?;
+
+        notifications
+            .find(notification_id)
+            .filter(user_id.eq(notification_user_id))
+            .get_result::<Notification>(conn)
+    }
+
+    pub async fn mark_all_read(
+        notification_user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::update(
+            notifications
+                .filter(user_id.eq(notification_user_id))
+                .filter(read_at.is_null()),
+        )
+        .set(read_at.eq(Utc::now().naive_utc()))
+        .execute(conn)
+    }
+}
+
+impl NotificationData {
+    pub async fn create_notifications(
+        notification_data: Vec<NotificationData>,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::insert_into(notifications)
+            .values(&notification_data)
+            .execute(conn)
+    }
+}
@@ -0,0 +1,43 @@
+use super::model::{NotificationData, NotificationKind};
+use crate::database::PgPool;
+use actix_web::web::Data;
+use log::error;
+use uuid::Uuid;
+
+// 다른 기능(공유, 댓글, 멘션 등)이 사용자에게 알림을 남길 때 사용
+pub struct Notice<'a> {
+    pub kind: NotificationKind,
+    pub post_id: Option<&'a str>,
+    // 알림을 만든 사용자, 본인에게는 알리지 않음
+    pub actor_id: Option<&'a str>,
+    pub message: String,
+}
+
+// 실패해도 원래 작업에는 영향 없음
+pub async fn notify(recipients: &[&str], notice: Notice<'_>, pool: &Data<PgPool>) {
+    let mut notification_data: Vec<NotificationData> = Vec::with_capacity(recipients.len());
+    for recipient in recipients {
+        if Some(*recipient) == notice.actor_id
+            || notification_data
+                .iter()
+                .any(|data| data.user_id == *recipient)
+        {
+            continue;
+        }
+        notification_data.push(NotificationData {
+            id: Uuid::new_v4().to_string(),
+            user_id: recipient.to_string(),
+            kind: notice.kind.as_str().to_string(),
+            post_id: notice.post_id.map(str::to_string),
+            message: notice.message.clone(),
+            actor_id: notice.actor_id.map(str::to_string),
+        });
+    }
+    if notification_data.is_empty() {
+        return;
+    }
+
+    if let Err(err) = NotificationData::create_notifications(notification_data, pool).await {
+        error!("Error created notification data: {:?}", err);
+    }
+}
@@ -0,0 +1,12 @@
+use crate::api::notification::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(handler::get_notifications)));
+
+    cfg.service(web::resource("/unread-count").route(web::get().to(handler::get_unread_count)));
+
+    cfg.service(web::resource("/read-all").route(web::post().to(handler::mark_all_read)));
+
+    cfg.service(web::resource("/{id}/read").route(web::post().to(handler::mark_read)));
+}
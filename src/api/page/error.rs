@@ -0,0 +1,36 @@
+use actix_web::{
+    error::ResponseError,
+    http::{header::ContentType, StatusCode},
+    HttpResponse,
+};
+use derive_more::{Display, Error};
+
+use super::template::render_error_page;
+
+// 공개 페이지는 브라우저에서 보므로 에러도 HTML로 응답
+#[derive(Debug, Display, Error)]
+pub enum PageError {
+    #[display(fmt = "internal error")]
+    InternalError,
+
+    #[display(fmt = "not found")]
+    NotFound,
+}
+
+impl ResponseError for PageError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::html())
+            .body(render_error_page(
+                self.status_code().as_u16(),
+                &self.to_string(),
+            ))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match *self {
+            PageError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            PageError::NotFound => StatusCode::NOT_FOUND,
+        }
+    }
+}
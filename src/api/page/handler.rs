@@ -0,0 +1,43 @@
+use super::error::PageError;
+use super::template::render_post_page;
+use crate::api::embed::model::Viewer;
+use crate::api::embed::render::render_post;
+use crate::api::post::model::Post;
+use crate::database::PgPool;
+use actix_web::{http::header::ContentType, web::Data, HttpRequest, HttpResponse, Result};
+use diesel::result::Error as DieselError;
+use log::error;
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, PageError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        PageError::NotFound
+    })
+}
+
+// GET /p/{user}/{slug}: 로그인 없이 보는 공개 노트 페이지 (공개되지 않은 노트는 404)
+pub async fn get_post_page(
+    req: HttpRequest,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, PageError> {
+    let user = path_param(&req, "user")?;
+    let slug = path_param(&req, "slug")?;
+
+    let (post, author) = match Post::get_published_post(user, slug, &pool).await {
+        Ok(published) => published,
+        Err(DieselError::NotFound) => return Err(PageError::NotFound),
+        Err(err) => {
+            error!("Error get published post data: {:?}", err);
+            return Err(PageError::InternalError);
+        }
+    };
+
+    // 공개된 노트만 임베드
+    let rendered = render_post(&post, &Viewer::Public, &pool)
+        .await
+        .map_err(|_| PageError::InternalError)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(render_post_page(&post, &author, &rendered)))
+}
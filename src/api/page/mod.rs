@@ -0,0 +1,4 @@
+pub mod error;
+pub mod handler;
+pub mod route;
+pub mod template;
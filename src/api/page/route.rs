@@ -0,0 +1,6 @@
+use crate::api::page::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/{user}/{slug}").route(web::get().to(handler::get_post_page)));
+}
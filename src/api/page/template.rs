@@ -0,0 +1,104 @@
+use crate::api::embed::model::RenderedPost;
+use crate::api::embed::render::segments_to_html;
+use crate::api::post::model::Post;
+use crate::api::user::model::User;
+
+// HTML 본문에 넣는 텍스트 이스케이프
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// 빈 줄로 나뉜 문단을 <p>로, 문단 안의 줄바꿈은 <br>로
+pub fn render_body(body: &str) -> String {
+    body.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| format!("<p>{}</p>", escape_html(paragraph).replace('\n', "<br>\n")))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// 공백을 정리한 본문 앞부분 (meta description, 피드 요약)
+pub fn summarize(body: &str, length: usize) -> String {
+    let summary: String = body.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if summary.chars().count() <= length {
+        return summary;
+    }
+    let mut summary: String = summary.chars().take(length).collect();
+    summary.push('…');
+    summary
+}
+
+fn layout(title: &str, head: &str, content: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+{head}<style>
+body {{ max-width: 720px; margin: 40px auto; padding: 0 20px; font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; line-height: 1.6; color: #222; }}
+header.author {{ display: flex; align-items: center; gap: 12px; margin-bottom: 24px; color: #555; }}
+header.author img {{ width: 40px; height: 40px; border-radius: 50%; }}
+h1 {{ line-height: 1.25; }}
+section.embed {{ margin: 16px 0; padding: 4px 16px; border-left: 3px solid #ddd; }}
+section.embed h2 {{ font-size: 1.1em; }}
+.embed-missing {{ color: #999; }}
+</style>
+</head>
+<body>
+{content}
+</body>
+</html>
+"#,
+        title = title,
+        head = head,
+        content = content,
+    )
+}
+
+// 공개된 노트 페이지 (임베드는 펼친 상태로)
+pub fn render_post_page(post: &Post, author: &User, rendered: &RenderedPost) -> String {
+    let title = escape_html(&post.title);
+    let author_name = escape_html(&author.user_name);
+    let published_at = post
+        .updated_at
+        .or(post.created_at)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+
+    let head = format!(
+        "<meta name=\"description\" content=\"{description}\">\n<meta property=\"og:title\" content=\"{title}\">\n<meta property=\"og:description\" content=\"{description}\">\n<meta property=\"og:type\" content=\"article\">\n",
+        description = escape_html(&summarize(&rendered.body, 160)),
+        title = title,
+    );
+
+    let content = format!(
+        "<article>\n<h1>{title}</h1>\n<header class=\"author\">\n<img src=\"{photo}\" alt=\"{author_name}\">\n<div><strong>{author_name}</strong><br><time>{published_at}</time></div>\n</header>\n{body}\n</article>",
+        title = title,
+        photo = escape_html(&author.photo),
+        author_name = author_name,
+        published_at = published_at,
+        body = segments_to_html(&rendered.segments),
+    );
+
+    layout(&title, &head, &content)
+}
+
+pub fn render_error_page(status: u16, message: &str) -> String {
+    let title = format!("{} {}", status, escape_html(message));
+    layout(&title, "", &format!("<h1>{}</h1>", title))
+}
@@ -0,0 +1,75 @@
+use std::cmp::max;
+
+use super::model::{PostPermission, PostRole};
+use crate::api::workspace::model::{WorkspaceMember, WorkspaceRole};
+use crate::database::PgPool;
+use actix_web::web::Data;
+use derive_more::{Display, Error};
+use log::error;
+
+#[derive(Debug, Display, Error)]
+pub enum AuthorizeError {
+    #[display(fmt = "not found")]
+    NotFound,
+
+    #[display(fmt = "forbidden")]
+    Forbidden,
+
+    #[display(fmt = "internal error")]
+    InternalError,
+}
+
+// 노트 접근 권한 확인 (모든 노트 관련 핸들러는 이 함수로만 권한을 검사)
+// 노트에 직접 받은 권한과 노트가 속한 워크스페이스 멤버 권한 중 높은 쪽을 적용하고,
+// required 이상의 권한이 있으면 사용자의 실제 권한을 반환
+pub async fn authorize(
+    post_id: &str,
+    user_id: &str,
+    required: PostRole,
+    pool: &Data<PgPool>,
+) -> Result<PostRole, AuthorizeError> {
+    let permission_role = PostPermission::get_permission(post_id, user_id, pool)
+        .await
+        .map_err(|err| {
+            error!("Error get post permission data: {:?}", err);
+            AuthorizeError::InternalError
+        })?
+        .and_then(|permission| permission.role());
+
+    let workspace_role = WorkspaceMember::get_role_by_post(post_id, user_id, pool)
+        .await
+        .map_err(|err| {
+            error!("Error get workspace member data: {:?}", err);
+            AuthorizeError::InternalError
+        })?
+        .map(|role| role.post_role());
+
+    match max(permission_role, workspace_role) {
+        Some(role) if role >= required => Ok(role),
+        Some(_) => Err(AuthorizeError::Forbidden),
+        // 권한이 전혀 없으면 노트 존재 여부를 드러내지 않음
+        None => Err(AuthorizeError::NotFound),
+    }
+}
+
+// 워크스페이스 멤버 권한 확인
+pub async fn authorize_workspace(
+    workspace_id: &str,
+    user_id: &str,
+    required: WorkspaceRole,
+    pool: &Data<PgPool>,
+) -> Result<WorkspaceRole, AuthorizeError> {
+    let role = WorkspaceMember::get_member(workspace_id, user_id, pool)
+        .await
+        .map_err(|err| {
+            error!("Error get workspace member data: {:?}", err);
+            AuthorizeError::InternalError
+        })?
+        .and_then(|member| member.role());
+
+    match role {
+        Some(role) if role >= required => Ok(role),
+        Some(_) => Err(AuthorizeError::Forbidden),
+        None => Err(AuthorizeError::NotFound),
+    }
+}
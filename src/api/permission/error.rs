@@ -0,0 +1,74 @@
+use actix_web::{
+    error::ResponseError,
+    http::{header::ContentType, StatusCode},
+    HttpResponse,
+};
+use derive_more::{Display, Error};
+use serde::Serialize;
+
+use super::authorize::AuthorizeError;
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    code: u16,
+    message: String,
+}
+
+#[derive(Debug, Display, Error)]
+pub enum PermissionError {
+    #[display(fmt = "internal error")]
+    InternalError,
+
+    #[display(fmt = "bad request")]
+    BadClientData,
+
+    #[display(fmt = "not found")]
+    NotFound,
+
+    #[display(fmt = "forbidden")]
+    Forbidden,
+
+    #[display(fmt = "user not found")]
+    UserNotFound,
+
+    #[display(fmt = "a post must keep at least one owner")]
+    LastOwner,
+
+    #[display(fmt = "Validation error on field: {}", field)]
+    ValidationError { field: String },
+}
+
+impl ResponseError for PermissionError {
+    fn error_response(&self) -> HttpResponse {
+        let error_response = ErrorResponse {
+            code: self.status_code().as_u16(),
+            message: self.to_string(),
+        };
+
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .json(error_response)
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match *self {
+            PermissionError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            PermissionError::BadClientData => StatusCode::BAD_REQUEST,
+            PermissionError::NotFound => StatusCode::NOT_FOUND,
+            PermissionError::Forbidden => StatusCode::FORBIDDEN,
+            PermissionError::UserNotFound => StatusCode::NOT_FOUND,
+            PermissionError::LastOwner => StatusCode::CONFLICT,
+            PermissionError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<AuthorizeError> for PermissionError {
+    fn from(err: AuthorizeError) -> Self {
+        match err {
+            AuthorizeError::NotFound => PermissionError::NotFound,
+            AuthorizeError::Forbidden => PermissionError::Forbidden,
+            AuthorizeError::InternalError => PermissionError::InternalError,
+        }
+    }
+}
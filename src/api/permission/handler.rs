@@ -0,0 +1,228 @@
+use super::authorize::authorize;
+use super::error::PermissionError;
+use super::model::{
+    InviteCollaborator, PostPermission, PostPermissionData, PostRole, UpdateCollaborator,
+};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::notification::model::NotificationKind;
+use crate::api::notification::notify::{notify, Notice};
+use crate::api::post::model::Post;
+use crate::api::sync::model::PostTombstone;
+use crate::api::user::model::User;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::ContentType,
+    web::{self, Data},
+    HttpRequest, HttpResponse, Result,
+};
+use log::error;
+use serde_json::to_vec;
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, PermissionError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        PermissionError::BadClientData
+    })
+}
+
+async fn get_current_role(
+    post_id: &str,
+    user_id: &str,
+    pool: &Data<PgPool>,
+) -> Result<Option<PostRole>, PermissionError> {
+    PostPermission::get_permission(post_id, user_id, pool)
+        .await
+        .map(|permission| permission.and_then(|permission| permission.role()))
+        .map_err(|err| {
+            error!("Error get post permission data: {:?}", err);
+            PermissionError::InternalError
+        })
+}
+
+// 새 협업자에게 공유 알림
+async fn notify_shared(
+    post_id: &str,
+    actor_id: &str,
+    user_id: &str,
+    role: PostRole,
+    pool: &Data<PgPool>,
+) {
+    let title = Post::get_posts_load_by_id(post_id, pool)
+        .await
+        .map(|post| post.title)
+        .unwrap_or_default();
+    let actor = User::get_users_by_id(actor_id, pool)
+        .await
+        .map(|user| user.user_name)
+        .unwrap_or_else(|_| "Someone".to_string());
+
+    let notice = Notice {
+        kind: NotificationKind::Share,
+        post_id: Some(post_id),
+        actor_id: Some(actor_id),
+        message: format!(
+            "{} shared \"{}\" with you as {}",
+            actor,
+            title,
+            role.as_str()
+        ),
+    };
+    notify(&[user_id], notice, pool).await;
+}
+
+pub async fn get_collaborators(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, PermissionError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Viewer, &pool).await?;
+
+    match PostPermission::get_collaborators(post_id, &pool).await {
+        Ok(collaborators) => {
+            let json_bytes = to_vec(&collaborators).map_err(|err| {
+                error!("Failed to serialize collaborators to JSON: {:?}", err);
+                PermissionError::InternalError
+            })?;
+
+            Ok(HttpResponse::Ok()
+                .content_type(ContentType::json())
+                .body(json_bytes))
+        }
+        Err(err) => {
+            error!("Error get collaborators data: {:?}", err);
+            Err(PermissionError::InternalError)
+        }
+    }
+}
+
+pub async fn invite_collaborator(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: web::Json<InviteCollaborator>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, PermissionError> {
+    let post_id = path_param(&req, "id")?;
+    let invite = _body.into_inner();
+    authorize(post_id, &auth_guard.user_id, PostRole::Owner, &pool).await?;
+
+    let email = invite.email.trim().to_lowercase();
+    let user = User::get_users_by_email(&email, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get users by email data: {:?}", err);
+            PermissionError::UserNotFound
+        })?;
+
+    // 이미 협업자면 권한만 변경
+    let current = get_current_role(post_id, &user.id, &pool).await?;
+    let result = match current {
+        Some(_) => PostPermission::update_role(post_id, &user.id, invite.role, &pool).await,
+        None => {
+            let permission_data = PostPermissionData {
+                post_id: post_id.to_string(),
+                user_id: user.id.clone(),
+                role: invite.role.as_str().to_string(),
+            };
+            PostPermissionData::create_permission(permission_data, &pool)
+                .await
+                .map(Some)
+        }
+    };
+
+    match result {
+        Ok(None) => Err(PermissionError::LastOwner),
+        Ok(Some(_)) => {
+            if current.is_none() {
+                if let Err(err) =
+                    PostTombstone::record_gained_access(&user.id, &[post_id.to_string()], &pool)
+                        .await
+                {
+                    error!("Error deleted post tombstone data: {:?}", err);
+                }
+                notify_shared(post_id, &auth_guard.user_id, &user.id, invite.role, &pool).await;
+            }
+
+            Ok(HttpResponse::Created().json(serde_json::json!({
+            "user_id": user.id,
+            "email": user.email,
+            "user_name": user.user_name,
+            "photo": user.photo,
+            "role": invite.role,
+            })))
+        }
+        Err(err) => {
+            error!("Error created new post permission data: {:?}", err);
+            Err(PermissionError::InternalError)
+        }
+    }
+}
+
+pub async fn update_collaborator(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: web::Json<UpdateCollaborator>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, PermissionError> {
+    let post_id = path_param(&req, "id")?;
+    let user_id = path_param(&req, "user_id")?;
+    let role = _body.into_inner().role;
+    authorize(post_id, &auth_guard.user_id, PostRole::Owner, &pool).await?;
+
+    match PostPermission::update_role(post_id, user_id, role, &pool).await {
+        Ok(None) => Err(PermissionError::LastOwner),
+        Ok(Some(0)) => {
+            error!("Update failed");
+            Err(PermissionError::NotFound)
+        }
+        Err(err) => {
+            error!("Error updated post permission data: {:?}", err);
+            Err(PermissionError::InternalError)
+        }
+        Ok(Some(_)) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("updated collaborator role")),
+    }
+}
+
+pub async fn delete_collaborator(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, PermissionError> {
+    let post_id = path_param(&req, "id")?;
+    let user_id = path_param(&req, "user_id")?;
+
+    // 본인은 스스로 나갈 수 있고, 다른 사람을 제거하려면 owner 권한 필요
+    let required = if user_id == auth_guard.user_id {
+        PostRole::Viewer
+    } else {
+        PostRole::Owner
+    };
+    authorize(post_id, &auth_guard.user_id, required, &pool).await?;
+
+    let result = PostPermission::delete_permission(post_id, user_id, &pool).await;
+    if let Ok(Some(1)) = result {
+        // 워크스페이스 멤버로 여전히 볼 수 있으면 남기지 않음
+        if let Err(err) =
+            PostTombstone::record_lost_access(user_id, &[post_id.to_string()], &pool).await
+        {
+            error!("Error created post tombstone data: {:?}", err);
+        }
+    }
+
+    match result {
+        Ok(None) => Err(PermissionError::LastOwner),
+        Ok(Some(0)) => {
+            error!("Delete failed");
+            Err(PermissionError::NotFound)
+        }
+        Err(err) => {
+            error!("Error deleted post permission data: {:?}", err);
+            Err(PermissionError::InternalError)
+        }
+        Ok(Some(_)) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("deleted collaborator")),
+    }
+}
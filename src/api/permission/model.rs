@@ -0,0 +1,188 @@
+use std::str::FromStr;
+
+use crate::database::PgPool;
+use crate::schema::{post_permissions, users};
+use actix_web::web::Data;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::{result::Error, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+// 노트 권한 (선언 순서가 권한 크기: viewer < commenter < editor < owner)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum PostRole {
+    Viewer,
+    Commenter,
+    Editor,
+    Owner,
+}
+
+impl PostRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostRole::Viewer => "viewer",
+            PostRole::Commenter => "commenter",
+            PostRole::Editor => "editor",
+            PostRole::Owner => "owner",
+        }
+    }
+}
+
+impl FromStr for PostRole {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "viewer" => Ok(PostRole::Viewer),
+            "commenter" => Ok(PostRole::Commenter),
+            "editor" => Ok(PostRole::Editor),
+            "owner" => Ok(PostRole::Owner),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::post_permissions)]
+pub struct PostPermission {
+    pub post_id: String,
+    pub user_id: String,
+    pub role: String,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::post_permissions)]
+pub struct PostPermissionData {
+    pub post_id: String,
+    pub user_id: String,
+    pub role: String,
+}
+
+// 협업자 목록 응답 (권한 + 사용자 정보)
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+pub struct Collaborator {
+    pub user_id: String,
+    pub email: String,
+    pub user_name: String,
+    pub photo: String,
+    pub role: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct InviteCollaborator {
+    pub email: String,
+    pub role: PostRole,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdateCollaborator {
+    pub role: PostRole,
+}
+
+impl PostPermission {
+    pub fn role(&self) -> Option<PostRole> {
+        self.role.parse::<PostRole>().ok()
+    }
+
+    pub async fn get_permission(
+        post_id: &str,
+        user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Option<PostPermission>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        post_permissions::table
+            .find((post_id, user_id))
+            .get_result::<PostPermission>(conn)
+            .optional()
+    }
+
+    pub async fn get_collaborators(
+        post_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Collaborator>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        post_permissions::table
+            .inner_join(users::table)
+            .filter(post_permissions::post_id.eq(post_id))
+            .order(post_permissions::created_at.asc())
+            .select((
+                users::id,
+                users::email,
+                users::user_name,
+                users::photo,
+                post_permissions::role,
+            ))
+            .load::<Collaborator>(conn)
+    }
+
+    // 마지막 owner의 권한을 낮추면 None
+    pub async fn update_role(
+        post_id: &str,
+        user_id: &str,
+        role: PostRole,
+        pool: &Data<PgPool>,
+    ) -> Result<Option<usize>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            if role != PostRole::Owner && !keeps_other_owner(conn, post_id, user_id)? {
+                return Ok(None);
+            }
+
+            diesel::update(post_permissions::table.find((post_id, user_id)))
+                .set((
+                    post_permissions::role.eq(role.as_str()),
+                    post_permissions::updated_at.eq(Some(Utc::now().naive_utc())),
+                ))
+                .execute(conn)
+                .map(Some)
+        })
+    }
+
+    // 마지막 owner를 제거하면 None
+    pub async fn delete_permission(
+        post_id: &str,
+        user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Option<usize>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            if !keeps_other_owner(conn, post_id, user_id)? {
+                return Ok(None);
+            }
+
+            diesel::delete(post_permissions::table.find((post_id, user_id)))
+                .execute(conn)
+                .map(Some)
+        })
+    }
+}
+
+// user_id의 owner 권한이 없어져도 다른 owner가 남는지 확인
+// owner 행을 잠그므로 두 owner가 동시에 서로를 내려도 한쪽은 기다린 뒤 다시 확인함
+fn keeps_other_owner(conn: &mut PgConnection, post_id: &str, user_id: &str) -> Result<bool, Error> {
+    let owners = post_permissions::table
+        .filter(post_permissions::post_id.eq(post_id))
+        .filter(post_permissions::role.eq(PostRole::Owner.as_str()))
+        .select(post_permissions::user_id)
+        .for_update()
+        .load::<String>(conn)?;
+
+    Ok(!owners.iter().any(|owner| owner == user_id) || owners.len() > 1)
+}
+
+impl PostPermissionData {
+    pub async fn create_permission(
+        permission_data: PostPermissionData,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::insert_into(post_permissions::table)
+            .values(permission_data)
+            .execute(conn)
+    }
+}
@@ -0,0 +1,16 @@
+use crate::api::permission::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(handler::get_collaborators))
+            .route(web::post().to(handler::invite_collaborator)),
+    );
+
+    cfg.service(
+        web::resource("/{user_id}")
+            .route(web::put().to(handler::update_collaborator))
+            .route(web::delete().to(handler::delete_collaborator)),
+    );
+}
@@ -6,6 +6,8 @@ use actix_web::{
 use derive_more::{Display, Error};
 use serde::Serialize;
 
+use crate::api::permission::authorize::AuthorizeError;
+
 #[derive(Debug, Serialize)]
 struct ErrorResponse {
     code: u16,
@@ -23,8 +25,17 @@ pub enum PostError {
     #[display(fmt = "timeout")]
     Timeout,
 
+    #[display(fmt = "not found")]
+    NotFound,
+
+    #[display(fmt = "forbidden")]
+    Forbidden,
+
     #[display(fmt = "Validation error on field: {}", field)]
     ValidationError { field: String },
+
+    #[display(fmt = "slug already in use")]
+    SlugTaken,
 }
 
 impl ResponseError for PostError {
@@ -44,7 +55,20 @@ impl ResponseError for PostError {
             PostError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
             PostError::BadClientData => StatusCode::BAD_REQUEST,
             PostError::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            PostError::NotFound => StatusCode::NOT_FOUND,
+            PostError::Forbidden => StatusCode::FORBIDDEN,
             PostError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+            PostError::SlugTaken => StatusCode::CONFLICT,
+        }
+    }
+}
+
+impl From<AuthorizeError> for PostError {
+    fn from(err: AuthorizeError) -> Self {
+        match err {
+            AuthorizeError::NotFound => PostError::NotFound,
+            AuthorizeError::Forbidden => PostError::Forbidden,
+            AuthorizeError::InternalError => PostError::InternalError,
         }
     }
 }
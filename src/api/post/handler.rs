@@ -1,18 +1,125 @@
 use super::error::PostError;
-use super::model::{Post, PostData};
+use super::model::{Post, PostData, PostListQuery};
+use super::saved::after_post_saved;
+use super::slug::slugify;
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::collab::room::Rooms;
+use crate::api::event::bus::EventBus;
+use crate::api::event::model::{PostEvent, PostEventKind};
+use crate::api::permission::authorize::{authorize, authorize_workspace};
+use crate::api::permission::model::PostRole;
+use crate::api::sync::model::PostTombstone;
+use crate::api::workspace::model::WorkspaceRole;
+use crate::api::workspace::selector::WorkspaceSelector;
 use crate::database::PgPool;
 use actix_web::Responder;
 use actix_web::{http::header::ContentType, web, web::Data, HttpRequest, HttpResponse, Result};
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
 use log::{error, info, warn};
 use serde_json::to_vec;
 
-pub async fn get_posts(pool: Data<PgPool>) -> Result<impl Responder, PostError> {
+// 워크스페이스가 선택된 경우 해당 워크스페이스의 노트만 다룰 수 있음
+async fn check_workspace(
+    post_id: &str,
+    workspace: &WorkspaceSelector,
+    pool: &Data<PgPool>,
+) -> Result<(), PostError> {
+    if let Some(workspace_id) = &workspace.0 {
+        let post = Post::get_posts_load_by_id(post_id, pool)
+            .await
+            .map_err(|_| PostError::NotFound)?;
+        if post.workspace_id.as_deref() != Some(workspace_id.as_str()) {
+            return Err(PostError::NotFound);
+        }
+    }
+
+    Ok(())
+}
+
+// 비공개 예약은 공개 예약보다 뒤여야 함
+// 수정이면 보내지 않은 예약은 저장된 값과 합쳐서 확인 (공개 예약을 처리한 바로 뒤에 비공개 예약을 처리하므로)
+fn check_schedule(post_data: &PostData, stored: Option<&Post>) -> Result<(), PostError> {
+    let publish_at = post_data
+        .publish_at
+        .unwrap_or_else(|| stored.and_then(|post| post.publish_at));
+    let unpublish_at = post_data
+        .unpublish_at
+        .unwrap_or_else(|| stored.and_then(|post| post.unpublish_at));
+
+    if let (Some(publish_at), Some(unpublish_at)) = (publish_at, unpublish_at) {
+        if unpublish_at <= publish_at {
+            return Err(PostError::ValidationError {
+                field: "unpublish_at".to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// GET /api/posts?scheduled=true: 공개/비공개가 예약된 노트
+async fn get_scheduled_posts(
+    auth_guard: &AuthenticationGuard,
+    workspace: &WorkspaceSelector,
+    pool: &Data<PgPool>,
+) -> Result<HttpResponse, PostError> {
+    if let Some(workspace_id) = &workspace.0 {
+        authorize_workspace(
+            workspace_id,
+            &auth_guard.user_id,
+            WorkspaceRole::Viewer,
+            pool,
+        )
+        .await?;
+    }
+
+    let post_list = Post::get_scheduled_posts(&auth_guard.user_id, workspace.0.as_deref(), pool)
+        .await
+        .map_err(|err| {
+            error!("Error get scheduled posts data: {:?}", err);
+            PostError::InternalError
+        })?;
+
+    let json_bytes = to_vec(&post_list).map_err(|err| {
+        error!("Failed to serialize posts to JSON: {:?}", err);
+        PostError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
+
+pub async fn get_posts(
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
+    query: web::Query<PostListQuery>,
+    pool: Data<PgPool>,
+) -> Result<impl Responder, PostError> {
     info!("로깅 테스트");
     warn!("로깅 테스트2");
 
+    if query.scheduled == Some(true) {
+        return get_scheduled_posts(&auth_guard, &workspace, &pool).await;
+    }
+
     // let post_list = Post::get_posts_load(&pool).await;
 
-    match Post::get_posts(&pool).await {
+    let post_list = match &workspace.0 {
+        Some(workspace_id) => {
+            authorize_workspace(
+                workspace_id,
+                &auth_guard.user_id,
+                WorkspaceRole::Viewer,
+                &pool,
+            )
+            .await?;
+            Post::get_posts_by_workspace(workspace_id, &pool).await
+        }
+        None => Post::get_posts(&auth_guard.user_id, &pool).await,
+    };
+
+    match post_list {
         Ok(post_data) => {
             let json_bytes = to_vec(&post_data).map_err(|err| {
                 error!("Failed to serialize posts to JSON: {:?}", err);
@@ -35,8 +142,15 @@ pub async fn get_posts(pool: Data<PgPool>) -> Result<impl Responder, PostError>
 
 pub async fn get_posts_by_id(
     req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
     pool: Data<PgPool>,
 ) -> Result<HttpResponse, PostError> {
+    if let Some(post_id) = req.match_info().get("id") {
+        authorize(post_id, &auth_guard.user_id, PostRole::Viewer, &pool).await?;
+        check_workspace(post_id, &workspace, &pool).await?;
+    }
+
     match req.match_info().get("id") {
         Some(post_id) => match Post::get_posts_by_id(post_id, &pool).await {
             Ok(post_data) => {
@@ -63,15 +177,42 @@ pub async fn get_posts_by_id(
 }
 
 pub async fn create_posts(
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
     _body: web::Json<PostData>,
     pool: Data<PgPool>,
+    events: Data<EventBus>,
 ) -> Result<HttpResponse, PostError> {
-    let post_data = _body.into_inner();
+    let mut post_data = _body.into_inner();
+    check_schedule(&post_data, None)?;
+    if let Some(workspace_id) = workspace.0 {
+        authorize_workspace(
+            &workspace_id,
+            &auth_guard.user_id,
+            WorkspaceRole::Editor,
+            &pool,
+        )
+        .await?;
+        post_data.workspace_id = Some(workspace_id);
+    }
 
-    match PostData::create_posts(post_data, &pool).await {
-        Ok(_) => Ok(HttpResponse::Ok()
-            .content_type(ContentType::json())
-            .body("created new post")),
+    let published = post_data.published == Some(true);
+    match PostData::create_posts(post_data, &auth_guard.user_id, &pool).await {
+        Ok(post_id) => {
+            after_post_saved(
+                &post_id,
+                Some(&auth_guard.user_id),
+                PostEventKind::Created,
+                published,
+                &events,
+                &pool,
+            )
+            .await;
+
+            Ok(HttpResponse::Ok()
+                .content_type(ContentType::json())
+                .body("created new post"))
+        }
         Err(err) => {
             error!("Error created new post data: {:?}", err);
             Err(PostError::BadClientData)
@@ -80,23 +221,84 @@ pub async fn create_posts(
 }
 
 pub async fn update_posts(
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
     _body: web::Json<PostData>,
     pool: Data<PgPool>,
+    rooms: Data<Rooms>,
+    events: Data<EventBus>,
 ) -> Result<HttpResponse, PostError> {
-    let post_data = _body.into_inner();
-    match post_data.id {
-        Some(_) => match PostData::update_posts(post_data, &pool).await {
+    let mut post_data = _body.into_inner();
+    if let Some(post_id) = &post_data.id {
+        authorize(post_id, &auth_guard.user_id, PostRole::Editor, &pool).await?;
+        check_workspace(post_id, &workspace, &pool).await?;
+    }
+
+    // 예약을 하나만 바꾸면 저장된 다른 예약과 비교
+    let stored = match &post_data.id {
+        Some(post_id) if post_data.publish_at.is_some() || post_data.unpublish_at.is_some() => {
+            Some(
+                Post::get_posts_load_by_id(post_id, &pool)
+                    .await
+                    .map_err(|err| {
+                        error!("Error get posts by id data: {:?}", err);
+                        PostError::InternalError
+                    })?,
+            )
+        }
+        _ => None,
+    };
+    check_schedule(&post_data, stored.as_ref())?;
+
+    if let Some(post_slug) = &post_data.slug {
+        let post_slug = slugify(post_slug);
+        if post_slug.is_empty() {
+            return Err(PostError::ValidationError {
+                field: "slug".to_string(),
+            });
+        }
+        post_data.slug = Some(post_slug);
+    }
+
+    let post_body = post_data.body.clone();
+    // 비공개였던 노트를 공개하면 published 이벤트
+    let publishing = match &post_data.id {
+        Some(post_id) if post_data.published == Some(true) => Post::get_posts_by_id(post_id, &pool)
+            .await
+            .map(|(_, _, _, published)| !published)
+            .unwrap_or(false),
+        _ => false,
+    };
+
+    match post_data.id.clone() {
+        Some(post_id) => match PostData::update_posts(post_data, &pool).await {
             Ok(0) => {
                 error!("Update failed");
                 Err(PostError::BadClientData)
             }
+            // 작성자의 다른 노트가 이미 쓰는 slug
+            Err(DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => Err(PostError::SlugTaken),
             Err(err) => {
                 error!("Error updated post data: {:?}", err);
                 Err(PostError::InternalError)
             }
-            Ok(_) => Ok(HttpResponse::Ok()
-                .content_type(ContentType::json())
-                .body("updated new post")),
+            Ok(_) => {
+                // 공동 편집 중인 문서에도 수정된 본문 반영
+                rooms.reset_body(&post_id, &post_body, &pool).await;
+                after_post_saved(
+                    &post_id,
+                    Some(&auth_guard.user_id),
+                    PostEventKind::Updated,
+                    publishing,
+                    &events,
+                    &pool,
+                )
+                .await;
+
+                Ok(HttpResponse::Ok()
+                    .content_type(ContentType::json())
+                    .body("updated new post"))
+            }
         },
         None => {
             // post_data.id가 None인 경우의 동작
@@ -107,22 +309,55 @@ pub async fn update_posts(
 
 pub async fn delete_posts_by_id(
     req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
     pool: Data<PgPool>,
+    events: Data<EventBus>,
 ) -> Result<HttpResponse, PostError> {
+    if let Some(post_id) = req.match_info().get("id") {
+        authorize(post_id, &auth_guard.user_id, PostRole::Owner, &pool).await?;
+        check_workspace(post_id, &workspace, &pool).await?;
+    }
+
+    // 삭제 후에는 권한 정보가 사라지므로 이벤트 수신자와 웹훅에 보낼 노트를 미리 구함
+    let recipients = match req.match_info().get("id") {
+        Some(post_id) => PostEvent::get_recipients(post_id, &pool)
+            .await
+            .unwrap_or_else(|err| {
+                error!("Error get post event recipients: {:?}", err);
+                Vec::new()
+            }),
+        None => Vec::new(),
+    };
+
     match req.match_info().get("id") {
-        Some(post_id) => match Post::delete_posts_by_id(post_id, &pool).await {
-            Ok(0) => {
-                error!("Delete failed");
-                Err(PostError::BadClientData)
-            }
-            Err(err) => {
-                error!("Error deleted post data: {:?}", err);
-                Err(PostError::InternalError)
+        Some(post_id) => {
+            let post = Post::get_posts_load_by_id(post_id, &pool)
+                .await
+                .map_err(|err| {
+                    error!("Error get posts by id data: {:?}", err);
+                    PostError::InternalError
+                })?;
+            match PostTombstone::delete_post(post_id, None, &recipients, &pool).await {
+                Ok(0) => {
+                    error!("Delete failed");
+                    Err(PostError::BadClientData)
+                }
+                Err(err) => {
+                    error!("Error deleted post data: {:?}", err);
+                    Err(PostError::InternalError)
+                }
+                Ok(_) => {
+                    events
+                        .publish_to(&post, PostEventKind::Deleted, recipients, &pool)
+                        .await;
+
+                    Ok(HttpResponse::Ok()
+                        .content_type(ContentType::json())
+                        .body("deleted post data"))
+                }
             }
-            Ok(_) => Ok(HttpResponse::Ok()
-                .content_type(ContentType::json())
-                .body("deleted post data")),
-        },
+        }
         None => {
             // post_id가 None인 경우의 동작
             error!("Error delete posts data");
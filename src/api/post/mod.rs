@@ -2,3 +2,6 @@ pub mod error;
 pub mod handler;
 pub mod model;
 pub mod route;
+pub mod saved;
+pub mod scheduler;
+pub mod slug;
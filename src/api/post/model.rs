@@ -1,10 +1,15 @@
+use super::slug::{slugify, unique_slug};
+use crate::api::permission::model::{PostPermissionData, PostRole};
+use crate::api::sync::model::SyncToken;
+use crate::api::user::model::User;
 use crate::database::PgPool;
 use crate::schema::posts::{self, dsl::*};
+use crate::schema::{post_permissions, users, workspace_members};
 use actix_web::web::Data;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use diesel::prelude::*;
 use diesel::{result::Error, AsChangeset, Insertable, Queryable};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
@@ -16,6 +21,15 @@ pub struct Post {
     pub published: bool,
     pub created_at: Option<chrono::NaiveDateTime>,
     pub updated_at: Option<chrono::NaiveDateTime>,
+    pub workspace_id: Option<String>,
+    pub sync_version: i64,
+    // 동기화 순서용 트랜잭션 id
+    #[serde(skip)]
+    pub sync_xid: i64,
+    pub author_id: Option<String>,
+    pub slug: Option<String>,
+    pub publish_at: Option<chrono::NaiveDateTime>,
+    pub unpublish_at: Option<chrono::NaiveDateTime>,
 }
 
 // <'a> 은 라이프타임 매개변수를 나타냄(a라는 라이프타임이 있다)
@@ -27,6 +41,36 @@ pub struct PostData {
     pub body: String,
     pub published: Option<bool>,
     pub updated_at: Option<chrono::NaiveDateTime>,
+    // 요청 본문이 아닌 워크스페이스 선택(X-Workspace-Id)으로만 지정
+    #[serde(skip_deserializing)]
+    pub workspace_id: Option<String>,
+    // 노트를 만든 사용자, 생성 시 서버에서 지정
+    #[serde(skip_deserializing)]
+    pub author_id: Option<String>,
+    // 비워두면 생성 시 제목으로 만듦
+    pub slug: Option<String>,
+    // 예약 공개/비공개 시각, 필드가 없으면 그대로 두고 null이면 예약 취소
+    #[serde(default, deserialize_with = "deserialize_schedule")]
+    pub publish_at: Option<Option<chrono::NaiveDateTime>>,
+    #[serde(default, deserialize_with = "deserialize_schedule")]
+    pub unpublish_at: Option<Option<chrono::NaiveDateTime>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PostListQuery {
+    // true면 공개/비공개가 예약된 노트만
+    pub scheduled: Option<bool>,
+}
+
+// 시간대가 포함된 RFC 3339 시각을 받아 UTC로 저장
+fn deserialize_schedule<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<chrono::NaiveDateTime>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<DateTime<Utc>>::deserialize(deserializer)
+        .map(|date| Some(date.map(|date| date.naive_utc())))
 }
 // Query (조회)할 때 (Queryable):
 
@@ -39,22 +83,146 @@ pub struct PostData {
 // 따라서 &'a str과 같이 라이프타임이 있는 참조를 사용하여 문자열을 참조하고, 데이터베이스에는 참조만 전달합니다.
 
 impl Post {
+    // 사용자가 권한을 가진 노트만 조회
     pub async fn get_posts(
+        user_id: &str,
         pool: &Data<PgPool>,
     ) -> Result<Vec<(String, String, String, bool)>, Error> {
         let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
 
         posts
+            .inner_join(post_permissions::table)
+            .filter(post_permissions::user_id.eq(user_id))
             .select((body, title, posts::id, published))
             .load::<(String, String, String, bool)>(conn)
     }
 
+    // 워크스페이스에 속한 노트 조회
+    pub async fn get_posts_by_workspace(
+        workspace: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<(String, String, String, bool)>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        posts
+            .filter(workspace_id.eq(workspace))
+            .order(created_at.desc())
+            .select((body, title, id, published))
+            .load::<(String, String, String, bool)>(conn)
+    }
+
+    // since 이후 horizon 전에 끝난 트랜잭션이 바꾼 노트 (직접 권한 또는 워크스페이스 멤버)
+    pub async fn get_posts_since(
+        user_id: &str,
+        since: SyncToken,
+        horizon: i64,
+        limit: i64,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Post>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        let permitted = post_permissions::table
+            .filter(post_permissions::user_id.eq(user_id))
+            .select(post_permissions::post_id);
+        let member_of = workspace_members::table
+            .filter(workspace_members::user_id.eq(user_id))
+            .select(workspace_members::workspace_id.nullable());
+
+        posts
+            .filter(
+                sync_xid
+                    .gt(since.xid)
+                    .or(sync_xid.eq(since.xid).and(sync_version.gt(since.version))),
+            )
+            .filter(sync_xid.lt(horizon))
+            .filter(id.eq_any(permitted).or(workspace_id.eq_any(member_of)))
+            .order((sync_xid.asc(), sync_version.asc()))
+            .limit(limit)
+            .load::<Post>(conn)
+    }
+
+    // 공개/비공개가 예약된 노트 (직접 권한 또는 워크스페이스 멤버), 가까운 예약 순
+    pub async fn get_scheduled_posts(
+        user_id: &str,
+        workspace: Option<&str>,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Post>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        let mut query = posts
+            .filter(publish_at.is_not_null().or(unpublish_at.is_not_null()))
+            .order((publish_at.asc().nulls_last(), unpublish_at.asc()))
+            .into_boxed();
+
+        query = match workspace {
+            Some(workspace) => query.filter(workspace_id.eq(workspace)),
+            None => {
+                let permitted = post_permissions::table
+                    .filter(post_permissions::user_id.eq(user_id))
+                    .select(post_permissions::post_id);
+                let member_of = workspace_members::table
+                    .filter(workspace_members::user_id.eq(user_id))
+                    .select(workspace_members::workspace_id.nullable());
+                query.filter(id.eq_any(permitted).or(workspace_id.eq_any(member_of)))
+            }
+        };
+
+        query.load::<Post>(conn)
+    }
+
+    // 예약 시각이 지난 노트 공개, 공개된 노트 id 반환
+    pub async fn publish_due_posts(pool: &Data<PgPool>) -> Result<Vec<String>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        let now = Utc::now().naive_utc();
+
+        diesel::update(posts.filter(publish_at.le(now)))
+            .set((
+                published.eq(true),
+                publish_at.eq(None::<chrono::NaiveDateTime>),
+                updated_at.eq(now),
+            ))
+            .returning(id)
+            .get_results::<String>(conn)
+    }
+
+    // 비공개 예약 시각이 지난 노트 비공개 (공개 예약이 남아있으면 그 뒤에 처리)
+    pub async fn unpublish_due_posts(pool: &Data<PgPool>) -> Result<Vec<String>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        let now = Utc::now().naive_utc();
+
+        diesel::update(
+            posts
+                .filter(unpublish_at.le(now))
+                .filter(publish_at.is_null()),
+        )
+        .set((
+            published.eq(false),
+            unpublish_at.eq(None::<chrono::NaiveDateTime>),
+            updated_at.eq(now),
+        ))
+        .returning(id)
+        .get_results::<String>(conn)
+    }
+
     pub async fn get_posts_load(pool: &Data<PgPool>) -> Result<Vec<Post>, Error> {
         let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
         // use crate::schema::posts::{dsl::*}로 인해서 posts::table을 posts로 사용가능
         posts.load::<Post>(conn)
     }
 
+    pub async fn get_posts_load_by_workspace(
+        workspace: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Post>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        posts.filter(workspace_id.eq(workspace)).load::<Post>(conn)
+    }
+
+    pub async fn get_posts_load_by_id(post_id: &str, pool: &Data<PgPool>) -> Result<Post, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        posts.find(post_id).get_result::<Post>(conn)
+    }
+
     pub async fn get_posts_by_id(
         post_id: &str,
         pool: &Data<PgPool>,
@@ -72,6 +240,40 @@ impl Post {
         // .load::<(String, String, String, bool)>(conn)
     }
 
+    // 공개 페이지: 작성자와 slug로 공개된 노트 조회
+    pub async fn get_published_post(
+        author: &str,
+        post_slug: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<(Post, User), Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        posts
+            .inner_join(users::table)
+            .filter(author_id.eq(author).or(users::handle.eq(author)))
+            .filter(slug.eq(post_slug))
+            .filter(published.eq(true))
+            .select((posts::all_columns, users::all_columns))
+            .get_result::<(Post, User)>(conn)
+    }
+
+    // 피드: 작성자의 공개된 노트를 최근 수정 순으로
+    pub async fn get_published_posts_by_author(
+        author: &str,
+        limit: i64,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Post>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        posts
+            .filter(author_id.eq(author))
+            .filter(published.eq(true))
+            .filter(slug.is_not_null())
+            .order((updated_at.desc().nulls_last(), created_at.desc()))
+            .limit(limit)
+            .load::<Post>(conn)
+    }
+
     pub async fn delete_posts_by_id(post_id: &str, pool: &Data<PgPool>) -> Result<usize, Error> {
         let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
         diesel::delete(posts.find(post_id)).execute(conn)
@@ -79,16 +281,57 @@ impl Post {
 }
 
 impl PostData {
-    pub async fn create_posts(post_data: PostData, pool: &Data<PgPool>) -> Result<(), Error> {
+    // 노트 생성과 동시에 작성자에게 owner 권한 부여
+    pub async fn create_posts(
+        post_data: PostData,
+        user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<String, Error> {
         let post = PostData {
             id: Some(Uuid::new_v4().to_string()),
             ..post_data
         };
 
+        PostData::insert_posts(post, user_id, pool).await
+    }
+
+    // 클라이언트가 정한 id로 노트 생성 (오프라인 동기화)
+    pub async fn insert_posts(
+        post_data: PostData,
+        user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<String, Error> {
+        let post_id = post_data.id.clone().unwrap_or_default();
+        let base_slug = slugify(post_data.slug.as_deref().unwrap_or(&post_data.title));
+        let permission = PostPermissionData {
+            post_id: post_id.clone(),
+            user_id: user_id.to_string(),
+            role: PostRole::Owner.as_str().to_string(),
+        };
+
         let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
 
-        diesel::insert_into(posts).values(post).execute(conn)?;
-        Ok(())
+        conn.transaction(|conn| {
+            let taken = posts
+                .filter(author_id.eq(user_id))
+                .filter(slug.like(format!("{}%", base_slug)))
+                .select(slug)
+                .load::<Option<String>>(conn)?
+                .into_iter()
+                .flatten()
+                .collect::<Vec<String>>();
+            let post = PostData {
+                author_id: Some(user_id.to_string()),
+                slug: Some(unique_slug(&base_slug, &taken)),
+                ..post_data
+            };
+
+            diesel::insert_into(posts).values(post).execute(conn)?;
+            diesel::insert_into(post_permissions::table)
+                .values(permission)
+                .execute(conn)?;
+            Ok(post_id)
+        })
     }
 
     pub async fn update_posts(post_data: PostData, pool: &Data<PgPool>) -> Result<usize, Error> {
@@ -106,4 +349,26 @@ impl PostData {
             // .get_result::<Post>(conn)
             .execute(conn)
     }
+
+    // 클라이언트가 알고 있던 버전일 때만 수정, 새 버전 반환 (다르면 None)
+    pub async fn update_posts_if_version(
+        post_data: PostData,
+        base_version: i64,
+        pool: &Data<PgPool>,
+    ) -> Result<Option<i64>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        let post_id = post_data.id.clone().unwrap_or_default();
+
+        let post = PostData {
+            id: None,
+            updated_at: Some(Utc::now().naive_utc()),
+            ..post_data
+        };
+
+        diesel::update(posts.find(post_id).filter(sync_version.eq(base_version)))
+            .set(post)
+            .returning(sync_version)
+            .get_result::<i64>(conn)
+            .optional()
+    }
 }
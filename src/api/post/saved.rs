@@ -0,0 +1,29 @@
+use crate::api::event::bus::EventBus;
+use crate::api::event::model::PostEventKind;
+use crate::api::link::model::PostLink;
+use crate::api::mention::model::Mention;
+use crate::api::task::model::Task;
+use crate::database::PgPool;
+use actix_web::web::Data;
+
+// 노트를 만들거나 본문을 저장한 뒤 링크, 할 일, 멘션을 다시 읽고 이벤트 발행
+// kind: Created 또는 Updated, actor: 저장한 사용자 (협업 편집처럼 알 수 없으면 None)
+// newly_published: 이번 저장으로 공개됐으면 published 이벤트도 보냄
+pub async fn after_post_saved(
+    post_id: &str,
+    actor: Option<&str>,
+    kind: PostEventKind,
+    newly_published: bool,
+    events: &EventBus,
+    pool: &Data<PgPool>,
+) {
+    PostLink::refresh_links(post_id, pool).await;
+    Task::refresh_tasks(post_id, pool).await;
+    Mention::refresh_post_mentions(post_id, actor, pool).await;
+    events.publish(post_id, kind, pool).await;
+    if newly_published {
+        events
+            .publish(post_id, PostEventKind::Published, pool)
+            .await;
+    }
+}
@@ -0,0 +1,56 @@
+use std::env;
+use std::time::Duration;
+
+use super::model::Post;
+use crate::api::event::bus::EventBus;
+use crate::api::event::model::PostEventKind;
+use crate::database::PgPool;
+use actix_web::web::Data;
+use log::{error, info};
+
+// 예약된 공개/비공개 처리 주기 (초)
+const DEFAULT_SCHEDULE_INTERVAL: u64 = 30;
+
+// 예약 시각은 DB에 있으므로 재시작하면 밀린 예약부터 바로 처리
+pub fn spawn_scheduler(pool: Data<PgPool>, events: Data<EventBus>) {
+    let interval = env::var("PUBLISH_SCHEDULE_INTERVAL")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_SCHEDULE_INTERVAL);
+
+    actix_web::rt::spawn(async move {
+        loop {
+            run_due(&pool, &events).await;
+            actix_web::rt::time::sleep(Duration::from_secs(interval)).await;
+        }
+    });
+}
+
+async fn run_due(pool: &Data<PgPool>, events: &Data<EventBus>) {
+    // 공개 먼저 처리해야 같은 주기에 지난 비공개 예약도 이어서 처리됨
+    let published = Post::publish_due_posts(pool).await.unwrap_or_else(|err| {
+        error!("Error publishing scheduled posts: {:?}", err);
+        Vec::new()
+    });
+    let unpublished = Post::unpublish_due_posts(pool).await.unwrap_or_else(|err| {
+        error!("Error unpublishing scheduled posts: {:?}", err);
+        Vec::new()
+    });
+
+    for post_id in published.iter().chain(unpublished.iter()) {
+        events.publish(post_id, PostEventKind::Updated, pool).await;
+    }
+    for post_id in &published {
+        events
+            .publish(post_id, PostEventKind::Published, pool)
+            .await;
+    }
+    if !published.is_empty() || !unpublished.is_empty() {
+        info!(
+            "Scheduled posts: {} published, {} unpublished",
+            published.len(),
+            unpublished.len()
+        );
+    }
+}
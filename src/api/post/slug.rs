@@ -0,0 +1,32 @@
+// 공개 페이지 주소(/p/{user}/{slug})에 쓰는 slug
+const MAX_SLUG_LENGTH: usize = 80;
+
+const DEFAULT_SLUG: &str = "note";
+
+// 소문자 + 문자/숫자만 남기고 나머지는 '-'로 (한글 등 유니코드 문자는 유지)
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    let slug: String = slug.chars().take(MAX_SLUG_LENGTH).collect();
+    slug.trim_end_matches('-').to_string()
+}
+
+// 작성자의 다른 노트와 겹치면 -2, -3 ... 을 붙임
+pub fn unique_slug(base: &str, taken: &[String]) -> String {
+    let base = if base.is_empty() { DEFAULT_SLUG } else { base };
+    if !taken.iter().any(|slug| slug == base) {
+        return base.to_string();
+    }
+
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap_or_default()
+}
@@ -0,0 +1,246 @@
+use super::error::ReminderError;
+use super::model::{
+    Reminder, ReminderData, ReminderListQuery, ReminderRequest, SnoozeRequest,
+    DEFAULT_SNOOZE_MINUTES,
+};
+use super::recurrence::{is_valid, next_occurrence};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::permission::authorize::authorize;
+use crate::api::permission::model::PostRole;
+use crate::api::user::model::User;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::ContentType,
+    web::{Data, Json, Query},
+    HttpRequest, HttpResponse, Result,
+};
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use diesel::result::Error as DieselError;
+use log::error;
+use serde_json::to_vec;
+use uuid::Uuid;
+
+// 다시 알림은 최대 1년 뒤까지
+const MAX_SNOOZE_MINUTES: i64 = 60 * 24 * 365;
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, ReminderError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        ReminderError::BadClientData
+    })
+}
+
+fn json_response<T: serde::Serialize>(data: &T) -> Result<HttpResponse, ReminderError> {
+    let json_bytes = to_vec(data).map_err(|err| {
+        error!("Failed to serialize reminders to JSON: {:?}", err);
+        ReminderError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
+
+fn not_found_or_internal(err: DieselError) -> ReminderError {
+    match err {
+        DieselError::NotFound => ReminderError::NotFound,
+        err => {
+            error!("Error reminder data: {:?}", err);
+            ReminderError::InternalError
+        }
+    }
+}
+
+fn validation_error(field: &str) -> ReminderError {
+    ReminderError::ValidationError {
+        field: field.to_string(),
+    }
+}
+
+// RFC 3339, 또는 시간대 없는 "YYYY-MM-DDTHH:MM[:SS]"는 tz 기준 (UTC, 초 단위로 반환)
+fn parse_time(value: &str, tz: Tz) -> Option<NaiveDateTime> {
+    let time = match DateTime::parse_from_rfc3339(value) {
+        Ok(time) => time.naive_utc(),
+        Err(_) => {
+            let local = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+                .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M"))
+                .ok()?;
+            // 서머타임으로 없는 시각은 받지 않음
+            tz.from_local_datetime(&local).earliest()?.naive_utc()
+        }
+    };
+
+    time.with_nanosecond(0)
+}
+
+async fn get_user(user_id: &str, pool: &Data<PgPool>) -> Result<User, ReminderError> {
+    User::get_users_by_id(user_id, pool).await.map_err(|err| {
+        error!("Error get users by id data: {:?}", err);
+        ReminderError::InternalError
+    })
+}
+
+// POST /api/posts/{id}/reminders: 노트 리마인더 만들기
+pub async fn create_reminder(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: Json<ReminderRequest>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, ReminderError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Viewer, &pool).await?;
+
+    let request = _body.into_inner();
+    let user = get_user(&auth_guard.user_id, &pool).await?;
+    let tz = user.tz();
+    let now = Utc::now().naive_utc();
+
+    let starts_at =
+        parse_time(&request.remind_at, tz).ok_or_else(|| validation_error("remind_at"))?;
+    let rrule = request
+        .rrule
+        .map(|rrule| {
+            let rrule = rrule.trim();
+            rrule.strip_prefix("RRULE:").unwrap_or(rrule).to_string()
+        })
+        .filter(|rrule| !rrule.is_empty());
+
+    // 반복이면 지금 이후 첫 반복부터
+    let fire_at = match &rrule {
+        Some(rrule) => {
+            if !is_valid(rrule, starts_at, tz) {
+                return Err(validation_error("rrule"));
+            }
+            let after = now.max(starts_at - Duration::seconds(1));
+            next_occurrence(rrule, starts_at, tz, after).ok_or_else(|| validation_error("rrule"))?
+        }
+        None if starts_at <= now => return Err(validation_error("remind_at")),
+        None => starts_at,
+    };
+
+    let reminder_data = ReminderData {
+        id: Uuid::new_v4().to_string(),
+        post_id: post_id.to_string(),
+        user_id: user.id.clone(),
+        message: request
+            .message
+            .map(|message| message.trim().to_string())
+            .filter(|message| !message.is_empty()),
+        starts_at,
+        rrule,
+        timezone: tz.name().to_string(),
+        fire_at,
+        email: request.email.unwrap_or(false),
+    };
+
+    match ReminderData::create_reminder(reminder_data, &pool).await {
+        Ok(reminder) => Ok(HttpResponse::Created().json(reminder)),
+        Err(err) => {
+            error!("Error created new reminder data: {:?}", err);
+            Err(ReminderError::InternalError)
+        }
+    }
+}
+
+// GET /api/posts/{id}/reminders: 이 노트에 내가 만든 리마인더
+pub async fn get_post_reminders(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, ReminderError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Viewer, &pool).await?;
+
+    match Reminder::get_reminders(&auth_guard.user_id, Some(post_id), None, &pool).await {
+        Ok(reminders) => json_response(&reminders),
+        Err(err) => {
+            error!("Error get reminders data: {:?}", err);
+            Err(ReminderError::InternalError)
+        }
+    }
+}
+
+// GET /api/reminders?active=: 내 리마인더, 다음 울릴 시각 순
+pub async fn get_reminders(
+    auth_guard: AuthenticationGuard,
+    query: Query<ReminderListQuery>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, ReminderError> {
+    match Reminder::get_reminders(&auth_guard.user_id, None, query.active, &pool).await {
+        Ok(reminders) => json_response(&reminders),
+        Err(err) => {
+            error!("Error get reminders data: {:?}", err);
+            Err(ReminderError::InternalError)
+        }
+    }
+}
+
+// POST /api/reminders/{id}/snooze: 다시 알림 (울린 뒤나 해제한 뒤에도 가능)
+pub async fn snooze_reminder(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: Json<SnoozeRequest>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, ReminderError> {
+    let reminder_id = path_param(&req, "id")?;
+    Reminder::get_reminder_by_id(reminder_id, &auth_guard.user_id, &pool)
+        .await
+        .map_err(not_found_or_internal)?;
+
+    let request = _body.into_inner();
+    let now = Utc::now().naive_utc();
+    let until = match &request.until {
+        Some(until) => {
+            let user = get_user(&auth_guard.user_id, &pool).await?;
+            parse_time(until, user.tz())
+                .filter(|until| *until > now)
+                .ok_or_else(|| validation_error("until"))?
+        }
+        None => {
+            let minutes = request.minutes.unwrap_or(DEFAULT_SNOOZE_MINUTES);
+            if !(1..=MAX_SNOOZE_MINUTES).contains(&minutes) {
+                return Err(validation_error("minutes"));
+            }
+            now.with_nanosecond(0).unwrap_or(now) + Duration::minutes(minutes)
+        }
+    };
+
+    match Reminder::snooze_reminder(reminder_id, &auth_guard.user_id, until, &pool).await {
+        Ok(reminder) => json_response(&reminder),
+        Err(err) => Err(not_found_or_internal(err)),
+    }
+}
+
+// POST /api/reminders/{id}/dismiss: 리마인더 끄기
+pub async fn dismiss_reminder(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, ReminderError> {
+    let reminder_id = path_param(&req, "id")?;
+
+    match Reminder::dismiss_reminder(reminder_id, &auth_guard.user_id, &pool).await {
+        Ok(reminder) => json_response(&reminder),
+        Err(err) => Err(not_found_or_internal(err)),
+    }
+}
+
+pub async fn delete_reminder(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, ReminderError> {
+    let reminder_id = path_param(&req, "id")?;
+
+    match Reminder::delete_reminder(reminder_id, &auth_guard.user_id, &pool).await {
+        Ok(0) => Err(ReminderError::NotFound),
+        Ok(_) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("deleted reminder")),
+        Err(err) => {
+            error!("Error deleted reminder data: {:?}", err);
+            Err(ReminderError::InternalError)
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod error;
+pub mod handler;
+pub mod model;
+pub mod recurrence;
+pub mod route;
+pub mod scheduler;
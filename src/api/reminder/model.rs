@@ -0,0 +1,235 @@
+use crate::api::notification::model::NotificationData;
+use crate::database::PgPool;
+use crate::schema::notifications;
+use crate::schema::reminders::dsl::*;
+use actix_web::web::Data;
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::{result::Error, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+// 한 번에 처리할 리마인더 수
+pub const REMINDER_BATCH_SIZE: i64 = 100;
+
+// 시각을 주지 않은 다시 알림 간격 (분)
+pub const DEFAULT_SNOOZE_MINUTES: i64 = 10;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::reminders)]
+pub struct Reminder {
+    pub id: String,
+    pub post_id: String,
+    pub user_id: String,
+    pub message: Option<String>,
+    pub starts_at: NaiveDateTime,
+    pub rrule: Option<String>,
+    pub timezone: String,
+    pub fire_at: NaiveDateTime,
+    pub email: bool,
+    pub active: bool,
+    pub last_fired_at: Option<NaiveDateTime>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::reminders)]
+pub struct ReminderData {
+    pub id: String,
+    pub post_id: String,
+    pub user_id: String,
+    pub message: Option<String>,
+    pub starts_at: NaiveDateTime,
+    pub rrule: Option<String>,
+    pub timezone: String,
+    pub fire_at: NaiveDateTime,
+    pub email: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReminderRequest {
+    // RFC 3339, 시간대가 없으면 사용자 시간대 기준
+    pub remind_at: String,
+    pub rrule: Option<String>,
+    pub message: Option<String>,
+    pub email: Option<bool>,
+}
+
+// until이 없으면 minutes(기본 10분) 뒤
+#[derive(Deserialize, Debug)]
+pub struct SnoozeRequest {
+    pub minutes: Option<i64>,
+    pub until: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReminderListQuery {
+    pub active: Option<bool>,
+}
+
+impl Reminder {
+    pub async fn get_reminders(
+        reminder_user_id: &str,
+        reminder_post_id: Option<&str>,
+        reminder_active: Option<bool>,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Reminder>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        let mut query = reminders
+            .filter(user_id.eq(reminder_user_id.to_string()))
+            .into_boxed();
+        if let Some(reminder_post_id) = reminder_post_id {
+            query = query.filter(post_id.eq(reminder_post_id.to_string()));
+        }
+        if let Some(reminder_active) = reminder_active {
+            query = query.filter(active.eq(reminder_active));
+        }
+
+        query.order(fire_at.asc()).load::<Reminder>(conn)
+    }
+
+    // 다른 사용자의 리마인더는 없는 것으로 취급
+    pub async fn get_reminder_by_id(
+        reminder_id: &str,
+        reminder_user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Reminder, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        reminders
+            .find(reminder_id)
+            .filter(user_id.eq(reminder_user_id))
+            .get_result::<Reminder>(conn)
+    }
+
+    pub async fn snooze_reminder(
+        reminder_id: &str,
+        reminder_user_id: &str,
+        until: NaiveDateTime,
+        pool: &Data<PgPool>,
+    ) -> Result<Reminder, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::update(
+            reminders
+                .find(reminder_id)
+                .filter(user_id.eq(reminder_user_id)),
+        )
+        .set((
+            fire_at.eq(until),
+            active.eq(true),
+            updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .get_result::<Reminder>(conn)
+    }
+
+    // 반복 리마인더도 이후 반복까지 모두 멈춤
+    pub async fn dismiss_reminder(
+        reminder_id: &str,
+        reminder_user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Reminder, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::update(
+            reminders
+                .find(reminder_id)
+                .filter(user_id.eq(reminder_user_id)),
+        )
+        .set((active.eq(false), updated_at.eq(Utc::now().naive_utc())))
+        .get_result::<Reminder>(conn)
+    }
+
+    pub async fn delete_reminder(
+        reminder_id: &str,
+        reminder_user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::delete(
+            reminders
+                .find(reminder_id)
+                .filter(user_id.eq(reminder_user_id)),
+        )
+        .execute(conn)
+    }
+
+    // 울릴 시각이 지난 리마인더 (서버가 꺼져 있던 동안 밀린 것 포함)
+    pub async fn get_due_reminders(
+        now: NaiveDateTime,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Reminder>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        reminders
+            .filter(active.eq(true))
+            .filter(fire_at.le(now))
+            .order(fire_at.asc())
+            .limit(REMINDER_BATCH_SIZE)
+            .load::<Reminder>(conn)
+    }
+
+    // 다음 시각으로 넘기면서 알림을 남김 (next가 없으면 끝)
+    // 읽은 뒤 다른 곳에서 먼저 처리했으면 false
+    pub async fn fire_reminder(
+        reminder: &Reminder,
+        next: Option<NaiveDateTime>,
+        notification: NotificationData,
+        pool: &Data<PgPool>,
+    ) -> Result<bool, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        let now = Utc::now().naive_utc();
+
+        conn.transaction(|conn| {
+            let updated = diesel::update(
+                reminders
+                    .find(&reminder.id)
+                    .filter(active.eq(true))
+                    .filter(fire_at.eq(reminder.fire_at)),
+            )
+            .set((
+                fire_at.eq(next.unwrap_or(reminder.fire_at)),
+                active.eq(next.is_some()),
+                last_fired_at.eq(now),
+                updated_at.eq(now),
+            ))
+            .execute(conn)?;
+            if updated == 0 {
+                return Ok(false);
+            }
+
+            diesel::insert_into(notifications::table)
+                .values(&notification)
+                .execute(conn)?;
+
+            Ok(true)
+        })
+    }
+
+    // 노트를 더 볼 수 없게 된 리마인더
+    pub async fn deactivate_reminder(
+        reminder_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::update(reminders.find(reminder_id))
+            .set((active.eq(false), updated_at.eq(Utc::now().naive_utc())))
+            .execute(conn)
+    }
+}
+
+impl ReminderData {
+    pub async fn create_reminder(
+        reminder_data: ReminderData,
+        pool: &Data<PgPool>,
+    ) -> Result<Reminder, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::insert_into(reminders)
+            .values(&reminder_data)
+            .get_result::<Reminder>(conn)
+    }
+}
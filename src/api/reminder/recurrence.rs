@@ -0,0 +1,125 @@
+use chrono::{Duration, NaiveDateTime, TimeZone, Timelike};
+use chrono_tz::Tz;
+use rrule::{Frequency, RRule, RRuleSet, Unvalidated};
+
+// reminders.rrule
+pub const MAX_RRULE_LENGTH: usize = 255;
+
+// 반복 간격을 확인할 처음 반복 횟수
+const CHECKED_OCCURRENCES: u16 = 100;
+
+// starts_at(UTC)을 시간대 tz의 DTSTART로 둔 반복 규칙
+fn rrule_set(rrule: &str, starts_at: NaiveDateTime, tz: Tz) -> Option<RRuleSet> {
+    let rrule = rrule.trim();
+    let rrule = rrule.strip_prefix("RRULE:").unwrap_or(rrule);
+    let rrule: RRule<Unvalidated> = rrule.parse().ok()?;
+
+    let start = rrule::Tz::from(tz).from_utc_datetime(&starts_at);
+    rrule.build(start).ok()
+}
+
+// 한 시간보다 잦은 반복은 받지 않음
+// FREQ=HOURLY;BYMINUTE=0,30 처럼 BY* 규칙으로 잦아지는 경우도 있어서 처음 몇 번의 간격을 확인
+pub fn is_valid(rrule: &str, starts_at: NaiveDateTime, tz: Tz) -> bool {
+    if rrule.len() > MAX_RRULE_LENGTH {
+        return false;
+    }
+
+    let set = match rrule_set(rrule, starts_at, tz) {
+        Some(set) => set,
+        None => return false,
+    };
+    if set
+        .get_rrule()
+        .iter()
+        .any(|rule| matches!(rule.get_freq(), Frequency::Minutely | Frequency::Secondly))
+    {
+        return false;
+    }
+
+    let dates = set.all(CHECKED_OCCURRENCES).dates;
+    dates
+        .windows(2)
+        .all(|pair| pair[1] - pair[0] >= Duration::hours(1))
+}
+
+// after 이후(after는 제외) 첫 반복 시각 (UTC), 반복이 끝났으면 None
+pub fn next_occurrence(
+    rrule: &str,
+    starts_at: NaiveDateTime,
+    tz: Tz,
+    after: NaiveDateTime,
+) -> Option<NaiveDateTime> {
+    let set = rrule_set(rrule, starts_at, tz)?;
+    let after = after.with_nanosecond(0).unwrap_or(after) + Duration::seconds(1);
+
+    set.after(rrule::Tz::from(tz).from_utc_datetime(&after))
+        .all(1)
+        .dates
+        .first()
+        .map(|date| date.naive_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn starts_at() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn accepts_hourly_and_slower_rules() {
+        let tz = chrono_tz::Asia::Seoul;
+        assert!(is_valid("FREQ=HOURLY", starts_at(), tz));
+        assert!(is_valid("RRULE:FREQ=DAILY;BYHOUR=9,10", starts_at(), tz));
+        assert!(is_valid("FREQ=WEEKLY;BYDAY=MO,WE,FR", starts_at(), tz));
+        assert!(is_valid(
+            "FREQ=DAILY;BYHOUR=9,10;BYMINUTE=50",
+            starts_at(),
+            tz
+        ));
+        // 서머타임이 바뀌어도 한 시간 간격은 유지
+        assert!(is_valid(
+            "FREQ=HOURLY",
+            starts_at(),
+            chrono_tz::America::New_York
+        ));
+    }
+
+    #[test]
+    fn rejects_rules_more_frequent_than_hourly() {
+        let tz = chrono_tz::Asia::Seoul;
+        assert!(!is_valid("FREQ=MINUTELY", starts_at(), tz));
+        assert!(!is_valid("FREQ=HOURLY;BYMINUTE=0,30", starts_at(), tz));
+        assert!(!is_valid(
+            "FREQ=DAILY;BYHOUR=9;BYMINUTE=0,1,2",
+            starts_at(),
+            tz
+        ));
+        assert!(!is_valid("FREQ=DAILY;BYSECOND=0,10", starts_at(), tz));
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        let tz = chrono_tz::Asia::Seoul;
+        assert!(!is_valid("FREQ=SOMETIMES", starts_at(), tz));
+        assert!(!is_valid(&"FREQ=DAILY;".repeat(30), starts_at(), tz));
+    }
+
+    #[test]
+    fn finds_the_next_occurrence_after_a_time() {
+        let tz = chrono_tz::Asia::Seoul;
+        // 서울 09:00 = UTC 00:00
+        let next = next_occurrence("FREQ=DAILY", starts_at(), tz, starts_at());
+        assert_eq!(next, Some(starts_at() + Duration::days(1)));
+        assert_eq!(
+            next_occurrence("FREQ=DAILY;COUNT=1", starts_at(), tz, starts_at()),
+            None
+        );
+    }
+}
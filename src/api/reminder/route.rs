@@ -0,0 +1,20 @@
+use crate::api::reminder::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(handler::get_reminders)));
+
+    cfg.service(web::resource("/{id}").route(web::delete().to(handler::delete_reminder)));
+
+    cfg.service(web::resource("/{id}/snooze").route(web::post().to(handler::snooze_reminder)));
+
+    cfg.service(web::resource("/{id}/dismiss").route(web::post().to(handler::dismiss_reminder)));
+}
+
+pub fn configure_post(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(handler::get_post_reminders))
+            .route(web::post().to(handler::create_reminder)),
+    );
+}
@@ -0,0 +1,124 @@
+use std::env;
+use std::time::Duration;
+
+use super::model::Reminder;
+use super::recurrence::next_occurrence;
+use crate::api::notification::model::{NotificationData, NotificationKind};
+use crate::api::permission::authorize::{authorize, AuthorizeError};
+use crate::api::permission::model::PostRole;
+use crate::api::post::model::Post;
+use crate::api::user::model::User;
+use crate::database::PgPool;
+use crate::mailer::Mailer;
+use actix_web::web::Data;
+use chrono::Utc;
+use chrono_tz::Tz;
+use log::{error, info};
+use uuid::Uuid;
+
+// 리마인더 확인 주기 (초)
+const DEFAULT_REMINDER_INTERVAL: u64 = 30;
+
+// 울릴 시각은 DB에 있으므로 재시작하면 밀린 리마인더부터 바로 처리
+pub fn spawn_scheduler(pool: Data<PgPool>, mailer: Data<Mailer>) {
+    let interval = env::var("REMINDER_INTERVAL")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_REMINDER_INTERVAL);
+
+    actix_web::rt::spawn(async move {
+        loop {
+            run_due(&pool, &mailer).await;
+            actix_web::rt::time::sleep(Duration::from_secs(interval)).await;
+        }
+    });
+}
+
+async fn run_due(pool: &Data<PgPool>, mailer: &Data<Mailer>) {
+    let due = Reminder::get_due_reminders(Utc::now().naive_utc(), pool)
+        .await
+        .unwrap_or_else(|err| {
+            error!("Error get due reminders: {:?}", err);
+            Vec::new()
+        });
+
+    let mut fired = 0;
+    for reminder in &due {
+        if fire(reminder, pool, mailer).await {
+            fired += 1;
+        }
+    }
+    if fired > 0 {
+        info!("Reminders: {} fired", fired);
+    }
+}
+
+async fn fire(reminder: &Reminder, pool: &Data<PgPool>, mailer: &Data<Mailer>) -> bool {
+    match authorize(&reminder.post_id, &reminder.user_id, PostRole::Viewer, pool).await {
+        Ok(_) => {}
+        // 노트를 더 볼 수 없으면 리마인더를 끔
+        Err(AuthorizeError::NotFound) | Err(AuthorizeError::Forbidden) => {
+            info!("Reminder {} lost access to its note", reminder.id);
+            if let Err(err) = Reminder::deactivate_reminder(&reminder.id, pool).await {
+                error!("Error deactivating reminder: {:?}", err);
+            }
+            return false;
+        }
+        // 다음 주기에 다시 시도
+        Err(AuthorizeError::InternalError) => return false,
+    }
+
+    let post = match Post::get_posts_load_by_id(&reminder.post_id, pool).await {
+        Ok(post) => post,
+        Err(err) => {
+            error!("Error get posts by id data: {:?}", err);
+            return false;
+        }
+    };
+
+    // 반복이면 지금 이후 다음 반복, 밀린 반복은 한 번만 알림
+    let tz = reminder.timezone.parse::<Tz>().unwrap_or(Tz::UTC);
+    let next = reminder
+        .rrule
+        .as_ref()
+        .and_then(|rrule| next_occurrence(rrule, reminder.starts_at, tz, Utc::now().naive_utc()));
+
+    let message = match &reminder.message {
+        Some(message) => format!("{}: {}", post.title, message),
+        None => post.title.clone(),
+    };
+    let notification = NotificationData {
+        id: Uuid::new_v4().to_string(),
+        user_id: reminder.user_id.clone(),
+        kind: NotificationKind::Reminder.as_str().to_string(),
+        post_id: Some(reminder.post_id.clone()),
+        message: message.clone(),
+        actor_id: None,
+    };
+
+    match Reminder::fire_reminder(reminder, next, notification, pool).await {
+        Ok(true) => {}
+        // 다른 곳에서 먼저 처리함
+        Ok(false) => return false,
+        Err(err) => {
+            error!("Error firing reminder: {:?}", err);
+            return false;
+        }
+    }
+
+    if reminder.email {
+        match User::get_users_by_id(&reminder.user_id, pool).await {
+            Ok(user) => {
+                let subject = format!("Reminder: {}", post.title);
+                let body = format!("You asked to be reminded about this note.\n\n{}", message);
+                if let Err(err) = mailer.send(&user.email, &subject, &body).await {
+                    error!("Error sending reminder mail: {:?}", err);
+                }
+            }
+            Err(err) => error!("Error get users by id data: {:?}", err),
+        }
+    }
+
+    true
+}
@@ -0,0 +1,78 @@
+use actix_web::{
+    error::ResponseError,
+    http::{header::ContentType, StatusCode},
+    HttpResponse,
+};
+use derive_more::{Display, Error};
+use serde::Serialize;
+
+use crate::api::permission::authorize::AuthorizeError;
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    code: u16,
+    message: String,
+}
+
+#[derive(Debug, Display, Error)]
+pub enum ShareError {
+    #[display(fmt = "internal error")]
+    InternalError,
+
+    #[display(fmt = "bad request")]
+    BadClientData,
+
+    #[display(fmt = "not found")]
+    NotFound,
+
+    #[display(fmt = "forbidden")]
+    Forbidden,
+
+    #[display(fmt = "password required")]
+    PasswordRequired,
+
+    #[display(fmt = "invalid password")]
+    InvalidPassword,
+
+    #[display(fmt = "share link expired")]
+    Expired,
+
+    #[display(fmt = "Validation error on field: {}", field)]
+    ValidationError { field: String },
+}
+
+impl ResponseError for ShareError {
+    fn error_response(&self) -> HttpResponse {
+        let error_response = ErrorResponse {
+            code: self.status_code().as_u16(),
+            message: self.to_string(),
+        };
+
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .json(error_response)
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match *self {
+            ShareError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            ShareError::BadClientData => StatusCode::BAD_REQUEST,
+            ShareError::NotFound => StatusCode::NOT_FOUND,
+            ShareError::Forbidden => StatusCode::FORBIDDEN,
+            ShareError::PasswordRequired => StatusCode::UNAUTHORIZED,
+            ShareError::InvalidPassword => StatusCode::UNAUTHORIZED,
+            ShareError::Expired => StatusCode::GONE,
+            ShareError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<AuthorizeError> for ShareError {
+    fn from(err: AuthorizeError) -> Self {
+        match err {
+            AuthorizeError::NotFound => ShareError::NotFound,
+            AuthorizeError::Forbidden => ShareError::Forbidden,
+            AuthorizeError::InternalError => ShareError::InternalError,
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use super::error::ShareError;
+use super::model::{CreateShareLink, ShareLink, ShareLinkData, ShareLinkResponse, SharedPost};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::permission::authorize::authorize;
+use crate::api::permission::model::PostRole;
+use crate::api::post::model::Post;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::ContentType,
+    web::{self, Data},
+    HttpRequest, HttpResponse, Result,
+};
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, SaltString};
+use argon2::{Argon2, PasswordVerifier};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use log::error;
+use rand::RngCore;
+use serde_json::to_vec;
+use uuid::Uuid;
+
+// 비밀번호가 걸린 공유 링크는 이 헤더로 비밀번호를 전달
+const SHARE_PASSWORD_HEADER: &str = "X-Share-Password";
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, ShareError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        ShareError::BadClientData
+    })
+}
+
+// 추측할 수 없는 256비트 랜덤 토큰
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub async fn get_share_links(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, ShareError> {
+    let post_id = path_param(&req, "id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Editor, &pool).await?;
+
+    match ShareLink::get_share_links_by_post(post_id, &pool).await {
+        Ok(share_links) => {
+            let share_links = share_links
+                .into_iter()
+                .map(ShareLinkResponse::from)
+                .collect::<Vec<_>>();
+
+            let json_bytes = to_vec(&share_links).map_err(|err| {
+                error!("Failed to serialize share links to JSON: {:?}", err);
+                ShareError::InternalError
+            })?;
+
+            Ok(HttpResponse::Ok()
+                .content_type(ContentType::json())
+                .body(json_bytes))
+        }
+        Err(err) => {
+            error!("Error get share links data: {:?}", err);
+            Err(ShareError::InternalError)
+        }
+    }
+}
+
+pub async fn create_share_link(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: web::Json<CreateShareLink>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, ShareError> {
+    let post_id = path_param(&req, "id")?;
+    let share_data = _body.into_inner();
+
+    authorize(post_id, &auth_guard.user_id, PostRole::Editor, &pool).await?;
+
+    if let Some(expires_at) = share_data.expires_at {
+        if expires_at <= Utc::now().naive_utc() {
+            return Err(ShareError::ValidationError {
+                field: "expires_at".to_string(),
+            });
+        }
+    }
+
+    let password_hash = match share_data.password.filter(|p| !p.is_empty()) {
+        Some(password) => {
+            let hash = web::block(move || {
+                let salt = SaltString::generate(&mut OsRng);
+                Argon2::default()
+                    .hash_password(password.as_bytes(), &salt)
+                    .map(|hash| hash.to_string())
+            })
+            .await
+            .map_err(|err| {
+                error!("Error hashing share link password: {:?}", err);
+                ShareError::InternalError
+            })?
+            .map_err(|err| {
+                error!("Error hashing share link password: {:?}", err);
+                ShareError::InternalError
+            })?;
+
+            Some(hash)
+        }
+        None => None,
+    };
+
+    let share_link_data = ShareLinkData {
+        id: Uuid::new_v4().to_string(),
+        post_id: post_id.to_string(),
+        user_id: auth_guard.user_id,
+        token: generate_token(),
+        password_hash,
+        expires_at: share_data.expires_at,
+    };
+
+    match ShareLinkData::create_share_link(share_link_data, &pool).await {
+        Ok(share_link) => Ok(HttpResponse::Created().json(ShareLinkResponse::from(share_link))),
+        Err(err) => {
+            error!("Error created new share link data: {:?}", err);
+            Err(ShareError::InternalError)
+        }
+    }
+}
+
+pub async fn delete_share_link(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, ShareError> {
+    let post_id = path_param(&req, "id")?;
+    let share_id = path_param(&req, "share_id")?;
+    authorize(post_id, &auth_guard.user_id, PostRole::Editor, &pool).await?;
+
+    let share_link = ShareLink::get_share_link_by_id(post_id, share_id, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get share link data: {:?}", err);
+            ShareError::NotFound
+        })?;
+
+    match ShareLink::delete_share_link_by_id(&share_link.id, &pool).await {
+        Ok(0) => {
+            error!("Delete failed");
+            Err(ShareError::NotFound)
+        }
+        Err(err) => {
+            error!("Error deleted share link data: {:?}", err);
+            Err(ShareError::InternalError)
+        }
+        Ok(_) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("revoked share link")),
+    }
+}
+
+// 인증 없이 공유 토큰으로 노트 조회
+pub async fn get_shared_post(
+    req: HttpRequest,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, ShareError> {
+    let token = path_param(&req, "token")?;
+
+    let share_link = ShareLink::get_share_link_by_token(token, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get share link by token: {:?}", err);
+            ShareError::NotFound
+        })?;
+
+    if let Some(expires_at) = share_link.expires_at {
+        if expires_at <= Utc::now().naive_utc() {
+            return Err(ShareError::Expired);
+        }
+    }
+
+    if let Some(password_hash) = share_link.password_hash {
+        let password = req
+            .headers()
+            .get(SHARE_PASSWORD_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+            .ok_or(ShareError::PasswordRequired)?;
+
+        let verified = web::block(move || {
+            PasswordHash::new(&password_hash)
+                .map(|hash| {
+                    Argon2::default()
+                        .verify_password(password.as_bytes(), &hash)
+                        .is_ok()
+                })
+                .unwrap_or(false)
+        })
+        .await
+        .map_err(|err| {
+            error!("Error verifying share link password: {:?}", err);
+            ShareError::InternalError
+        })?;
+
+        if !verified {
+            return Err(ShareError::InvalidPassword);
+        }
+    }
+
+    match Post::get_posts_load_by_id(&share_link.post_id, &pool).await {
+        Ok(post) => Ok(HttpResponse::Ok().json(SharedPost {
+            title: post.title,
+            body: post.body,
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+        })),
+        Err(err) => {
+            error!("Error get shared post data: {:?}", err);
+            Err(ShareError::NotFound)
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::database::PgPool;
+use crate::schema::share_links::{self};
+use actix_web::web::Data;
+use diesel::prelude::*;
+use diesel::{result::Error, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::share_links)]
+pub struct ShareLink {
+    pub id: String,
+    pub post_id: String,
+    pub user_id: String,
+    pub token: String,
+    #[serde(skip_serializing)]
+    pub password_hash: Option<String>,
+    pub expires_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::share_links)]
+pub struct ShareLinkData {
+    pub id: String,
+    pub post_id: String,
+    pub user_id: String,
+    pub token: String,
+    pub password_hash: Option<String>,
+    pub expires_at: Option<chrono::NaiveDateTime>,
+}
+
+// 공유 링크 생성 요청
+#[derive(Deserialize, Debug)]
+pub struct CreateShareLink {
+    pub password: Option<String>,
+    pub expires_at: Option<chrono::NaiveDateTime>,
+}
+
+// 클라이언트 응답용: 비밀번호 설정 여부와 공유 경로 포함
+#[derive(Serialize, Debug)]
+pub struct ShareLinkResponse {
+    #[serde(flatten)]
+    pub share_link: ShareLink,
+    pub has_password: bool,
+    pub url: String,
+}
+
+impl From<ShareLink> for ShareLinkResponse {
+    fn from(share_link: ShareLink) -> Self {
+        ShareLinkResponse {
+            has_password: share_link.password_hash.is_some(),
+            url: format!("/api/shared/{}", share_link.token),
+            share_link,
+        }
+    }
+}
+
+// 계정 없이 보는 노트 (공유 링크 응답)
+#[derive(Serialize, Debug)]
+pub struct SharedPost {
+    pub title: String,
+    pub body: String,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+impl ShareLink {
+    pub async fn get_share_links_by_post(
+        post_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<ShareLink>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        share_links::table
+            .filter(share_links::post_id.eq(post_id))
+            .order(share_links::created_at.desc())
+            .load::<ShareLink>(conn)
+    }
+
+    pub async fn get_share_link_by_id(
+        post_id: &str,
+        share_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<ShareLink, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        share_links::table
+            .find(share_id)
+            .filter(share_links::post_id.eq(post_id))
+            .get_result::<ShareLink>(conn)
+    }
+
+    pub async fn get_share_link_by_token(
+        token: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<ShareLink, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        share_links::table
+            .filter(share_links::token.eq(token))
+            .get_result::<ShareLink>(conn)
+    }
+
+    pub async fn delete_share_link_by_id(
+        share_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::delete(share_links::table.find(share_id)).execute(conn)
+    }
+}
+
+impl ShareLinkData {
+    pub async fn create_share_link(
+        share_link_data: ShareLinkData,
+        pool: &Data<PgPool>,
+    ) -> Result<ShareLink, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::insert_into(share_links::table)
+            .values(share_link_data)
+            .get_result::<ShareLink>(conn)
+    }
+}
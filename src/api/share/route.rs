@@ -0,0 +1,16 @@
+use crate::api::share::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(handler::get_share_links))
+            .route(web::post().to(handler::create_share_link)),
+    );
+
+    cfg.service(web::resource("/{share_id}").route(web::delete().to(handler::delete_share_link)));
+}
+
+pub fn configure_shared(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/{token}").route(web::get().to(handler::get_shared_post)));
+}
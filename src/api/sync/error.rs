@@ -0,0 +1,70 @@
+use actix_web::{
+    error::ResponseError,
+    http::{header::ContentType, StatusCode},
+    HttpResponse,
+};
+use derive_more::{Display, Error};
+use serde::Serialize;
+
+use crate::api::permission::authorize::AuthorizeError;
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    code: u16,
+    message: String,
+}
+
+#[derive(Debug, Display, Error)]
+pub enum SyncError {
+    #[display(fmt = "internal error")]
+    InternalError,
+
+    #[display(fmt = "bad request")]
+    BadClientData,
+
+    #[display(fmt = "not found")]
+    NotFound,
+
+    #[display(fmt = "forbidden")]
+    Forbidden,
+
+    #[display(fmt = "sync batch exceeds {} changes", limit)]
+    PayloadTooLarge { limit: usize },
+
+    #[display(fmt = "Validation error on field: {}", field)]
+    ValidationError { field: String },
+}
+
+impl ResponseError for SyncError {
+    fn error_response(&self) -> HttpResponse {
+        let error_response = ErrorResponse {
+            code: self.status_code().as_u16(),
+            message: self.to_string(),
+        };
+
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .json(error_response)
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match *self {
+            SyncError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            SyncError::BadClientData => StatusCode::BAD_REQUEST,
+            SyncError::NotFound => StatusCode::NOT_FOUND,
+            SyncError::Forbidden => StatusCode::FORBIDDEN,
+            SyncError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            SyncError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<AuthorizeError> for SyncError {
+    fn from(err: AuthorizeError) -> Self {
+        match err {
+            AuthorizeError::NotFound => SyncError::NotFound,
+            AuthorizeError::Forbidden => SyncError::Forbidden,
+            AuthorizeError::InternalError => SyncError::InternalError,
+        }
+    }
+}
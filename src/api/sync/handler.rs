@@ -0,0 +1,362 @@
+use super::error::SyncError;
+use super::model::{
+    PostTombstone, SyncChange, SyncQuery, SyncRequest, SyncResponse, SyncResult, SyncStatus,
+    SyncToken, MAX_SYNC_BATCH, SYNC_PAGE_SIZE,
+};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::collab::room::Rooms;
+use crate::api::event::bus::EventBus;
+use crate::api::event::model::{PostEvent, PostEventKind};
+use crate::api::permission::authorize::{authorize, authorize_workspace, AuthorizeError};
+use crate::api::permission::model::PostRole;
+use crate::api::post::model::{Post, PostData};
+use crate::api::post::saved::after_post_saved;
+use crate::api::workspace::model::WorkspaceRole;
+use crate::api::workspace::selector::WorkspaceSelector;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::ContentType,
+    web::{self, Data},
+    HttpResponse, Result,
+};
+use diesel::result::Error as DieselError;
+use log::error;
+use serde_json::to_vec;
+use uuid::Uuid;
+
+fn internal_error(err: impl std::fmt::Debug) -> SyncError {
+    error!("Error applying sync change: {:?}", err);
+    SyncError::InternalError
+}
+
+fn json_response<T: serde::Serialize>(data: &T) -> Result<HttpResponse, SyncError> {
+    let json_bytes = to_vec(data).map_err(|err| {
+        error!("Failed to serialize sync data to JSON: {:?}", err);
+        SyncError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
+
+// GET /api/sync?since=: since 이후 바뀐 노트와 삭제된 노트
+// 아직 끝나지 않은 트랜잭션보다 뒤에 커밋된 변경은 다음 요청으로 미룸 (늦게 커밋된 변경을 건너뛰지 않도록)
+pub async fn get_changes(
+    auth_guard: AuthenticationGuard,
+    query: web::Query<SyncQuery>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, SyncError> {
+    let since = match query.since.as_deref() {
+        None | Some("") => SyncToken::default(),
+        Some(token) => token
+            .parse::<SyncToken>()
+            .map_err(|_| SyncError::ValidationError {
+                field: "since".to_string(),
+            })?,
+    };
+
+    let horizon = PostTombstone::get_sync_horizon(&pool)
+        .await
+        .map_err(|err| {
+            error!("Error get sync horizon: {:?}", err);
+            SyncError::InternalError
+        })?;
+    let mut changes =
+        Post::get_posts_since(&auth_guard.user_id, since, horizon, SYNC_PAGE_SIZE, &pool)
+            .await
+            .map_err(|err| {
+                error!("Error get changed posts data: {:?}", err);
+                SyncError::InternalError
+            })?;
+    let mut deleted = PostTombstone::get_tombstones_since(
+        &auth_guard.user_id,
+        since,
+        horizon,
+        SYNC_PAGE_SIZE,
+        &pool,
+    )
+    .await
+    .map_err(|err| {
+        error!("Error get post tombstones data: {:?}", err);
+        SyncError::InternalError
+    })?;
+
+    // 한쪽이라도 페이지가 꽉 찼으면 두 목록 모두 그 위치까지만 돌려주고 나머지는 다음 요청으로
+    let full = |len: usize| len as i64 == SYNC_PAGE_SIZE;
+    let limit = [
+        changes
+            .last()
+            .filter(|_| full(changes.len()))
+            .map(SyncToken::from),
+        deleted
+            .last()
+            .filter(|_| full(deleted.len()))
+            .map(SyncToken::from),
+    ]
+    .into_iter()
+    .flatten()
+    .min();
+
+    let sync_token = match limit {
+        Some(limit) => {
+            changes.retain(|post| SyncToken::from(post) <= limit);
+            deleted.retain(|tombstone| SyncToken::from(tombstone) <= limit);
+            limit
+        }
+        // horizon 전의 변경은 모두 돌려줌
+        None => since.max(SyncToken::horizon(horizon)),
+    };
+
+    json_response(&SyncResponse {
+        changes,
+        deleted,
+        sync_token: sync_token.to_string(),
+        has_more: limit.is_some(),
+    })
+}
+
+// POST /api/sync: 클라이언트 변경 일괄 반영, 항목마다 결과 반환
+pub async fn push_changes(
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
+    _body: web::Json<SyncRequest>,
+    pool: Data<PgPool>,
+    rooms: Data<Rooms>,
+    events: Data<EventBus>,
+) -> Result<HttpResponse, SyncError> {
+    let request = _body.into_inner();
+    if request.changes.len() > MAX_SYNC_BATCH {
+        return Err(SyncError::PayloadTooLarge {
+            limit: MAX_SYNC_BATCH,
+        });
+    }
+
+    // 새 노트를 워크스페이스에 만들려면 editor 이상
+    if let Some(workspace_id) = &workspace.0 {
+        authorize_workspace(
+            workspace_id,
+            &auth_guard.user_id,
+            WorkspaceRole::Editor,
+            &pool,
+        )
+        .await?;
+    }
+
+    let mut results = Vec::with_capacity(request.changes.len());
+    for change in request.changes {
+        let post_id = change.id.clone();
+        let result = apply_change(
+            &auth_guard.user_id,
+            &workspace,
+            change,
+            &pool,
+            &rooms,
+            &events,
+        )
+        .await
+        .unwrap_or_else(|_| SyncResult::new(&post_id, SyncStatus::Error));
+        results.push(result);
+    }
+
+    json_response(&results)
+}
+
+async fn apply_change(
+    user_id: &str,
+    workspace: &WorkspaceSelector,
+    change: SyncChange,
+    pool: &Data<PgPool>,
+    rooms: &Data<Rooms>,
+    events: &Data<EventBus>,
+) -> Result<SyncResult, SyncError> {
+    let post_id = change.id.clone();
+    if Uuid::parse_str(&post_id).is_err() {
+        return Ok(SyncResult::new(&post_id, SyncStatus::Invalid));
+    }
+
+    let post = match Post::get_posts_load_by_id(&post_id, pool).await {
+        Ok(post) => Some(post),
+        Err(DieselError::NotFound) => None,
+        Err(err) => return Err(internal_error(err)),
+    };
+
+    let post = match post {
+        Some(post) => post,
+        None => {
+            return if change.deleted {
+                // 이미 삭제된 노트
+                Ok(SyncResult::new(&post_id, SyncStatus::Applied))
+            } else if change.base_version.is_some() {
+                // 오프라인에서 수정하는 사이 서버에서 삭제됨
+                Ok(SyncResult::new(&post_id, SyncStatus::Conflict))
+            } else {
+                create_post(user_id, workspace, change, pool, events).await
+            };
+        }
+    };
+
+    let required = if change.deleted {
+        PostRole::Owner
+    } else {
+        PostRole::Editor
+    };
+    match authorize(&post_id, user_id, required, pool).await {
+        Ok(_) => {}
+        Err(AuthorizeError::Forbidden) => {
+            return Ok(SyncResult::new(&post_id, SyncStatus::Forbidden))
+        }
+        // 권한이 없는 노트는 id가 겹쳐도 존재를 드러내지 않음
+        Err(AuthorizeError::NotFound) => {
+            let status = if change.base_version.is_some() {
+                SyncStatus::NotFound
+            } else {
+                SyncStatus::Invalid
+            };
+            return Ok(SyncResult::new(&post_id, status));
+        }
+        Err(err) => return Err(err.into()),
+    }
+
+    if workspace.0.is_some() && post.workspace_id != workspace.0 {
+        return Ok(SyncResult::new(&post_id, SyncStatus::NotFound));
+    }
+
+    let conflict = |server: Post| SyncResult {
+        id: server.id.clone(),
+        status: SyncStatus::Conflict,
+        sync_version: Some(server.sync_version),
+        server: Some(server),
+    };
+
+    let base_version = match change.base_version {
+        Some(version) if version == post.sync_version => version,
+        _ => return Ok(conflict(post)),
+    };
+
+    if change.deleted {
+        let recipients = PostEvent::get_recipients(&post_id, pool)
+            .await
+            .map_err(internal_error)?;
+        return match PostTombstone::delete_post(&post_id, Some(base_version), &recipients, pool)
+            .await
+        {
+            Ok(0) => refetch_conflict(&post_id, pool).await,
+            Ok(_) => {
+                events
+                    .publish_to(&post, PostEventKind::Deleted, recipients, pool)
+                    .await;
+                Ok(SyncResult::new(&post_id, SyncStatus::Applied))
+            }
+            Err(err) => Err(internal_error(err)),
+        };
+    }
+
+    let (title, body) = match (change.title, change.body) {
+        (Some(title), Some(body)) => (title, body),
+        _ => return Ok(SyncResult::new(&post_id, SyncStatus::Invalid)),
+    };
+    let post_data = PostData {
+        id: Some(post_id.clone()),
+        title,
+        body: body.clone(),
+        published: change.published,
+        updated_at: None,
+        workspace_id: None,
+        author_id: None,
+        slug: None,
+        publish_at: None,
+        unpublish_at: None,
+    };
+
+    match PostData::update_posts_if_version(post_data, base_version, pool).await {
+        Ok(Some(version)) => {
+            rooms.reset_body(&post_id, &body, pool).await;
+            after_post_saved(
+                &post_id,
+                Some(user_id),
+                PostEventKind::Updated,
+                !post.published && change.published == Some(true),
+                events,
+                pool,
+            )
+            .await;
+            Ok(SyncResult {
+                sync_version: Some(version),
+                ..SyncResult::new(&post_id, SyncStatus::Applied)
+            })
+        }
+        // 확인한 뒤 다른 곳에서 먼저 수정됨
+        Ok(None) => refetch_conflict(&post_id, pool).await,
+        Err(err) => Err(internal_error(err)),
+    }
+}
+
+async fn create_post(
+    user_id: &str,
+    workspace: &WorkspaceSelector,
+    change: SyncChange,
+    pool: &Data<PgPool>,
+    events: &Data<EventBus>,
+) -> Result<SyncResult, SyncError> {
+    let post_id = change.id;
+    let published = change.published == Some(true);
+    let (title, body) = match (change.title, change.body) {
+        (Some(title), Some(body)) => (title, body),
+        _ => return Ok(SyncResult::new(&post_id, SyncStatus::Invalid)),
+    };
+
+    let post_data = PostData {
+        id: Some(post_id.clone()),
+        title,
+        body,
+        published: change.published,
+        updated_at: None,
+        workspace_id: workspace.0.clone(),
+        author_id: None,
+        slug: None,
+        publish_at: None,
+        unpublish_at: None,
+    };
+
+    // 삭제된 노트를 같은 id로 다시 만들 수 없음
+    if PostTombstone::is_deleted(&post_id, pool)
+        .await
+        .map_err(internal_error)?
+    {
+        return Ok(SyncResult::new(&post_id, SyncStatus::Conflict));
+    }
+    PostData::insert_posts(post_data, user_id, pool)
+        .await
+        .map_err(internal_error)?;
+    after_post_saved(
+        &post_id,
+        Some(user_id),
+        PostEventKind::Created,
+        published,
+        events,
+        pool,
+    )
+    .await;
+
+    let post = Post::get_posts_load_by_id(&post_id, pool)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(SyncResult {
+        sync_version: Some(post.sync_version),
+        ..SyncResult::new(&post_id, SyncStatus::Applied)
+    })
+}
+
+async fn refetch_conflict(post_id: &str, pool: &Data<PgPool>) -> Result<SyncResult, SyncError> {
+    match Post::get_posts_load_by_id(post_id, pool).await {
+        Ok(server) => Ok(SyncResult {
+            sync_version: Some(server.sync_version),
+            server: Some(server),
+            ..SyncResult::new(post_id, SyncStatus::Conflict)
+        }),
+        Err(DieselError::NotFound) => Ok(SyncResult::new(post_id, SyncStatus::Conflict)),
+        Err(err) => Err(internal_error(err)),
+    }
+}
@@ -0,0 +1,321 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::api::post::model::Post;
+use crate::database::PgPool;
+use crate::schema::post_tombstones::dsl::*;
+use crate::schema::{post_permissions, posts, workspace_members};
+use actix_web::web::Data;
+use chrono::Utc;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use diesel::upsert::excluded;
+use diesel::{result::Error, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+// GET /api/sync 한 번에 돌려주는 노트/삭제 기록 수
+pub const SYNC_PAGE_SIZE: i64 = 500;
+
+// POST /api/sync 한 번에 받는 변경 수
+pub const MAX_SYNC_BATCH: usize = 500;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::post_tombstones)]
+pub struct PostTombstone {
+    pub post_id: String,
+    #[serde(skip_serializing)]
+    pub user_id: String,
+    pub sync_version: i64,
+    #[serde(skip)]
+    pub sync_xid: i64,
+    pub deleted_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::post_tombstones)]
+pub struct PostTombstoneData {
+    pub post_id: String,
+    pub user_id: String,
+    pub deleted_at: Option<chrono::NaiveDateTime>,
+}
+
+// 동기화 위치: 이 (트랜잭션 id, 버전)까지의 변경은 모두 받음
+// 클라이언트에는 "xid.version" 문자열로 전달
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SyncToken {
+    pub xid: i64,
+    pub version: i64,
+}
+
+impl SyncToken {
+    // 끝나지 않은 트랜잭션이 없는 지점까지 모두 받았을 때의 위치 (버전은 1부터 시작)
+    pub fn horizon(xid: i64) -> Self {
+        SyncToken { xid, version: 0 }
+    }
+}
+
+impl fmt::Display for SyncToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.xid, self.version)
+    }
+}
+
+impl FromStr for SyncToken {
+    type Err = ();
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let (xid, version) = token.split_once('.').ok_or(())?;
+        Ok(SyncToken {
+            xid: xid.parse().map_err(|_| ())?,
+            version: version.parse().map_err(|_| ())?,
+        })
+    }
+}
+
+impl From<&Post> for SyncToken {
+    fn from(post: &Post) -> Self {
+        SyncToken {
+            xid: post.sync_xid,
+            version: post.sync_version,
+        }
+    }
+}
+
+impl From<&PostTombstone> for SyncToken {
+    fn from(tombstone: &PostTombstone) -> Self {
+        SyncToken {
+            xid: tombstone.sync_xid,
+            version: tombstone.sync_version,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SyncQuery {
+    pub since: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SyncResponse {
+    pub changes: Vec<Post>,
+    pub deleted: Vec<PostTombstone>,
+    // 다음 요청의 since로 그대로 전달
+    pub sync_token: String,
+    // true면 sync_token으로 바로 다시 요청
+    pub has_more: bool,
+}
+
+// 클라이언트의 변경 하나 (오프라인에서 만든 노트는 클라이언트가 UUID를 정함)
+#[derive(Deserialize, Debug)]
+pub struct SyncChange {
+    pub id: String,
+    // 클라이언트가 마지막으로 받은 서버 버전, 새 노트면 없음
+    pub base_version: Option<i64>,
+    #[serde(default)]
+    pub deleted: bool,
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub published: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SyncRequest {
+    pub changes: Vec<SyncChange>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStatus {
+    Applied,
+    Conflict,
+    Forbidden,
+    NotFound,
+    Invalid,
+    Error,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SyncResult {
+    pub id: String,
+    pub status: SyncStatus,
+    pub sync_version: Option<i64>,
+    // 충돌 시 서버의 현재 노트, 서버에서 삭제된 경우 없음
+    pub server: Option<Post>,
+}
+
+impl SyncResult {
+    pub fn new(result_post_id: &str, status: SyncStatus) -> Self {
+        SyncResult {
+            id: result_post_id.to_string(),
+            status,
+            sync_version: None,
+            server: None,
+        }
+    }
+}
+
+// 삭제 기록 추가, 이미 있으면 다시 삭제된 것으로 버전을 올림
+fn insert_tombstones(conn: &mut PgConnection, rows: &[PostTombstoneData]) -> Result<usize, Error> {
+    if rows.is_empty() {
+        return Ok(0);
+    }
+
+    diesel::insert_into(post_tombstones)
+        .values(rows)
+        .on_conflict((post_id, user_id))
+        .do_update()
+        .set(deleted_at.eq(excluded(deleted_at)))
+        .execute(conn)
+}
+
+impl PostTombstone {
+    // 이 값보다 작은 트랜잭션 id는 모두 끝났으므로 그 변경은 더 늦게 나타나지 않음
+    pub async fn get_sync_horizon(pool: &Data<PgPool>) -> Result<i64, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::select(sql::<BigInt>(
+            "pg_snapshot_xmin(pg_current_snapshot())::text::bigint",
+        ))
+        .get_result::<i64>(conn)
+    }
+
+    pub async fn get_tombstones_since(
+        tombstone_user_id: &str,
+        since: SyncToken,
+        horizon: i64,
+        limit: i64,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<PostTombstone>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        post_tombstones
+            .filter(user_id.eq(tombstone_user_id))
+            .filter(
+                sync_xid
+                    .gt(since.xid)
+                    .or(sync_xid.eq(since.xid).and(sync_version.gt(since.version))),
+            )
+            .filter(sync_xid.lt(horizon))
+            .order((sync_xid.asc(), sync_version.asc()))
+            .limit(limit)
+            .load::<PostTombstone>(conn)
+    }
+
+    // 노트 삭제와 동시에 접근 가능했던 사용자들에게 삭제 기록 남김
+    // base_version이 있으면 그 버전일 때만 삭제
+    pub async fn delete_post(
+        tombstone_post_id: &str,
+        base_version: Option<i64>,
+        recipients: &[String],
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            let deleted = match base_version {
+                Some(version) => diesel::delete(
+                    posts::table
+                        .find(tombstone_post_id)
+                        .filter(posts::sync_version.eq(version)),
+                )
+                .execute(conn)?,
+                None => diesel::delete(posts::table.find(tombstone_post_id)).execute(conn)?,
+            };
+            if deleted == 0 {
+                return Ok(0);
+            }
+
+            let rows: Vec<PostTombstoneData> = recipients
+                .iter()
+                .map(|recipient| PostTombstoneData {
+                    post_id: tombstone_post_id.to_string(),
+                    user_id: recipient.clone(),
+                    deleted_at: Some(Utc::now().naive_utc()),
+                })
+                .collect();
+            insert_tombstones(conn, &rows)?;
+
+            Ok(deleted)
+        })
+    }
+
+    // 협업자 제거, 워크스페이스 탈퇴로 노트를 더 볼 수 없게 된 사용자에게 삭제 기록 남김
+    // 다른 경로(직접 권한, 다른 멤버십)로 여전히 볼 수 있는 노트는 제외
+    pub async fn record_lost_access(
+        tombstone_user_id: &str,
+        post_ids: &[String],
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        if post_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            let permitted = post_permissions::table
+                .filter(post_permissions::user_id.eq(tombstone_user_id))
+                .select(post_permissions::post_id);
+            let member_of = workspace_members::table
+                .filter(workspace_members::user_id.eq(tombstone_user_id))
+                .select(workspace_members::workspace_id.nullable());
+            let accessible = posts::table
+                .filter(posts::id.eq_any(post_ids))
+                .filter(
+                    posts::id
+                        .eq_any(permitted)
+                        .or(posts::workspace_id.eq_any(member_of)),
+                )
+                .select(posts::id)
+                .load::<String>(conn)?;
+
+            let rows: Vec<PostTombstoneData> = post_ids
+                .iter()
+                .filter(|lost| !accessible.contains(lost))
+                .map(|lost| PostTombstoneData {
+                    post_id: lost.clone(),
+                    user_id: tombstone_user_id.to_string(),
+                    deleted_at: Some(Utc::now().naive_utc()),
+                })
+                .collect();
+            insert_tombstones(conn, &rows)
+        })
+    }
+
+    // 협업자 초대, 워크스페이스 가입으로 노트를 (다시) 볼 수 있게 된 사용자
+    // 예전 삭제 기록을 지우고, 이미 받은 sync_token 이전에 바뀐 노트도 다음 동기화에 나오도록 동기화 위치를 올림
+    pub async fn record_gained_access(
+        tombstone_user_id: &str,
+        post_ids: &[String],
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        if post_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            diesel::delete(
+                post_tombstones
+                    .filter(user_id.eq(tombstone_user_id))
+                    .filter(post_id.eq_any(post_ids)),
+            )
+            .execute(conn)?;
+
+            // 값은 그대로 두고 트리거로 sync_xid, sync_version만 새로 받음
+            diesel::update(posts::table.filter(posts::id.eq_any(post_ids)))
+                .set(posts::sync_version.eq(posts::sync_version))
+                .execute(conn)
+        })
+    }
+
+    // 삭제된 노트의 id는 다시 쓰지 않음 (삭제 기록을 아직 받지 못한 사용자가 있을 수 있음)
+    pub async fn is_deleted(tombstone_post_id: &str, pool: &Data<PgPool>) -> Result<bool, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::select(diesel::dsl::exists(
+            post_tombstones.filter(post_id.eq(tombstone_post_id)),
+        ))
+        .get_result::<bool>(conn)
+    }
+}
@@ -0,0 +1,10 @@
+use crate::api::sync::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(handler::get_changes))
+            .route(web::post().to(handler::push_changes)),
+    );
+}
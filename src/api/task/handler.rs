@@ -0,0 +1,167 @@
+use super::error::TaskError;
+use super::model::{Task, TaskFilter, TaskQuery, TaskUpdateRequest};
+use super::parser::set_checkbox;
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::collab::room::Rooms;
+use crate::api::event::bus::EventBus;
+use crate::api::event::model::PostEventKind;
+use crate::api::permission::authorize::{authorize, authorize_workspace};
+use crate::api::permission::model::PostRole;
+use crate::api::post::model::{Post, PostData};
+use crate::api::post::saved::after_post_saved;
+use crate::api::workspace::model::WorkspaceRole;
+use crate::api::workspace::selector::WorkspaceSelector;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::ContentType,
+    web::{Data, Json, Query},
+    HttpRequest, HttpResponse, Result,
+};
+use chrono::NaiveDate;
+use diesel::result::Error as DieselError;
+use log::error;
+use serde_json::to_vec;
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, TaskError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        TaskError::BadClientData
+    })
+}
+
+fn json_response<T: serde::Serialize>(data: &T) -> Result<HttpResponse, TaskError> {
+    let json_bytes = to_vec(data).map_err(|err| {
+        error!("Failed to serialize tasks to JSON: {:?}", err);
+        TaskError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
+
+fn parse_date(value: Option<&str>, field: &str) -> Result<Option<NaiveDate>, TaskError> {
+    value
+        .map(|value| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| TaskError::ValidationError {
+                field: field.to_string(),
+            })
+        })
+        .transpose()
+}
+
+async fn load_task(task_id: &str, pool: &Data<PgPool>) -> Result<Task, TaskError> {
+    Task::get_task_by_id(task_id, pool)
+        .await
+        .map_err(|err| match err {
+            DieselError::NotFound => TaskError::NotFound,
+            err => {
+                error!("Error get task by id data: {:?}", err);
+                TaskError::InternalError
+            }
+        })
+}
+
+// GET /api/tasks?done=&post_id=&due_from=&due_to=: 볼 수 있는 노트의 할 일
+pub async fn get_tasks(
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
+    query: Query<TaskQuery>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, TaskError> {
+    if let Some(workspace_id) = &workspace.0 {
+        authorize_workspace(
+            workspace_id,
+            &auth_guard.user_id,
+            WorkspaceRole::Viewer,
+            &pool,
+        )
+        .await?;
+    }
+
+    let query = query.into_inner();
+    let filter = TaskFilter {
+        done: query.done,
+        due_from: parse_date(query.due_from.as_deref(), "due_from")?,
+        due_to: parse_date(query.due_to.as_deref(), "due_to")?,
+        post_id: query.post_id,
+    };
+
+    let task_list = Task::get_tasks(&auth_guard.user_id, workspace.0.as_deref(), filter, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get tasks data: {:?}", err);
+            TaskError::InternalError
+        })?;
+
+    json_response(&task_list)
+}
+
+// PATCH /api/tasks/{id}: 노트 본문의 체크 표시를 바꿈
+pub async fn update_task(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: Json<TaskUpdateRequest>,
+    pool: Data<PgPool>,
+    rooms: Data<Rooms>,
+    events: Data<EventBus>,
+) -> Result<HttpResponse, TaskError> {
+    let task = load_task(path_param(&req, "id")?, &pool).await?;
+    authorize(&task.post_id, &auth_guard.user_id, PostRole::Editor, &pool).await?;
+
+    let request = _body.into_inner();
+    if task.done == request.done {
+        return json_response(&task);
+    }
+
+    let post = Post::get_posts_load_by_id(&task.post_id, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get posts by id data: {:?}", err);
+            TaskError::InternalError
+        })?;
+
+    // 저장된 줄 번호의 항목이 달라졌으면 목록을 다시 읽고 클라이언트가 다시 시도
+    let post_body = match set_checkbox(&post.body, task.line, &task.text, request.done) {
+        Some(post_body) => post_body,
+        None => {
+            Task::refresh_tasks(&task.post_id, &pool).await;
+            return Err(TaskError::Conflict);
+        }
+    };
+
+    let post_data = PostData {
+        id: Some(post.id.clone()),
+        title: post.title,
+        body: post_body.clone(),
+        published: None,
+        updated_at: None,
+        workspace_id: None,
+        author_id: None,
+        slug: None,
+        publish_at: None,
+        unpublish_at: None,
+    };
+    match PostData::update_posts_if_version(post_data, post.sync_version, &pool).await {
+        Ok(Some(_)) => {}
+        // 읽은 뒤 다른 곳에서 먼저 수정됨
+        Ok(None) => return Err(TaskError::Conflict),
+        Err(err) => {
+            error!("Error updated post data: {:?}", err);
+            return Err(TaskError::InternalError);
+        }
+    }
+
+    rooms.reset_body(&post.id, &post_body, &pool).await;
+    after_post_saved(
+        &post.id,
+        Some(&auth_guard.user_id),
+        PostEventKind::Updated,
+        false,
+        &events,
+        &pool,
+    )
+    .await;
+
+    json_response(&load_task(&task.id, &pool).await?)
+}
@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+
+use super::parser::parse_tasks;
+use crate::database::PgPool;
+use crate::schema::tasks::dsl::*;
+use crate::schema::{post_permissions, posts, workspace_members};
+use actix_web::web::Data;
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::{result::Error, Insertable, Queryable};
+use log::error;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::tasks)]
+pub struct Task {
+    pub id: String,
+    pub post_id: String,
+    pub line: i32,
+    pub text: String,
+    pub done: bool,
+    pub due_date: Option<NaiveDate>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::tasks)]
+pub struct TaskData {
+    pub id: String,
+    pub post_id: String,
+    pub line: i32,
+    pub text: String,
+    pub done: bool,
+    pub due_date: Option<NaiveDate>,
+}
+
+// 목록에 함께 보여줄 노트 제목
+#[derive(Serialize, Debug, Queryable)]
+pub struct TaskItem {
+    pub id: String,
+    pub post_id: String,
+    pub post_title: String,
+    pub line: i32,
+    pub text: String,
+    pub done: bool,
+    pub due_date: Option<NaiveDate>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TaskQuery {
+    pub done: Option<bool>,
+    pub post_id: Option<String>,
+    // YYYY-MM-DD, 둘 다 해당 날짜 포함
+    pub due_from: Option<String>,
+    pub due_to: Option<String>,
+}
+
+// 검사를 마친 목록 조건
+#[derive(Debug, Default)]
+pub struct TaskFilter {
+    pub done: Option<bool>,
+    pub post_id: Option<String>,
+    pub due_from: Option<NaiveDate>,
+    pub due_to: Option<NaiveDate>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TaskUpdateRequest {
+    pub done: bool,
+}
+
+impl Task {
+    // 노트 본문의 할 일을 다시 읽음, 내용이 같은 항목은 가장 가까운 줄의 기존 항목을 이어 씀
+    pub async fn sync_tasks(task_post_id: &str, pool: &Data<PgPool>) -> Result<(), Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            let body = match posts::table
+                .find(task_post_id)
+                .select(posts::body)
+                .get_result::<String>(conn)
+                .optional()?
+            {
+                Some(body) => body,
+                None => return Ok(()),
+            };
+
+            let existing = tasks
+                .filter(post_id.eq(task_post_id))
+                .order(line.asc())
+                .load::<Task>(conn)?;
+            let mut kept: HashSet<&str> = HashSet::new();
+            let now = Utc::now().naive_utc();
+
+            for parsed in parse_tasks(&body) {
+                let matched = existing
+                    .iter()
+                    .filter(|task| task.text == parsed.text && !kept.contains(task.id.as_str()))
+                    .min_by_key(|task| (task.line - parsed.line).abs());
+
+                match matched {
+                    Some(task) => {
+                        kept.insert(&task.id);
+                        if task.line != parsed.line
+                            || task.done != parsed.done
+                            || task.due_date != parsed.due_date
+                        {
+                            diesel::update(tasks.find(&task.id))
+                                .set((
+                                    line.eq(parsed.line),
+                                    done.eq(parsed.done),
+                                    due_date.eq(parsed.due_date),
+                                    updated_at.eq(now),
+                                ))
+                                .execute(conn)?;
+                        }
+                    }
+                    None => {
+                        diesel::insert_into(tasks)
+                            .values(TaskData {
+                                id: Uuid::new_v4().to_string(),
+                                post_id: task_post_id.to_string(),
+                                line: parsed.line,
+                                text: parsed.text,
+                                done: parsed.done,
+                                due_date: parsed.due_date,
+                            })
+                            .execute(conn)?;
+                    }
+                }
+            }
+
+            let removed: Vec<&str> = existing
+                .iter()
+                .map(|task| task.id.as_str())
+                .filter(|task_id| !kept.contains(task_id))
+                .collect();
+            if !removed.is_empty() {
+                diesel::delete(tasks.filter(id.eq_any(removed))).execute(conn)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    // 본문이 바뀐 뒤 호출, 실패해도 노트 저장에는 영향 없음
+    pub async fn refresh_tasks(task_post_id: &str, pool: &Data<PgPool>) {
+        if let Err(err) = Task::sync_tasks(task_post_id, pool).await {
+            error!("Error updating post tasks: {:?}", err);
+        }
+    }
+
+    pub async fn get_task_by_id(task_id: &str, pool: &Data<PgPool>) -> Result<Task, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        tasks.find(task_id).get_result::<Task>(conn)
+    }
+
+    // 볼 수 있는 노트의 할 일 (워크스페이스를 주면 그 워크스페이스만), 마감일이 가까운 순
+    pub async fn get_tasks(
+        user_id: &str,
+        workspace: Option<&str>,
+        filter: TaskFilter,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<TaskItem>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        let mut query = tasks.inner_join(posts::table).into_boxed();
+        query = match workspace {
+            Some(workspace) => query.filter(posts::workspace_id.eq(workspace.to_string())),
+            None => {
+                let permitted = post_permissions::table
+                    .filter(post_permissions::user_id.eq(user_id.to_string()))
+                    .select(post_permissions::post_id);
+                let member_of = workspace_members::table
+                    .filter(workspace_members::user_id.eq(user_id.to_string()))
+                    .select(workspace_members::workspace_id.nullable());
+                query.filter(
+                    posts::id
+                        .eq_any(permitted)
+                        .or(posts::workspace_id.eq_any(member_of)),
+                )
+            }
+        };
+        if let Some(task_done) = filter.done {
+            query = query.filter(done.eq(task_done));
+        }
+        if let Some(task_post_id) = filter.post_id {
+            query = query.filter(post_id.eq(task_post_id));
+        }
+        if let Some(from) = filter.due_from {
+            query = query.filter(due_date.ge(from));
+        }
+        if let Some(to) = filter.due_to {
+            query = query.filter(due_date.le(to));
+        }
+
+        query
+            .order((
+                due_date.asc().nulls_last(),
+                posts::updated_at.desc().nulls_last(),
+                line.asc(),
+            ))
+            .select((
+                id,
+                post_id,
+                posts::title,
+                line,
+                text,
+                done,
+                due_date,
+                updated_at,
+            ))
+            .load::<TaskItem>(conn)
+    }
+}
@@ -0,0 +1,179 @@
+use chrono::NaiveDate;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParsedTask {
+    // 1부터 세는 줄 번호
+    pub line: i32,
+    pub text: String,
+    pub done: bool,
+    pub due_date: Option<NaiveDate>,
+}
+
+// "- [ ] 내용", "* [x] 내용", "+ [X] 내용" 형태의 줄이면 (체크 표시 위치, 완료 여부, 내용)
+fn checkbox(line: &str) -> Option<(usize, bool, &str)> {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+
+    let rest = trimmed.strip_prefix(['-', '*', '+'])?.strip_prefix(" [")?;
+    let done = match rest.as_bytes().first()? {
+        b' ' => false,
+        b'x' | b'X' => true,
+        _ => return None,
+    };
+    let rest = rest[1..].strip_prefix(']')?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    Some((indent + 3, done, rest.trim()))
+}
+
+// 내용 중 due:YYYY-MM-DD
+fn due_date(text: &str) -> Option<NaiveDate> {
+    text.split_whitespace()
+        .filter_map(|word| word.strip_prefix("due:"))
+        .find(|date| date.len() == 10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+}
+
+fn is_fence(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+// 본문의 할 일 항목 (``` 코드 블록 안은 제외)
+pub fn parse_tasks(body: &str) -> Vec<ParsedTask> {
+    let mut tasks = Vec::new();
+    let mut in_fence = false;
+
+    for (index, line) in body.split('\n').enumerate() {
+        if is_fence(line) {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        if let Some((_, done, text)) = checkbox(line) {
+            if text.is_empty() {
+                continue;
+            }
+            tasks.push(ParsedTask {
+                line: index as i32 + 1,
+                text: text.to_string(),
+                done,
+                due_date: due_date(text),
+            });
+        }
+    }
+
+    tasks
+}
+
+// line 줄의 할 일이 text이면 체크 표시를 바꾼 본문, 아니면 None
+pub fn set_checkbox(body: &str, line: i32, text: &str, done: bool) -> Option<String> {
+    let index = usize::try_from(line).ok()?.checked_sub(1)?;
+    let mut lines: Vec<&str> = body.split('\n').collect();
+    let current = *lines.get(index)?;
+
+    let in_fence = lines[..index].iter().filter(|line| is_fence(line)).count() % 2 == 1;
+    let (mark, _, current_text) = checkbox(current)?;
+    if in_fence || current_text != text {
+        return None;
+    }
+
+    let updated = format!(
+        "{}{}{}",
+        &current[..mark],
+        if done { 'x' } else { ' ' },
+        &current[mark + 1..]
+    );
+    lines[index] = &updated;
+
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_checkboxes_with_due_dates() {
+        let body = "# 할 일\n- [ ] 장보기 due:2024-06-14\n  * [x] 우유\n+ [X] done\n- [] 아님\n-[ ] 아님\n- [ ]\n- [ ]붙음";
+        let tasks = parse_tasks(body);
+
+        assert_eq!(
+            tasks,
+            vec![
+                ParsedTask {
+                    line: 2,
+                    text: "장보기 due:2024-06-14".to_string(),
+                    done: false,
+                    due_date: NaiveDate::from_ymd_opt(2024, 6, 14),
+                },
+                ParsedTask {
+                    line: 3,
+                    text: "우유".to_string(),
+                    done: true,
+                    due_date: None,
+                },
+                ParsedTask {
+                    line: 4,
+                    text: "done".to_string(),
+                    done: true,
+                    due_date: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_tasks_inside_code_fences() {
+        let body = "```md\n- [ ] 예시\n```\n- [ ] 진짜";
+        let tasks = parse_tasks(body);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].line, 4);
+        assert_eq!(tasks[0].text, "진짜");
+    }
+
+    #[test]
+    fn handles_crlf_line_endings() {
+        let body = "- [ ] 첫째\r\n- [x] 둘째\r\n";
+        let tasks = parse_tasks(body);
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].text, "첫째");
+        assert!(tasks[1].done);
+
+        assert_eq!(
+            set_checkbox(body, 1, "첫째", true).as_deref(),
+            Some("- [x] 첫째\r\n- [x] 둘째\r\n")
+        );
+    }
+
+    #[test]
+    fn toggles_only_the_matching_line() {
+        let body = "intro\n  - [ ] 한글 항목 🎉\n- [x] other";
+
+        assert_eq!(
+            set_checkbox(body, 2, "한글 항목 🎉", true).as_deref(),
+            Some("intro\n  - [x] 한글 항목 🎉\n- [x] other")
+        );
+        assert_eq!(
+            set_checkbox(body, 3, "other", false).as_deref(),
+            Some("intro\n  - [ ] 한글 항목 🎉\n- [ ] other")
+        );
+        // 줄이 바뀌었거나 할 일이 아닌 줄
+        assert_eq!(set_checkbox(body, 2, "other", true), None);
+        assert_eq!(set_checkbox(body, 1, "intro", true), None);
+        assert_eq!(set_checkbox(body, 0, "intro", true), None);
+        assert_eq!(set_checkbox(body, 9, "intro", true), None);
+    }
+
+    #[test]
+    fn does_not_toggle_inside_code_fences() {
+        let body = "```\n- [ ] 예시\n```";
+        assert_eq!(set_checkbox(body, 2, "예시", true), None);
+    }
+}
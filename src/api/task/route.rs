@@ -0,0 +1,8 @@
+use crate::api::task::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(handler::get_tasks)));
+
+    cfg.service(web::resource("/{id}").route(web::patch().to(handler::update_task)));
+}
@@ -0,0 +1,310 @@
+use super::error::TemplateError;
+use super::model::{InstantiateRequest, Template, TemplateData, TemplateRequest};
+use super::variables::Context;
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::event::bus::EventBus;
+use crate::api::event::model::PostEventKind;
+use crate::api::permission::authorize::authorize_workspace;
+use crate::api::post::model::{Post, PostData};
+use crate::api::post::saved::after_post_saved;
+use crate::api::user::model::User;
+use crate::api::workspace::model::WorkspaceRole;
+use crate::api::workspace::selector::WorkspaceSelector;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::ContentType,
+    web::{self, Data},
+    HttpRequest, HttpResponse, Result,
+};
+use diesel::result::Error as DieselError;
+use log::error;
+use serde_json::to_vec;
+use uuid::Uuid;
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, TemplateError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        TemplateError::BadClientData
+    })
+}
+
+fn validate_request(request: &TemplateRequest) -> Result<(), TemplateError> {
+    let name = request.name.trim();
+    if name.is_empty() || name.chars().count() > 255 {
+        return Err(TemplateError::ValidationError {
+            field: "name".to_string(),
+        });
+    }
+    if request.title.chars().count() > 255 {
+        return Err(TemplateError::ValidationError {
+            field: "title".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn json_response<T: serde::Serialize>(data: &T) -> Result<HttpResponse, TemplateError> {
+    let json_bytes = to_vec(data).map_err(|err| {
+        error!("Failed to serialize template data to JSON: {:?}", err);
+        TemplateError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
+
+// 개인 템플릿은 만든 사용자만, 워크스페이스 템플릿은 멤버가 사용 (수정은 editor 이상)
+pub async fn load_template(
+    template_id: &str,
+    user_id: &str,
+    required: WorkspaceRole,
+    pool: &Data<PgPool>,
+) -> Result<Template, TemplateError> {
+    let template = match Template::get_template_by_id(template_id, pool).await {
+        Ok(template) => template,
+        Err(DieselError::NotFound) => return Err(TemplateError::NotFound),
+        Err(err) => {
+            error!("Error get template by id data: {:?}", err);
+            return Err(TemplateError::InternalError);
+        }
+    };
+
+    match &template.workspace_id {
+        Some(workspace_id) => {
+            authorize_workspace(workspace_id, user_id, required, pool).await?;
+        }
+        None if template.user_id != user_id => return Err(TemplateError::NotFound),
+        None => {}
+    }
+
+    Ok(template)
+}
+
+pub async fn get_templates(
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, TemplateError> {
+    if let Some(workspace_id) = &workspace.0 {
+        authorize_workspace(
+            workspace_id,
+            &auth_guard.user_id,
+            WorkspaceRole::Viewer,
+            &pool,
+        )
+        .await?;
+    }
+
+    match Template::get_templates(&auth_guard.user_id, workspace.0.as_deref(), &pool).await {
+        Ok(templates) => json_response(&templates),
+        Err(err) => {
+            error!("Error get templates data: {:?}", err);
+            Err(TemplateError::InternalError)
+        }
+    }
+}
+
+pub async fn create_template(
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
+    _body: web::Json<TemplateRequest>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, TemplateError> {
+    validate_request(&_body)?;
+    if let Some(workspace_id) = &workspace.0 {
+        authorize_workspace(
+            workspace_id,
+            &auth_guard.user_id,
+            WorkspaceRole::Editor,
+            &pool,
+        )
+        .await?;
+    }
+
+    let request = _body.into_inner();
+    let template_data = TemplateData {
+        id: Uuid::new_v4().to_string(),
+        name: request.name.trim().to_string(),
+        title: request.title,
+        body: request.body,
+        user_id: auth_guard.user_id,
+        workspace_id: workspace.0,
+    };
+
+    match TemplateData::create_template(template_data, &pool).await {
+        Ok(template) => Ok(HttpResponse::Created().json(template)),
+        Err(err) => {
+            error!("Error created new template data: {:?}", err);
+            Err(TemplateError::InternalError)
+        }
+    }
+}
+
+pub async fn get_template_by_id(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, TemplateError> {
+    let template_id = path_param(&req, "id")?;
+    let template = load_template(
+        template_id,
+        &auth_guard.user_id,
+        WorkspaceRole::Viewer,
+        &pool,
+    )
+    .await?;
+
+    json_response(&template)
+}
+
+pub async fn update_template(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: web::Json<TemplateRequest>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, TemplateError> {
+    let template_id = path_param(&req, "id")?;
+    validate_request(&_body)?;
+    load_template(
+        template_id,
+        &auth_guard.user_id,
+        WorkspaceRole::Editor,
+        &pool,
+    )
+    .await?;
+
+    let mut request = _body.into_inner();
+    request.name = request.name.trim().to_string();
+
+    match TemplateData::update_template(template_id, &request, &pool).await {
+        Ok(0) => {
+            error!("Update failed");
+            Err(TemplateError::NotFound)
+        }
+        Err(err) => {
+            error!("Error updated template data: {:?}", err);
+            Err(TemplateError::InternalError)
+        }
+        Ok(_) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("updated template")),
+    }
+}
+
+pub async fn delete_template(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, TemplateError> {
+    let template_id = path_param(&req, "id")?;
+    load_template(
+        template_id,
+        &auth_guard.user_id,
+        WorkspaceRole::Editor,
+        &pool,
+    )
+    .await?;
+
+    match Template::delete_template_by_id(template_id, &pool).await {
+        Ok(0) => {
+            error!("Delete failed");
+            Err(TemplateError::NotFound)
+        }
+        Err(err) => {
+            error!("Error deleted template data: {:?}", err);
+            Err(TemplateError::InternalError)
+        }
+        Ok(_) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("deleted template")),
+    }
+}
+
+// POST /api/posts/from-template/{id}: 템플릿의 {{변수}}를 채워 새 노트 생성
+// 워크스페이스를 선택하지 않으면 워크스페이스 템플릿은 그 워크스페이스에, 개인 템플릿은 개인 노트로
+pub async fn create_post_from_template(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
+    _body: Option<web::Json<InstantiateRequest>>,
+    pool: Data<PgPool>,
+    events: Data<EventBus>,
+) -> Result<HttpResponse, TemplateError> {
+    let template_id = path_param(&req, "id")?;
+    let request = _body.map(web::Json::into_inner).unwrap_or_default();
+    let template = load_template(
+        template_id,
+        &auth_guard.user_id,
+        WorkspaceRole::Viewer,
+        &pool,
+    )
+    .await?;
+
+    let workspace_id = workspace.0.or(template.workspace_id.clone());
+    if let Some(workspace_id) = &workspace_id {
+        authorize_workspace(
+            workspace_id,
+            &auth_guard.user_id,
+            WorkspaceRole::Editor,
+            &pool,
+        )
+        .await?;
+    }
+
+    let user = User::get_users_by_id(&auth_guard.user_id, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get users by id data: {:?}", err);
+            TemplateError::InternalError
+        })?;
+    let context = Context {
+        now: user.local_now(),
+        user_name: &user.user_name,
+        email: &user.email,
+        fields: &request.fields,
+    };
+
+    let title = match request.title {
+        Some(title) => title,
+        None if template.title.trim().is_empty() => context.substitute(&template.name),
+        None => context.substitute(&template.title),
+    };
+    let post_data = PostData {
+        id: None,
+        title: title.chars().take(255).collect(),
+        body: context.substitute(&template.body),
+        published: request.published,
+        updated_at: None,
+        workspace_id,
+        author_id: None,
+        slug: None,
+        publish_at: None,
+        unpublish_at: None,
+    };
+
+    let post_id = PostData::create_posts(post_data, &auth_guard.user_id, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error created new post data: {:?}", err);
+            TemplateError::InternalError
+        })?;
+    after_post_saved(
+        &post_id,
+        Some(&auth_guard.user_id),
+        PostEventKind::Created,
+        request.published == Some(true),
+        &events,
+        &pool,
+    )
+    .await;
+
+    match Post::get_posts_load_by_id(&post_id, &pool).await {
+        Ok(post) => Ok(HttpResponse::Created().json(post)),
+        Err(err) => {
+            error!("Error get posts by id data: {:?}", err);
+            Err(TemplateError::InternalError)
+        }
+    }
+}
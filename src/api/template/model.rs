@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use crate::database::PgPool;
+use crate::schema::templates::dsl::*;
+use actix_web::web::Data;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::{result::Error, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::templates)]
+pub struct Template {
+    pub id: String,
+    pub name: String,
+    pub title: String,
+    pub body: String,
+    pub user_id: String,
+    pub workspace_id: Option<String>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::templates)]
+pub struct TemplateData {
+    pub id: String,
+    pub name: String,
+    pub title: String,
+    pub body: String,
+    pub user_id: String,
+    pub workspace_id: Option<String>,
+}
+
+// 템플릿 생성/수정 요청
+#[derive(Deserialize, Debug)]
+pub struct TemplateRequest {
+    pub name: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+}
+
+// 템플릿으로 노트 만들기 요청
+#[derive(Deserialize, Debug, Default)]
+pub struct InstantiateRequest {
+    // 템플릿의 {{이름}}에 들어갈 값
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+    // 템플릿 제목 대신 쓸 제목
+    pub title: Option<String>,
+    pub published: Option<bool>,
+}
+
+impl Template {
+    // 워크스페이스를 선택하면 그 워크스페이스 템플릿, 아니면 내 개인 템플릿
+    pub async fn get_templates(
+        template_user_id: &str,
+        workspace: Option<&str>,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Template>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        match workspace {
+            Some(workspace) => templates
+                .filter(workspace_id.eq(workspace))
+                .order(name.asc())
+                .load::<Template>(conn),
+            None => templates
+                .filter(user_id.eq(template_user_id))
+                .filter(workspace_id.is_null())
+                .order(name.asc())
+                .load::<Template>(conn),
+        }
+    }
+
+    pub async fn get_template_by_id(
+        template_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Template, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        templates.find(template_id).get_result::<Template>(conn)
+    }
+
+    pub async fn delete_template_by_id(
+        template_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::delete(templates.find(template_id)).execute(conn)
+    }
+}
+
+impl TemplateData {
+    pub async fn create_template(
+        template_data: TemplateData,
+        pool: &Data<PgPool>,
+    ) -> Result<Template, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::insert_into(templates)
+            .values(&template_data)
+            .get_result::<Template>(conn)
+    }
+
+    pub async fn update_template(
+        template_id: &str,
+        request: &TemplateRequest,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::update(templates.find(template_id))
+            .set((
+                name.eq(&request.name),
+                title.eq(&request.title),
+                body.eq(&request.body),
+                updated_at.eq(Some(Utc::now().naive_utc())),
+            ))
+            .execute(conn)
+    }
+}
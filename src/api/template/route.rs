@@ -0,0 +1,21 @@
+use crate::api::template::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(handler::get_templates))
+            .route(web::post().to(handler::create_template)),
+    );
+
+    cfg.service(
+        web::resource("/{id}")
+            .route(web::get().to(handler::get_template_by_id))
+            .route(web::put().to(handler::update_template))
+            .route(web::delete().to(handler::delete_template)),
+    );
+}
+
+pub fn configure_instantiate(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(handler::create_post_from_template)));
+}
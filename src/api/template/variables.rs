@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use chrono::NaiveDateTime;
+
+// 변수 이름 최대 길이, 넘으면 변수가 아닌 일반 텍스트로 둠
+const MAX_VARIABLE_LENGTH: usize = 64;
+
+// 템플릿에서 쓸 수 있는 기본 변수
+pub struct Context<'a> {
+    pub now: NaiveDateTime,
+    pub user_name: &'a str,
+    pub email: &'a str,
+    // 사용자가 넘긴 값, 같은 이름이면 기본 변수보다 우선
+    pub fields: &'a HashMap<String, String>,
+}
+
+impl Context<'_> {
+    fn format_date(&self, format: &str) -> Option<String> {
+        // 잘못된 형식 문자열이나 시간대가 필요한 %z, %Z는 fmt::Error가 나므로
+        // to_string()(panic) 대신 직접 써서 None으로
+        let mut formatted = String::new();
+        write!(formatted, "{}", self.now.format(format)).ok()?;
+        Some(formatted)
+    }
+
+    fn value(&self, variable: &str) -> Option<String> {
+        if let Some(value) = self.fields.get(variable) {
+            return Some(value.clone());
+        }
+
+        // {{date:%Y/%m/%d}}처럼 형식 지정
+        if let Some(format) = variable.strip_prefix("date:") {
+            return self.format_date(format);
+        }
+
+        match variable {
+            "date" => self.format_date("%Y-%m-%d"),
+            "time" => self.format_date("%H:%M"),
+            "datetime" => self.format_date("%Y-%m-%d %H:%M"),
+            "weekday" => self.format_date("%A"),
+            "user_name" => Some(self.user_name.to_string()),
+            "email" => Some(self.email.to_string()),
+            _ => None,
+        }
+    }
+
+    // {{변수}}를 값으로 바꿈, 모르는 변수는 그대로 둠
+    pub fn substitute(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(start) = rest.find("{{") {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let end = match after.find("}}") {
+                Some(end) if end <= MAX_VARIABLE_LENGTH => end,
+                _ => {
+                    result.push_str("{{");
+                    rest = after;
+                    continue;
+                }
+            };
+
+            match self.value(after[..end].trim()) {
+                Some(value) => result.push_str(&value),
+                None => result.push_str(&rest[start..start + end + 4]),
+            }
+            rest = &after[end + 2..];
+        }
+        result.push_str(rest);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn substitute(text: &str) -> String {
+        let fields = HashMap::from([("project".to_string(), "노트앱".to_string())]);
+        let context = Context {
+            now: NaiveDateTime::parse_from_str("2024-05-27 09:05:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            user_name: "Kim",
+            email: "kim@example.com",
+            fields: &fields,
+        };
+        context.substitute(text)
+    }
+
+    #[test]
+    fn substitutes_builtin_and_user_fields() {
+        assert_eq!(
+            substitute("# {{date}} {{ time }} {{weekday}}"),
+            "# 2024-05-27 09:05 Monday"
+        );
+        assert_eq!(
+            substitute("{{user_name}} <{{email}}>: {{project}}"),
+            "Kim <kim@example.com>: 노트앱"
+        );
+        assert_eq!(substitute("{{date:%Y/%m/%d}}"), "2024/05/27");
+    }
+
+    #[test]
+    fn keeps_unknown_and_unclosed_variables() {
+        assert_eq!(substitute("{{unknown}} 그대로"), "{{unknown}} 그대로");
+        assert_eq!(substitute("열림 {{date"), "열림 {{date");
+        assert_eq!(substitute("{{{{date}}}}"), "{{{{date}}}}");
+        let long = format!("{{{{{}}}}}", "x".repeat(MAX_VARIABLE_LENGTH + 1));
+        assert_eq!(substitute(&long), long);
+    }
+
+    #[test]
+    fn keeps_date_formats_that_cannot_be_rendered() {
+        // 시간대 정보가 없으므로 출력할 수 없음, panic하지 않고 그대로 둠
+        assert_eq!(substitute("{{date:%z}}"), "{{date:%z}}");
+        assert_eq!(substitute("{{date:%:z}}"), "{{date:%:z}}");
+        assert_eq!(substitute("{{date:%Z}}"), "{{date:%Z}}");
+        assert_eq!(substitute("{{date:%Q}}"), "{{date:%Q}}");
+        assert_eq!(substitute("{{date:%}}"), "{{date:%}}");
+    }
+
+    #[test]
+    fn handles_multibyte_and_crlf_text() {
+        assert_eq!(
+            substitute("회의록 {{date}}\r\n참석: {{user_name}}"),
+            "회의록 2024-05-27\r\n참석: Kim"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use actix_web::{
+    error::ResponseError,
+    http::{header::ContentType, StatusCode},
+    HttpResponse,
+};
+use derive_more::{Display, Error};
+use serde::Serialize;
+
+use super::handler::{TUS_RESUMABLE, TUS_VERSION};
+use crate::api::permission::authorize::AuthorizeError;
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    code: u16,
+    message: String,
+}
+
+#[derive(Debug, Display, Error)]
+pub enum UploadError {
+    #[display(fmt = "internal error")]
+    InternalError,
+
+    #[display(fmt = "bad request")]
+    BadClientData,
+
+    #[display(fmt = "not found")]
+    NotFound,
+
+    #[display(fmt = "forbidden")]
+    Forbidden,
+
+    #[display(fmt = "upload offset mismatch")]
+    OffsetConflict,
+
+    #[display(fmt = "upload exceeds {} bytes", limit)]
+    PayloadTooLarge { limit: i64 },
+
+    #[display(fmt = "content type must be application/offset+octet-stream")]
+    UnsupportedMediaType,
+
+    #[display(fmt = "unsupported tus version")]
+    UnsupportedVersion,
+
+    #[display(fmt = "Validation error on field: {}", field)]
+    ValidationError { field: String },
+}
+
+impl ResponseError for UploadError {
+    fn error_response(&self) -> HttpResponse {
+        let error_response = ErrorResponse {
+            code: self.status_code().as_u16(),
+            message: self.to_string(),
+        };
+
+        let mut response = HttpResponse::build(self.status_code());
+        response.insert_header(("Tus-Resumable", TUS_RESUMABLE));
+        if let UploadError::UnsupportedVersion = self {
+            response.insert_header(("Tus-Version", TUS_VERSION));
+        }
+
+        response
+            .insert_header(ContentType::json())
+            .json(error_response)
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match *self {
+            UploadError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            UploadError::BadClientData => StatusCode::BAD_REQUEST,
+            UploadError::NotFound => StatusCode::NOT_FOUND,
+            UploadError::Forbidden => StatusCode::FORBIDDEN,
+            UploadError::OffsetConflict => StatusCode::CONFLICT,
+            UploadError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            UploadError::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            UploadError::UnsupportedVersion => StatusCode::PRECONDITION_FAILED,
+            UploadError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<AuthorizeError> for UploadError {
+    fn from(err: AuthorizeError) -> Self {
+        match err {
+            AuthorizeError::NotFound => UploadError::NotFound,
+            AuthorizeError::Forbidden => UploadError::Forbidden,
+            AuthorizeError::InternalError => UploadError::InternalError,
+        }
+    }
+}
@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+use std::env;
+
+use super::error::UploadError;
+use super::model::{UploadChunk, UploadSession, UploadSessionData};
+use crate::api::attachment::handler::max_attachment_size;
+use crate::api::attachment::model::{Attachment, AttachmentData};
+use crate::api::attachment::thumbnail;
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::permission::authorize::authorize;
+use crate::api::permission::model::PostRole;
+use crate::database::PgPool;
+use crate::storage::BlobStore;
+use actix_web::http::header;
+use actix_web::{
+    web::{self, Data},
+    HttpRequest, HttpResponse, Result,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::BytesMut;
+use futures_util::StreamExt;
+use log::error;
+use uuid::Uuid;
+
+// tus 1.0.0 (https://tus.io/protocols/resumable-upload) core + creation, termination 확장
+pub const TUS_RESUMABLE: &str = "1.0.0";
+pub const TUS_VERSION: &str = "1.0.0";
+const TUS_EXTENSION: &str = "creation,termination";
+
+// 전체 업로드 최대 크기 기본값 (1GB)
+const DEFAULT_TUS_MAX_SIZE: i64 = 1024 * 1024 * 1024;
+// PATCH 한 번에 받을 수 있는 최대 크기 기본값 (16MB)
+const DEFAULT_TUS_MAX_CHUNK_SIZE: i64 = 16 * 1024 * 1024;
+
+fn env_limit(name: &str, default: i64) -> i64 {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(default)
+}
+
+fn header_str<'a>(req: &'a HttpRequest, name: &str) -> Option<&'a str> {
+    req.headers().get(name).and_then(|v| v.to_str().ok())
+}
+
+fn header_i64(req: &HttpRequest, name: &str) -> Result<i64, UploadError> {
+    header_str(req, name)
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|v| *v >= 0)
+        .ok_or_else(|| UploadError::ValidationError {
+            field: name.to_string(),
+        })
+}
+
+fn check_tus_resumable(req: &HttpRequest) -> Result<(), UploadError> {
+    match header_str(req, "Tus-Resumable") {
+        Some(TUS_RESUMABLE) => Ok(()),
+        _ => Err(UploadError::UnsupportedVersion),
+    }
+}
+
+// Upload-Metadata: "key base64value,key base64value"
+fn parse_metadata(value: &str) -> Result<HashMap<String, String>, UploadError> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let mut parts = pair.splitn(2, ' ');
+            let key = parts.next().unwrap_or_default().to_string();
+            let value = match parts.next() {
+                Some(encoded) => STANDARD
+                    .decode(encoded.trim())
+                    .ok()
+                    .and_then(|decoded| String::from_utf8(decoded).ok())
+                    .ok_or_else(|| UploadError::ValidationError {
+                        field: format!("Upload-Metadata.{}", key),
+                    })?,
+                None => String::new(),
+            };
+
+            Ok((key, value))
+        })
+        .collect()
+}
+
+async fn get_owned_upload(
+    req: &HttpRequest,
+    auth_guard: &AuthenticationGuard,
+    pool: &Data<PgPool>,
+) -> Result<UploadSession, UploadError> {
+    let upload_id = req
+        .match_info()
+        .get("id")
+        .ok_or(UploadError::BadClientData)?;
+
+    let upload = UploadSession::get_upload_by_id(upload_id, pool)
+        .await
+        .map_err(|err| {
+            error!("Error get upload session data: {:?}", err);
+            UploadError::NotFound
+        })?;
+
+    if upload.user_id != auth_guard.user_id {
+        return Err(UploadError::Forbidden);
+    }
+
+    Ok(upload)
+}
+
+// 마지막 chunk까지 받았지만 조립에 실패해 첨부파일을 만들지 못한 업로드
+fn is_unfinished(upload: &UploadSession) -> bool {
+    !upload.completed && upload.upload_offset == upload.upload_length
+}
+
+fn tus_response(mut response: actix_web::HttpResponseBuilder) -> actix_web::HttpResponseBuilder {
+    response.insert_header(("Tus-Resumable", TUS_RESUMABLE));
+    response
+}
+
+pub async fn get_upload_options() -> HttpResponse {
+    tus_response(HttpResponse::NoContent())
+        .insert_header(("Tus-Version", TUS_VERSION))
+        .insert_header(("Tus-Extension", TUS_EXTENSION))
+        .insert_header((
+            "Tus-Max-Size",
+            env_limit("TUS_MAX_SIZE", DEFAULT_TUS_MAX_SIZE).to_string(),
+        ))
+        .finish()
+}
+
+pub async fn create_upload(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+    store: Data<dyn BlobStore>,
+) -> Result<HttpResponse, UploadError> {
+    check_tus_resumable(&req)?;
+
+    let upload_length = header_i64(&req, "Upload-Length")?;
+    let limit = env_limit("TUS_MAX_SIZE", DEFAULT_TUS_MAX_SIZE);
+    if upload_length > limit {
+        return Err(UploadError::PayloadTooLarge { limit });
+    }
+
+    let metadata = parse_metadata(header_str(&req, "Upload-Metadata").unwrap_or_default())?;
+    let post_id = metadata
+        .get("post_id")
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| UploadError::ValidationError {
+            field: "Upload-Metadata.post_id".to_string(),
+        })?;
+
+    authorize(post_id, &auth_guard.user_id, PostRole::Editor, &pool).await?;
+
+    let file_name = metadata
+        .get("filename")
+        .filter(|v| !v.is_empty())
+        .cloned()
+        .unwrap_or_else(|| "file".to_string());
+    let content_type = metadata
+        .get("filetype")
+        .filter(|v| !v.is_empty())
+        .cloned()
+        .unwrap_or_else(|| {
+            mime_guess::from_path(&file_name)
+                .first_or_octet_stream()
+                .to_string()
+        });
+
+    // 이미지는 조립한 뒤 메타데이터를 지우느라 통째로 읽으므로 일반 첨부파일 크기까지만 받음
+    let image_limit = max_attachment_size() as i64;
+    if thumbnail::is_supported_image(&content_type) && upload_length > image_limit {
+        return Err(UploadError::PayloadTooLarge { limit: image_limit });
+    }
+
+    let upload_data = UploadSessionData {
+        id: Uuid::new_v4().to_string(),
+        user_id: auth_guard.user_id,
+        post_id: post_id.to_string(),
+        file_name,
+        content_type,
+        upload_length,
+    };
+
+    let upload = UploadSessionData::create_upload(upload_data, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error created new upload session data: {:?}", err);
+            UploadError::InternalError
+        })?;
+
+    let mut response = tus_response(HttpResponse::Created());
+    response.insert_header((header::LOCATION, format!("/api/uploads/{}", upload.id)));
+
+    // 빈 파일은 PATCH 없이 바로 첨부
+    if upload.upload_length == 0 {
+        let attachment = finish_upload(&upload, &pool, &store).await?;
+        response.insert_header(("Attachment-Id", attachment.id));
+    }
+
+    Ok(response.finish())
+}
+
+pub async fn get_upload_offset(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, UploadError> {
+    check_tus_resumable(&req)?;
+    let upload = get_owned_upload(&req, &auth_guard, &pool).await?;
+
+    let mut response = tus_response(HttpResponse::Ok());
+    response
+        .insert_header(("Upload-Offset", upload.upload_offset.to_string()))
+        .insert_header(("Upload-Length", upload.upload_length.to_string()))
+        .insert_header((header::CACHE_CONTROL, "no-store"));
+    if let Some(attachment_id) = upload.attachment_id {
+        response.insert_header(("Attachment-Id", attachment_id));
+    }
+
+    Ok(response.finish())
+}
+
+pub async fn append_upload_chunk(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    mut payload: web::Payload,
+    pool: Data<PgPool>,
+    store: Data<dyn BlobStore>,
+) -> Result<HttpResponse, UploadError> {
+    check_tus_resumable(&req)?;
+
+    if header_str(&req, header::CONTENT_TYPE.as_str()) != Some("application/offset+octet-stream") {
+        return Err(UploadError::UnsupportedMediaType);
+    }
+
+    let offset = header_i64(&req, "Upload-Offset")?;
+    let upload = get_owned_upload(&req, &auth_guard, &pool).await?;
+
+    // 마지막 PATCH 뒤 조립에 실패했으면 같은 offset으로 다시 보낸 PATCH에서 다시 시도
+    if is_unfinished(&upload) && offset == upload.upload_offset {
+        let attachment = finish_upload(&upload, &pool, &store).await?;
+        return Ok(tus_response(HttpResponse::NoContent())
+            .insert_header(("Upload-Offset", upload.upload_offset.to_string()))
+            .insert_header(("Attachment-Id", attachment.id))
+            .finish());
+    }
+
+    if upload.completed || offset != upload.upload_offset {
+        return Err(UploadError::OffsetConflict);
+    }
+
+    let remaining = upload.upload_length - upload.upload_offset;
+    let limit = remaining.min(env_limit("TUS_MAX_CHUNK_SIZE", DEFAULT_TUS_MAX_CHUNK_SIZE));
+
+    let mut data = BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(|err| {
+            error!("Error reading upload chunk: {:?}", err);
+            UploadError::BadClientData
+        })?;
+
+        if (data.len() + chunk.len()) as i64 > limit {
+            return Err(UploadError::PayloadTooLarge { limit });
+        }
+        data.extend_from_slice(&chunk);
+    }
+
+    if data.is_empty() {
+        return Ok(tus_response(HttpResponse::NoContent())
+            .insert_header(("Upload-Offset", upload.upload_offset.to_string()))
+            .finish());
+    }
+
+    let chunk = UploadChunk {
+        upload_id: upload.id.clone(),
+        chunk_offset: offset,
+        size: data.len() as i64,
+        storage_key: format!("uploads/{}/{:020}", upload.id, offset),
+    };
+
+    if let Err(err) = store.put(&chunk.storage_key, data.freeze()).await {
+        error!("Error storing upload chunk blob: {:?}", err);
+        return Err(UploadError::InternalError);
+    }
+
+    let new_offset = offset + chunk.size;
+    let storage_key = chunk.storage_key.clone();
+
+    match UploadSession::append_chunk(chunk, offset, &pool).await {
+        Ok(1) => {}
+        Ok(_) => {
+            // 다른 요청이 먼저 같은 offset을 기록함
+            if let Err(err) = store.delete(&storage_key).await {
+                error!("Error removing conflicting chunk blob: {:?}", err);
+            }
+            return Err(UploadError::OffsetConflict);
+        }
+        Err(err) => {
+            error!("Error updated upload session data: {:?}", err);
+            if let Err(err) = store.delete(&storage_key).await {
+                error!("Error removing orphaned chunk blob: {:?}", err);
+            }
+            return Err(UploadError::InternalError);
+        }
+    }
+
+    let mut response = tus_response(HttpResponse::NoContent());
+    response.insert_header(("Upload-Offset", new_offset.to_string()));
+
+    if new_offset == upload.upload_length {
+        let attachment = finish_upload(&upload, &pool, &store).await?;
+        response.insert_header(("Attachment-Id", attachment.id));
+    }
+
+    Ok(response.finish())
+}
+
+// 모든 chunk를 저장소에서 하나로 합친 뒤 노트 첨부파일로 등록
+async fn finish_upload(
+    upload: &UploadSession,
+    pool: &Data<PgPool>,
+    store: &Data<dyn BlobStore>,
+) -> Result<Attachment, UploadError> {
+    let chunks = UploadSession::get_chunks(&upload.id, pool)
+        .await
+        .map_err(|err| {
+            error!("Error get upload chunks data: {:?}", err);
+            UploadError::InternalError
+        })?;
+    let chunk_keys = chunks
+        .into_iter()
+        .map(|chunk| chunk.storage_key)
+        .collect::<Vec<_>>();
+
+    let attachment_id = Uuid::new_v4().to_string();
+    let storage_key = format!("attachments/{}/{}", upload.post_id, attachment_id);
+
+    if let Err(err) = store.concat(&chunk_keys, &storage_key).await {
+        error!("Error assembling upload chunks: {:?}", err);
+        return Err(UploadError::InternalError);
+    }
+
+    let mut size = upload.upload_length;
+    let mut image_data = None;
+
+    // 메타데이터 제거는 본문 전체를 메모리에 읽으므로 일반 첨부파일 크기까지만 (더 큰 이미지는 만들 때 거절)
+    if thumbnail::is_supported_image(&upload.content_type)
+        && upload.upload_length <= max_attachment_size() as i64
+    {
+        let data = store.get(&storage_key, None).await.map_err(|err| {
+            error!("Error reading assembled upload blob: {:?}", err);
+            UploadError::InternalError
+        })?;
+        let data = web::block(move || thumbnail::strip_metadata(data))
+            .await
+            .map_err(|err| {
+                error!("Error stripping image metadata: {:?}", err);
+                UploadError::InternalError
+            })?;
+
+        if let Err(err) = store.put(&storage_key, data.clone()).await {
+            error!("Error storing attachment blob: {:?}", err);
+            return Err(UploadError::InternalError);
+        }
+
+        size = data.len() as i64;
+        image_data = Some(data);
+    }
+
+    let attachment_data = AttachmentData {
+        id: attachment_id,
+        post_id: upload.post_id.clone(),
+        user_id: upload.user_id.clone(),
+        file_name: upload.file_name.clone(),
+        content_type: upload.content_type.clone(),
+        size,
+        storage_key: storage_key.clone(),
+    };
+
+    let attachment = match AttachmentData::create_attachment(attachment_data, pool).await {
+        Ok(attachment) => attachment,
+        Err(err) => {
+            error!("Error created new attachment data: {:?}", err);
+            if let Err(err) = store.delete(&storage_key).await {
+                error!("Error removing orphaned attachment blob: {:?}", err);
+            }
+            return Err(UploadError::InternalError);
+        }
+    };
+
+    match UploadSession::complete_upload(&upload.id, &attachment.id, pool).await {
+        Ok(0) => return finished_elsewhere(&upload.id, &attachment, pool, store).await,
+        Ok(_) => {}
+        Err(err) => {
+            error!("Error completed upload session data: {:?}", err);
+            return Err(UploadError::InternalError);
+        }
+    }
+
+    for chunk_key in chunk_keys {
+        if let Err(err) = store.delete(&chunk_key).await {
+            error!("Error deleting upload chunk blob: {:?}", err);
+        }
+    }
+
+    if let Some(data) = image_data {
+        thumbnail::spawn_thumbnail_job(attachment.clone(), data, pool.clone(), store.clone());
+    }
+
+    Ok(attachment)
+}
+
+// 동시에 들어온 다른 요청(PATCH 재시도, HEAD)이 먼저 완료함: 만든 첨부파일은 지우고 그쪽 결과를 돌려줌
+async fn finished_elsewhere(
+    upload_id: &str,
+    attachment: &Attachment,
+    pool: &Data<PgPool>,
+    store: &Data<dyn BlobStore>,
+) -> Result<Attachment, UploadError> {
+    if let Err(err) = Attachment::delete_attachment_by_id(&attachment.id, pool).await {
+        error!("Error deleting duplicate attachment data: {:?}", err);
+    }
+    if let Err(err) = store.delete(&attachment.storage_key).await {
+        error!("Error removing duplicate attachment blob: {:?}", err);
+    }
+
+    let attachment_id = UploadSession::get_upload_by_id(upload_id, pool)
+        .await
+        .ok()
+        .and_then(|upload| upload.attachment_id)
+        .ok_or(UploadError::InternalError)?;
+    Attachment::get_attachment_by_attachment_id(&attachment_id, pool)
+        .await
+        .map_err(|err| {
+            error!("Error get attachment data: {:?}", err);
+            UploadError::InternalError
+        })
+}
+
+pub async fn delete_upload(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+    store: Data<dyn BlobStore>,
+) -> Result<HttpResponse, UploadError> {
+    check_tus_resumable(&req)?;
+    let upload = get_owned_upload(&req, &auth_guard, &pool).await?;
+
+    let chunks = UploadSession::get_chunks(&upload.id, &pool)
+        .await
+        .unwrap_or_default();
+
+    match UploadSession::delete_upload_by_id(&upload.id, &pool).await {
+        Ok(0) => Err(UploadError::NotFound),
+        Err(err) => {
+            error!("Error deleted upload session data: {:?}", err);
+            Err(UploadError::InternalError)
+        }
+        Ok(_) => {
+            for chunk in chunks {
+                if let Err(err) = store.delete(&chunk.storage_key).await {
+                    error!("Error deleting upload chunk blob: {:?}", err);
+                }
+            }
+
+            Ok(tus_response(HttpResponse::NoContent()).finish())
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::database::PgPool;
+use crate::schema::{upload_chunks, upload_sessions};
+use actix_web::web::Data;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::{result::Error, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::upload_sessions)]
+pub struct UploadSession {
+    pub id: String,
+    pub user_id: String,
+    pub post_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub upload_length: i64,
+    pub upload_offset: i64,
+    pub attachment_id: Option<String>,
+    pub completed: bool,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::upload_sessions)]
+pub struct UploadSessionData {
+    pub id: String,
+    pub user_id: String,
+    pub post_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub upload_length: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[diesel(table_name = crate::schema::upload_chunks)]
+pub struct UploadChunk {
+    pub upload_id: String,
+    pub chunk_offset: i64,
+    pub size: i64,
+    pub storage_key: String,
+}
+
+impl UploadSession {
+    pub async fn get_upload_by_id(
+        upload_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<UploadSession, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        upload_sessions::table
+            .find(upload_id)
+            .get_result::<UploadSession>(conn)
+    }
+
+    pub async fn get_chunks(
+        upload_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<UploadChunk>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        upload_chunks::table
+            .filter(upload_chunks::upload_id.eq(upload_id))
+            .order(upload_chunks::chunk_offset.asc())
+            .load::<UploadChunk>(conn)
+    }
+
+    // 현재 offset이 expected_offset일 때만 chunk 기록 (동시 PATCH 충돌 방지)
+    pub async fn append_chunk(
+        chunk: UploadChunk,
+        expected_offset: i64,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            let updated = diesel::update(
+                upload_sessions::table
+                    .find(&chunk.upload_id)
+                    .filter(upload_sessions::upload_offset.eq(expected_offset)),
+            )
+            .set((
+                upload_sessions::upload_offset.eq(expected_offset + chunk.size),
+                upload_sessions::updated_at.eq(Some(Utc::now().naive_utc())),
+            ))
+            .execute(conn)?;
+
+            if updated == 1 {
+                diesel::insert_into(upload_chunks::table)
+                    .values(&chunk)
+                    .execute(conn)?;
+            }
+
+            Ok(updated)
+        })
+    }
+
+    pub async fn complete_upload(
+        upload_id: &str,
+        attachment_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            // 다른 요청이 먼저 완료했으면 0
+            let updated = diesel::update(
+                upload_sessions::table
+                    .find(upload_id)
+                    .filter(upload_sessions::completed.eq(false)),
+            )
+            .set((
+                upload_sessions::attachment_id.eq(Some(attachment_id)),
+                upload_sessions::completed.eq(true),
+            ))
+            .execute(conn)?;
+
+            if updated > 0 {
+                diesel::delete(upload_chunks::table.filter(upload_chunks::upload_id.eq(upload_id)))
+                    .execute(conn)?;
+            }
+
+            Ok(updated)
+        })
+    }
+
+    pub async fn delete_upload_by_id(upload_id: &str, pool: &Data<PgPool>) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::delete(upload_sessions::table.find(upload_id)).execute(conn)
+    }
+}
+
+impl UploadSessionData {
+    pub async fn create_upload(
+        upload_data: UploadSessionData,
+        pool: &Data<PgPool>,
+    ) -> Result<UploadSession, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::insert_into(upload_sessions::table)
+            .values(upload_data)
+            .get_result::<UploadSession>(conn)
+    }
+}
@@ -0,0 +1,18 @@
+use crate::api::upload::handler;
+use actix_web::{http::Method, web};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::method(Method::OPTIONS).to(handler::get_upload_options))
+            .route(web::post().to(handler::create_upload)),
+    );
+
+    cfg.service(
+        web::resource("/{id}")
+            .route(web::method(Method::OPTIONS).to(handler::get_upload_options))
+            .route(web::head().to(handler::get_upload_offset))
+            .route(web::patch().to(handler::append_upload_chunk))
+            .route(web::delete().to(handler::delete_upload)),
+    );
+}
@@ -25,6 +25,9 @@ pub enum UserError {
 
     #[display(fmt = "Validation error on field: {}", field)]
     ValidationError { field: String },
+
+    #[display(fmt = "handle already in use")]
+    HandleTaken,
 }
 
 impl ResponseError for UserError {
@@ -45,6 +48,7 @@ impl ResponseError for UserError {
             UserError::BadClientData => StatusCode::BAD_REQUEST,
             UserError::Timeout => StatusCode::GATEWAY_TIMEOUT,
             UserError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+            UserError::HandleTaken => StatusCode::CONFLICT,
         }
     }
 }
@@ -1,8 +1,16 @@
 use super::error::UserError;
-use super::model::{EmailQueryParam, UpdateUserData, User, UserData};
+use super::model::{
+    is_valid_handle, EmailQueryParam, UpdateUserData, UpdateUserSettings, User, UserData,
+    UserSettingsData, HANDLE_CONSTRAINT,
+};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::template::handler::load_template;
+use crate::api::workspace::model::WorkspaceRole;
 use crate::database::PgPool;
 use actix_web::Responder;
 use actix_web::{http::header::ContentType, web, web::Data, HttpRequest, HttpResponse, Result};
+use chrono_tz::Tz;
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
 use log::{error, info, warn};
 use serde_json::to_vec;
 
@@ -61,12 +69,28 @@ pub async fn create_users(
     _body: web::Json<UserData>,
     pool: Data<PgPool>,
 ) -> Result<HttpResponse, UserError> {
-    let user_data = _body.into_inner();
+    let mut user_data = _body.into_inner();
+
+    // handle은 소문자로 저장
+    user_data.handle = user_data.handle.map(|handle| handle.trim().to_lowercase());
+    if let Some(handle) = &user_data.handle {
+        if !is_valid_handle(handle) {
+            return Err(UserError::ValidationError {
+                field: "handle".to_string(),
+            });
+        }
+    }
 
     match UserData::create_users(user_data, &pool).await {
         Ok(_) => Ok(HttpResponse::Ok()
             .content_type(ContentType::json())
             .body("created new user")),
+        // 다른 사용자가 쓰는 handle
+        Err(DatabaseError(DatabaseErrorKind::UniqueViolation, info))
+            if info.constraint_name() == Some(HANDLE_CONSTRAINT) =>
+        {
+            Err(UserError::HandleTaken)
+        }
         Err(err) => {
             error!("Error created new user data: {:?}", err);
             Err(UserError::BadClientData)
@@ -120,3 +144,87 @@ pub async fn delete_users_by_id(
         }
     }
 }
+
+// GET /api/users/settings: 시간대, 데일리 노트 템플릿
+pub async fn get_settings(
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, UserError> {
+    match User::get_users_by_id(&auth_guard.user_id, &pool).await {
+        Ok(user) => {
+            let json_bytes = to_vec(&user.settings()).map_err(|err| {
+                error!("Failed to serialize user settings to JSON: {:?}", err);
+                UserError::InternalError
+            })?;
+
+            Ok(HttpResponse::Ok()
+                .content_type(ContentType::json())
+                .body(json_bytes))
+        }
+        Err(err) => {
+            error!("Error get users by id data: {:?}", err);
+            Err(UserError::InternalError)
+        }
+    }
+}
+
+pub async fn update_settings(
+    auth_guard: AuthenticationGuard,
+    _body: web::Json<UpdateUserSettings>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, UserError> {
+    let mut settings = _body.into_inner();
+
+    // handle은 소문자로 저장
+    settings.handle = settings.handle.map(|handle| handle.trim().to_lowercase());
+    if let Some(handle) = &settings.handle {
+        if !is_valid_handle(handle) {
+            return Err(UserError::ValidationError {
+                field: "handle".to_string(),
+            });
+        }
+    }
+    if let Some(timezone) = &settings.timezone {
+        if timezone.parse::<Tz>().is_err() {
+            return Err(UserError::ValidationError {
+                field: "timezone".to_string(),
+            });
+        }
+    }
+    // 쓸 수 있는 템플릿만 지정 가능
+    if let Some(Some(template_id)) = &settings.daily_template_id {
+        load_template(
+            template_id,
+            &auth_guard.user_id,
+            WorkspaceRole::Viewer,
+            &pool,
+        )
+        .await
+        .map_err(|_| UserError::ValidationError {
+            field: "daily_template_id".to_string(),
+        })?;
+    }
+
+    let settings_data = UserSettingsData {
+        handle: settings.handle,
+        timezone: settings.timezone,
+        daily_template_id: settings.daily_template_id,
+        updated_at: None,
+    };
+
+    match User::update_settings(&auth_guard.user_id, settings_data, &pool).await {
+        Ok(0) => {
+            error!("Update failed");
+            Err(UserError::BadClientData)
+        }
+        // 다른 사용자가 쓰는 handle
+        Err(DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => Err(UserError::HandleTaken),
+        Err(err) => {
+            error!("Error updated user settings data: {:?}", err);
+            Err(UserError::InternalError)
+        }
+        Ok(_) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("updated user settings")),
+    }
+}
@@ -2,11 +2,20 @@ use crate::database::PgPool;
 use crate::schema::users::{self};
 use actix_web::web::Data;
 use chrono::Utc;
+use chrono_tz::Tz;
 use diesel::prelude::*;
-use diesel::{result::Error, AsChangeset, Insertable, Queryable};
-use serde::{Deserialize, Serialize};
+use diesel::sql_types::Text;
+use diesel::{
+    result::{DatabaseErrorKind, Error},
+    AsChangeset, Insertable, Queryable,
+};
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
 
+diesel::define_sql_function! {
+    fn lower(text: Text) -> Text;
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
 #[diesel(table_name = crate::schema::users)]
 pub struct User {
@@ -19,6 +28,9 @@ pub struct User {
     pub photo: String,
     pub created_at: Option<chrono::NaiveDateTime>,
     pub updated_at: Option<chrono::NaiveDateTime>,
+    pub timezone: String,
+    pub daily_template_id: Option<String>,
+    pub handle: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
@@ -31,6 +43,9 @@ pub struct UserData {
     pub verified: bool,
     pub provider: String,
     pub photo: String,
+    // 없으면 이메일로 만듦
+    #[serde(default)]
+    pub handle: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, AsChangeset)]
@@ -51,7 +66,100 @@ pub struct EmailQueryParam {
     pub email: String,
 }
 
+#[derive(Serialize, Debug)]
+pub struct UserSettings {
+    pub handle: String,
+    pub timezone: String,
+    pub daily_template_id: Option<String>,
+}
+
+// 보낸 필드만 변경, daily_template_id가 null이면 템플릿 해제
+#[derive(Deserialize, Debug)]
+pub struct UpdateUserSettings {
+    pub handle: Option<String>,
+    pub timezone: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub daily_template_id: Option<Option<String>>,
+}
+
+#[derive(Debug, AsChangeset)]
+#[diesel(table_name = crate::schema::users)]
+pub struct UserSettingsData {
+    pub handle: Option<String>,
+    pub timezone: Option<String>,
+    pub daily_template_id: Option<Option<String>>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+// users.handle의 unique 인덱스
+pub const HANDLE_CONSTRAINT: &str = "users_handle_key";
+
+// 자동으로 만든 handle이 겹쳤을 때 다시 시도하는 횟수
+const HANDLE_ATTEMPTS: usize = 5;
+
+// @멘션 이름: 소문자, 숫자, _ 3~32자
+pub fn is_valid_handle(handle: &str) -> bool {
+    (3..=32).contains(&handle.len())
+        && handle
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+// 이메일 앞부분으로 만든 기본 handle (마이그레이션의 backfill과 같은 규칙)
+fn handle_base(email: &str) -> String {
+    let local = email.split('@').next().unwrap_or_default().to_lowercase();
+    let mut base: String = local
+        .chars()
+        .filter(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '_')
+        .take(24)
+        .collect();
+    while base.len() < 3 {
+        base.push('_');
+    }
+    base
+}
+
+// 필드가 없으면 None, null이면 Some(None)
+fn deserialize_nullable<'de, D>(deserializer: D) -> Result<Option<Option<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer).map(Some)
+}
+
 impl User {
+    // 잘못 저장된 시간대는 UTC로
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse::<Tz>().unwrap_or(Tz::UTC)
+    }
+
+    // 사용자 시간대의 현재 시각
+    pub fn local_now(&self) -> chrono::NaiveDateTime {
+        Utc::now().with_timezone(&self.tz()).naive_local()
+    }
+
+    pub fn settings(&self) -> UserSettings {
+        UserSettings {
+            handle: self.handle.clone(),
+            timezone: self.timezone.clone(),
+            daily_template_id: self.daily_template_id.clone(),
+        }
+    }
+
+    pub async fn update_settings(
+        user_id: &str,
+        settings: UserSettingsData,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::update(users::table.find(user_id))
+            .set(UserSettingsData {
+                updated_at: Some(Utc::now().naive_utc()),
+                ..settings
+            })
+            .execute(conn)
+    }
+
     pub async fn get_users(pool: &Data<PgPool>) -> Result<Vec<(String, String)>, Error> {
         let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
 
@@ -78,6 +186,30 @@ impl User {
         users::table.find(user_id).get_result::<User>(conn)
     }
 
+    // 공개 페이지, 피드 주소에는 id 대신 handle도 쓸 수 있음
+    pub async fn get_users_by_id_or_handle(user: &str, pool: &Data<PgPool>) -> Result<User, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        users::table
+            .filter(users::id.eq(user).or(users::handle.eq(user)))
+            .first::<User>(conn)
+    }
+
+    // 멘션 대상 찾기: handle 또는 이메일 (둘 다 소문자로 비교)
+    pub async fn get_users_by_handles_or_emails(
+        handles: &[String],
+        emails: &[String],
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<User>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        users::table
+            .filter(
+                users::handle
+                    .eq_any(handles)
+                    .or(lower(users::email).eq_any(emails)),
+            )
+            .load::<User>(conn)
+    }
+
     pub fn get_users_auth(user_id: &str, pool: &Data<PgPool>) -> Result<User, Error> {
         let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
         users::table.find(user_id).get_result::<User>(conn)
@@ -91,20 +223,61 @@ impl User {
 
 impl UserData {
     pub async fn create_users(user_data: UserData, pool: &Data<PgPool>) -> Result<String, Error> {
-        let user = UserData {
-            id: Some(Uuid::new_v4().to_string()),
-            ..user_data
-        };
-
         let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
 
-        diesel::insert_into(users::table)
-            .values(user)
-            .returning(users::id)
-            .get_result::<String>(conn)
+        if user_data.handle.is_some() {
+            return insert_user(conn, user_data);
+        }
+
+        // 고른 이름을 동시에 가입한 사용자가 먼저 가져갔으면 다시 고름
+        let mut attempts = 1;
+        loop {
+            let handle = free_handle(conn, &user_data.email)?;
+            let user = UserData {
+                handle: Some(handle),
+                ..user_data.clone()
+            };
+            match insert_user(conn, user) {
+                Err(Error::DatabaseError(DatabaseErrorKind::UniqueViolation, info))
+                    if info.constraint_name() == Some(HANDLE_CONSTRAINT)
+                        && attempts < HANDLE_ATTEMPTS =>
+                {
+                    attempts += 1;
+                }
+                result => return result,
+            }
+        }
     }
 }
 
+// 이메일 앞부분으로 만든 이름, 같은 이름이 있으면 _2, _3...
+fn free_handle(conn: &mut PgConnection, email: &str) -> Result<String, Error> {
+    let base = handle_base(email);
+    let taken: Vec<String> = users::table
+        .select(users::handle)
+        .filter(users::handle.like(format!("{}%", base)))
+        .load::<String>(conn)?;
+    Ok((1..)
+        .map(|n| match n {
+            1 => base.clone(),
+            _ => format!("{}_{}", base, n),
+        })
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap_or(base))
+}
+
+fn insert_user(conn: &mut PgConnection, user_data: UserData) -> Result<String, Error> {
+    let user = UserData {
+        id: Some(Uuid::new_v4().to_string()),
+        ..user_data
+    };
+
+    diesel::insert_into(users::table)
+        .values(user)
+        .returning(users::id)
+        .get_result::<String>(conn)
+}
+
 impl UpdateUserData {
     pub async fn update_users(
         user_data: UpdateUserData,
@@ -11,5 +11,11 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
 
     cfg.service(web::resource("/email").route(web::post().to(handler::get_users_by_email)));
 
+    cfg.service(
+        web::resource("/settings")
+            .route(web::get().to(handler::get_settings))
+            .route(web::put().to(handler::update_settings)),
+    );
+
     cfg.service(web::resource("/{id}").route(web::delete().to(handler::delete_users_by_id)));
 }
@@ -0,0 +1,80 @@
+use super::model::{Webhook, WebhookDeliveryData};
+use crate::api::event::model::PostEventKind;
+use crate::api::post::model::Post;
+use crate::database::PgPool;
+use actix_web::web::Data;
+use chrono::Utc;
+use log::error;
+use serde::Serialize;
+use uuid::Uuid;
+
+// 웹훅으로 보내는 본문
+#[derive(Serialize, Debug)]
+pub struct WebhookPayload<'a> {
+    pub event: &'a str,
+    pub occurred_at: String,
+    pub post: &'a Post,
+}
+
+pub fn event_name(kind: PostEventKind) -> &'static str {
+    match kind {
+        PostEventKind::Created => "post.created",
+        PostEventKind::Updated => "post.updated",
+        PostEventKind::Deleted => "post.deleted",
+        PostEventKind::Published => "post.published",
+    }
+}
+
+// 노트 이벤트를 구독한 웹훅마다 전송을 쌓아두면 워커가 보냄, 실패해도 노트 저장에는 영향 없음
+// recipients: 지금 노트에 접근할 수 있는 사용자 (삭제면 삭제 직전)
+pub async fn enqueue(post: &Post, kind: PostEventKind, recipients: &[String], pool: &Data<PgPool>) {
+    let event = event_name(kind);
+    let subscribed = match Webhook::get_subscribed_webhooks(
+        post.workspace_id.as_deref(),
+        post.author_id.as_deref(),
+        recipients,
+        event,
+        pool,
+    )
+    .await
+    {
+        Ok(subscribed) => subscribed,
+        Err(err) => {
+            error!("Error get subscribed webhooks: {:?}", err);
+            return;
+        }
+    };
+    if subscribed.is_empty() {
+        return;
+    }
+
+    let now = Utc::now();
+    let payload = WebhookPayload {
+        event,
+        occurred_at: now.to_rfc3339(),
+        post,
+    };
+    let payload = match serde_json::to_string(&payload) {
+        Ok(payload) => payload,
+        Err(err) => {
+            error!("Failed to serialize webhook payload to JSON: {:?}", err);
+            return;
+        }
+    };
+
+    let delivery_data: Vec<WebhookDeliveryData> = subscribed
+        .into_iter()
+        .map(|webhook| WebhookDeliveryData {
+            id: Uuid::new_v4().to_string(),
+            webhook_id: webhook.id,
+            event: event.to_string(),
+            payload: payload.clone(),
+            next_attempt_at: Some(now.naive_utc()),
+            redelivery_of: None,
+        })
+        .collect();
+
+    if let Err(err) = WebhookDeliveryData::create_deliveries(delivery_data, pool).await {
+        error!("Error created webhook delivery data: {:?}", err);
+    }
+}
@@ -0,0 +1,309 @@
+use super::error::WebhookError;
+use super::model::{
+    CreatedWebhook, DeliveryListQuery, DeliveryStatus, Webhook, WebhookChanges, WebhookData,
+    WebhookDelivery, WebhookDeliveryData, WebhookRequest, WebhookUpdate, WEBHOOK_EVENTS,
+};
+use super::target;
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::permission::authorize::authorize_workspace;
+use crate::api::workspace::model::WorkspaceRole;
+use crate::api::workspace::selector::WorkspaceSelector;
+use crate::database::PgPool;
+use actix_web::{
+    http::header::ContentType,
+    web::{Data, Json, Query},
+    HttpRequest, HttpResponse, Result,
+};
+use chrono::Utc;
+use diesel::result::Error as DieselError;
+use log::error;
+use rand::{rngs::OsRng, RngCore};
+use reqwest::Url;
+use serde_json::to_vec;
+use uuid::Uuid;
+
+const MAX_URL_LENGTH: usize = 2048;
+const MIN_SECRET_LENGTH: usize = 16;
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, WebhookError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        WebhookError::BadClientData
+    })
+}
+
+fn internal_error(err: DieselError) -> WebhookError {
+    error!("Error webhook data: {:?}", err);
+    WebhookError::InternalError
+}
+
+fn json_response<T: serde::Serialize>(data: &T) -> Result<HttpResponse, WebhookError> {
+    let json_bytes = to_vec(data).map_err(|err| {
+        error!("Failed to serialize webhook data to JSON: {:?}", err);
+        WebhookError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+async fn check_url(url: &str) -> Result<String, WebhookError> {
+    let url = url.trim();
+    let invalid = || WebhookError::ValidationError {
+        field: "url".to_string(),
+    };
+    if url.len() > MAX_URL_LENGTH {
+        return Err(invalid());
+    }
+    let parsed = Url::parse(url).map_err(|_| invalid())?;
+    if !matches!(parsed.scheme(), "http" | "https") || !parsed.has_host() {
+        return Err(invalid());
+    }
+    // 서버 내부망으로 요청을 보내는 데 쓰이지 않도록
+    if let Err(err) = target::resolve(&parsed).await {
+        error!("Rejected webhook url: {}", err);
+        return Err(invalid());
+    }
+    Ok(url.to_string())
+}
+
+// 알 수 없는 이벤트는 거절, 중복은 제거
+fn check_events(events: &[String]) -> Result<Vec<String>, WebhookError> {
+    let mut checked: Vec<String> = Vec::with_capacity(events.len());
+    for event in events {
+        if !WEBHOOK_EVENTS.contains(&event.as_str()) {
+            return Err(WebhookError::ValidationError {
+                field: "events".to_string(),
+            });
+        }
+        if !checked.contains(event) {
+            checked.push(event.clone());
+        }
+    }
+    if checked.is_empty() {
+        return Err(WebhookError::ValidationError {
+            field: "events".to_string(),
+        });
+    }
+    Ok(checked)
+}
+
+// 개인 웹훅은 만든 사용자만, 워크스페이스 웹훅은 admin 이상이 관리
+async fn load_webhook(
+    webhook_id: &str,
+    user_id: &str,
+    pool: &Data<PgPool>,
+) -> Result<Webhook, WebhookError> {
+    let webhook = match Webhook::get_webhook_by_id(webhook_id, pool).await {
+        Ok(webhook) => webhook,
+        Err(DieselError::NotFound) => return Err(WebhookError::NotFound),
+        Err(err) => return Err(internal_error(err)),
+    };
+
+    match &webhook.workspace_id {
+        Some(workspace_id) => {
+            authorize_workspace(workspace_id, user_id, WorkspaceRole::Admin, pool).await?;
+        }
+        None if webhook.user_id != user_id => return Err(WebhookError::NotFound),
+        None => {}
+    }
+
+    Ok(webhook)
+}
+
+// 다른 웹훅의 전송은 없는 것으로 취급
+async fn load_delivery(
+    webhook: &Webhook,
+    delivery_id: &str,
+    pool: &Data<PgPool>,
+) -> Result<WebhookDelivery, WebhookError> {
+    match WebhookDelivery::get_delivery_by_id(delivery_id, pool).await {
+        Ok(delivery) if delivery.webhook_id == webhook.id => Ok(delivery),
+        Ok(_) | Err(DieselError::NotFound) => Err(WebhookError::NotFound),
+        Err(err) => Err(internal_error(err)),
+    }
+}
+
+async fn authorize_selector(
+    workspace: &WorkspaceSelector,
+    user_id: &str,
+    pool: &Data<PgPool>,
+) -> Result<(), WebhookError> {
+    if let Some(workspace_id) = &workspace.0 {
+        authorize_workspace(workspace_id, user_id, WorkspaceRole::Admin, pool).await?;
+    }
+    Ok(())
+}
+
+// GET /api/webhooks: 내 개인 웹훅 (X-Workspace-Id가 있으면 그 워크스페이스 웹훅)
+pub async fn get_webhooks(
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WebhookError> {
+    authorize_selector(&workspace, &auth_guard.user_id, &pool).await?;
+
+    let webhooks = Webhook::get_webhooks(&auth_guard.user_id, workspace.0.as_deref(), &pool)
+        .await
+        .map_err(internal_error)?;
+    json_response(&webhooks)
+}
+
+// POST /api/webhooks: 서명 키는 이 응답에서만 알려줌
+pub async fn create_webhook(
+    auth_guard: AuthenticationGuard,
+    workspace: WorkspaceSelector,
+    _body: Json<WebhookRequest>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WebhookError> {
+    authorize_selector(&workspace, &auth_guard.user_id, &pool).await?;
+
+    let request = _body.into_inner();
+    let url = check_url(&request.url).await?;
+    let events = check_events(&request.events)?;
+    let secret = match request.secret {
+        Some(secret) if (MIN_SECRET_LENGTH..=255).contains(&secret.len()) => secret,
+        Some(_) => {
+            return Err(WebhookError::ValidationError {
+                field: "secret".to_string(),
+            })
+        }
+        None => generate_secret(),
+    };
+
+    let webhook_data = WebhookData {
+        id: Uuid::new_v4().to_string(),
+        user_id: auth_guard.user_id.clone(),
+        workspace_id: workspace.0,
+        url,
+        secret: secret.clone(),
+        events,
+    };
+    let webhook = WebhookData::create_webhook(webhook_data, &pool)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(HttpResponse::Created().json(CreatedWebhook { webhook, secret }))
+}
+
+pub async fn get_webhook_by_id(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WebhookError> {
+    let webhook = load_webhook(path_param(&req, "id")?, &auth_guard.user_id, &pool).await?;
+    json_response(&webhook)
+}
+
+// PUT /api/webhooks/{id}: url, events, active 중 보낸 것만 변경
+pub async fn update_webhook(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: Json<WebhookUpdate>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WebhookError> {
+    let webhook = load_webhook(path_param(&req, "id")?, &auth_guard.user_id, &pool).await?;
+
+    let update = _body.into_inner();
+    let changes = WebhookChanges {
+        url: match update.url.as_deref() {
+            Some(url) => Some(check_url(url).await?),
+            None => None,
+        },
+        events: update.events.as_deref().map(check_events).transpose()?,
+        active: update.active,
+        updated_at: None,
+    };
+
+    let webhook = Webhook::update_webhook(&webhook.id, changes, &pool)
+        .await
+        .map_err(internal_error)?;
+    json_response(&webhook)
+}
+
+pub async fn delete_webhook(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WebhookError> {
+    let webhook = load_webhook(path_param(&req, "id")?, &auth_guard.user_id, &pool).await?;
+
+    match Webhook::delete_webhook(&webhook.id, &pool).await {
+        Ok(0) => Err(WebhookError::NotFound),
+        Ok(_) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("deleted webhook")),
+        Err(err) => Err(internal_error(err)),
+    }
+}
+
+// GET /api/webhooks/{id}/deliveries?status=&limit=: 최근 전송 기록
+pub async fn get_deliveries(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    query: Query<DeliveryListQuery>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WebhookError> {
+    let webhook = load_webhook(path_param(&req, "id")?, &auth_guard.user_id, &pool).await?;
+
+    if let Some(status) = &query.status {
+        let known = [
+            DeliveryStatus::Pending,
+            DeliveryStatus::Succeeded,
+            DeliveryStatus::Failed,
+        ]
+        .iter()
+        .any(|known| known.as_str() == status);
+        if !known {
+            return Err(WebhookError::ValidationError {
+                field: "status".to_string(),
+            });
+        }
+    }
+
+    let deliveries = WebhookDelivery::get_deliveries(&webhook.id, &query, &pool)
+        .await
+        .map_err(internal_error)?;
+    json_response(&deliveries)
+}
+
+pub async fn get_delivery_by_id(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WebhookError> {
+    let webhook = load_webhook(path_param(&req, "id")?, &auth_guard.user_id, &pool).await?;
+    let delivery = load_delivery(&webhook, path_param(&req, "delivery_id")?, &pool).await?;
+    json_response(&delivery)
+}
+
+// POST /api/webhooks/{id}/deliveries/{delivery_id}/redeliver: 같은 본문으로 새 전송을 만들어 바로 보냄
+pub async fn redeliver(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WebhookError> {
+    let webhook = load_webhook(path_param(&req, "id")?, &auth_guard.user_id, &pool).await?;
+    let delivery = load_delivery(&webhook, path_param(&req, "delivery_id")?, &pool).await?;
+
+    let delivery_data = WebhookDeliveryData {
+        id: Uuid::new_v4().to_string(),
+        webhook_id: webhook.id,
+        event: delivery.event,
+        payload: delivery.payload,
+        next_attempt_at: Some(Utc::now().naive_utc()),
+        redelivery_of: Some(delivery.id),
+    };
+    let delivery = WebhookDeliveryData::create_delivery(delivery_data, &pool)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(HttpResponse::Created().json(delivery))
+}
@@ -0,0 +1,7 @@
+pub mod dispatch;
+pub mod error;
+pub mod handler;
+pub mod model;
+pub mod route;
+pub mod target;
+pub mod worker;
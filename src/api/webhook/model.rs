@@ -0,0 +1,357 @@
+use crate::database::PgPool;
+use crate::schema::webhook_deliveries;
+use crate::schema::webhooks::dsl::*;
+use actix_web::web::Data;
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::{result::Error, AsChangeset, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+// 등록할 수 있는 이벤트
+pub const WEBHOOK_EVENTS: [&str; 4] = [
+    "post.created",
+    "post.updated",
+    "post.deleted",
+    "post.published",
+];
+
+// 전송 기록 목록 기본/최대 개수
+pub const DEFAULT_DELIVERY_LIMIT: i64 = 20;
+pub const MAX_DELIVERY_LIMIT: i64 = 100;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::webhooks)]
+pub struct Webhook {
+    pub id: String,
+    pub user_id: String,
+    pub workspace_id: Option<String>,
+    pub url: String,
+    // 만들 때만 응답에 포함
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub events: Vec<String>,
+    pub active: bool,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::webhooks)]
+pub struct WebhookData {
+    pub id: String,
+    pub user_id: String,
+    pub workspace_id: Option<String>,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<String>,
+}
+
+// 만든 직후에만 서명 키를 알려줌
+#[derive(Serialize, Debug)]
+pub struct CreatedWebhook {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    pub secret: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WebhookRequest {
+    pub url: String,
+    pub events: Vec<String>,
+    // 비워두면 서버에서 만듦
+    pub secret: Option<String>,
+}
+
+// 보낸 필드만 변경
+#[derive(Deserialize, Debug)]
+pub struct WebhookUpdate {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, AsChangeset)]
+#[diesel(table_name = crate::schema::webhooks)]
+pub struct WebhookChanges {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub active: Option<bool>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Succeeded => "succeeded",
+            DeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::webhook_deliveries)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub webhook_id: String,
+    pub event: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: Option<NaiveDateTime>,
+    pub last_attempt_at: Option<NaiveDateTime>,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub redelivery_of: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::webhook_deliveries)]
+pub struct WebhookDeliveryData {
+    pub id: String,
+    pub webhook_id: String,
+    pub event: String,
+    pub payload: String,
+    pub next_attempt_at: Option<NaiveDateTime>,
+    pub redelivery_of: Option<String>,
+}
+
+// 한 번 보낸 결과
+#[derive(Debug)]
+pub struct AttemptResult {
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeliveryListQuery {
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl Webhook {
+    // 워크스페이스를 선택하면 그 워크스페이스 웹훅, 아니면 내 개인 웹훅
+    pub async fn get_webhooks(
+        webhook_user_id: &str,
+        workspace: Option<&str>,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Webhook>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        match workspace {
+            Some(workspace) => webhooks
+                .filter(workspace_id.eq(workspace))
+                .order(created_at.asc())
+                .load::<Webhook>(conn),
+            None => webhooks
+                .filter(user_id.eq(webhook_user_id))
+                .filter(workspace_id.is_null())
+                .order(created_at.asc())
+                .load::<Webhook>(conn),
+        }
+    }
+
+    pub async fn get_webhook_by_id(
+        webhook_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Webhook, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        webhooks.find(webhook_id).get_result::<Webhook>(conn)
+    }
+
+    // 노트 이벤트를 받을 웹훅: 워크스페이스 노트면 그 워크스페이스, 개인 노트면 작성자의 웹훅
+    // 작성자가 노트 권한을 잃었으면 (recipients에 없으면) 보내지 않음
+    pub async fn get_subscribed_webhooks(
+        post_workspace_id: Option<&str>,
+        post_author_id: Option<&str>,
+        recipients: &[String],
+        webhook_event: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<Webhook>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        let query = webhooks
+            .filter(active.eq(true))
+            .filter(events.contains(vec![webhook_event.to_string()]))
+            .into_boxed();
+        match (post_workspace_id, post_author_id) {
+            (Some(post_workspace_id), _) => query
+                .filter(workspace_id.eq(post_workspace_id))
+                .load::<Webhook>(conn),
+            (None, Some(post_author_id)) if recipients.iter().any(|r| r == post_author_id) => query
+                .filter(workspace_id.is_null())
+                .filter(user_id.eq(post_author_id))
+                .load::<Webhook>(conn),
+            (None, _) => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn update_webhook(
+        webhook_id: &str,
+        changes: WebhookChanges,
+        pool: &Data<PgPool>,
+    ) -> Result<Webhook, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::update(webhooks.find(webhook_id))
+            .set(WebhookChanges {
+                updated_at: Some(Utc::now().naive_utc()),
+                ..changes
+            })
+            .get_result::<Webhook>(conn)
+    }
+
+    pub async fn delete_webhook(webhook_id: &str, pool: &Data<PgPool>) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::delete(webhooks.find(webhook_id)).execute(conn)
+    }
+}
+
+impl WebhookData {
+    pub async fn create_webhook(
+        webhook_data: WebhookData,
+        pool: &Data<PgPool>,
+    ) -> Result<Webhook, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::insert_into(webhooks)
+            .values(webhook_data)
+            .get_result::<Webhook>(conn)
+    }
+}
+
+impl WebhookDelivery {
+    // 최근 전송부터
+    pub async fn get_deliveries(
+        delivery_webhook_id: &str,
+        query: &DeliveryListQuery,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<WebhookDelivery>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        let mut deliveries = webhook_deliveries::table
+            .filter(webhook_deliveries::webhook_id.eq(delivery_webhook_id))
+            .into_boxed();
+        if let Some(delivery_status) = &query.status {
+            deliveries = deliveries.filter(webhook_deliveries::status.eq(delivery_status));
+        }
+
+        deliveries
+            .order(webhook_deliveries::created_at.desc())
+            .limit(
+                query
+                    .limit
+                    .unwrap_or(DEFAULT_DELIVERY_LIMIT)
+                    .clamp(1, MAX_DELIVERY_LIMIT),
+            )
+            .load::<WebhookDelivery>(conn)
+    }
+
+    pub async fn get_delivery_by_id(
+        delivery_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<WebhookDelivery, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        webhook_deliveries::table
+            .find(delivery_id)
+            .get_result::<WebhookDelivery>(conn)
+    }
+
+    // 보낼 시각이 된 전송과 그 웹훅 (꺼둔 웹훅의 전송은 다시 켜면 보냄)
+    pub async fn get_due_deliveries(
+        now: NaiveDateTime,
+        limit: i64,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<(WebhookDelivery, Webhook)>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        webhook_deliveries::table
+            .inner_join(webhooks)
+            .filter(active.eq(true))
+            .filter(webhook_deliveries::status.eq(DeliveryStatus::Pending.as_str()))
+            .filter(webhook_deliveries::next_attempt_at.le(now))
+            .order(webhook_deliveries::next_attempt_at.asc())
+            .limit(limit)
+            .select((
+                webhook_deliveries::all_columns,
+                crate::schema::webhooks::all_columns,
+            ))
+            .load::<(WebhookDelivery, Webhook)>(conn)
+    }
+
+    // 보내는 동안 다른 워커가 가져가지 않도록 다음 시도 시각을 미뤄둠
+    pub async fn claim_delivery(
+        delivery: &WebhookDelivery,
+        lease_until: NaiveDateTime,
+        pool: &Data<PgPool>,
+    ) -> Result<bool, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::update(
+            webhook_deliveries::table
+                .find(&delivery.id)
+                .filter(webhook_deliveries::status.eq(DeliveryStatus::Pending.as_str()))
+                .filter(webhook_deliveries::next_attempt_at.eq(delivery.next_attempt_at)),
+        )
+        .set(webhook_deliveries::next_attempt_at.eq(lease_until))
+        .execute(conn)
+        .map(|updated| updated > 0)
+    }
+
+    // 시도 결과 기록, next_attempt_at이 없으면 더 보내지 않음
+    pub async fn record_attempt(
+        delivery_id: &str,
+        delivery_status: DeliveryStatus,
+        next: Option<NaiveDateTime>,
+        result: AttemptResult,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::update(webhook_deliveries::table.find(delivery_id))
+            .set((
+                webhook_deliveries::status.eq(delivery_status.as_str()),
+                webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                webhook_deliveries::next_attempt_at.eq(next),
+                webhook_deliveries::last_attempt_at.eq(Utc::now().naive_utc()),
+                webhook_deliveries::response_status.eq(result.response_status),
+                webhook_deliveries::response_body.eq(result.response_body),
+                webhook_deliveries::error.eq(result.error),
+            ))
+            .execute(conn)
+    }
+}
+
+impl WebhookDeliveryData {
+    pub async fn create_deliveries(
+        delivery_data: Vec<WebhookDeliveryData>,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::insert_into(webhook_deliveries::table)
+            .values(delivery_data)
+            .execute(conn)
+    }
+
+    pub async fn create_delivery(
+        delivery_data: WebhookDeliveryData,
+        pool: &Data<PgPool>,
+    ) -> Result<WebhookDelivery, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        diesel::insert_into(webhook_deliveries::table)
+            .values(delivery_data)
+            .get_result::<WebhookDelivery>(conn)
+    }
+}
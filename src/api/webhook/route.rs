@@ -0,0 +1,29 @@
+use crate::api::webhook::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(handler::get_webhooks))
+            .route(web::post().to(handler::create_webhook)),
+    );
+
+    cfg.service(
+        web::resource("/{id}")
+            .route(web::get().to(handler::get_webhook_by_id))
+            .route(web::put().to(handler::update_webhook))
+            .route(web::delete().to(handler::delete_webhook)),
+    );
+
+    cfg.service(web::resource("/{id}/deliveries").route(web::get().to(handler::get_deliveries)));
+
+    cfg.service(
+        web::resource("/{id}/deliveries/{delivery_id}")
+            .route(web::get().to(handler::get_delivery_by_id)),
+    );
+
+    cfg.service(
+        web::resource("/{id}/deliveries/{delivery_id}/redeliver")
+            .route(web::post().to(handler::redeliver)),
+    );
+}
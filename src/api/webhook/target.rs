@@ -0,0 +1,110 @@
+use std::env;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use reqwest::Url;
+
+// 내부망 주소로 보내도 되는지 (로컬 개발, 사내 배포)
+fn allow_private() -> bool {
+    env::var("WEBHOOK_ALLOW_PRIVATE")
+        .map(|value| matches!(value.as_str(), "1" | "true"))
+        .unwrap_or(false)
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // 0.0.0.0/8
+        || a == 0
+        // 100.64.0.0/10 (CGNAT)
+        || (a == 100 && (64..128).contains(&b))
+        // 198.18.0.0/15 (벤치마크)
+        || (a == 198 && (18..20).contains(&b))
+        // 240.0.0.0/4 (예약)
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    // ::ffff:127.0.0.1 처럼 IPv4를 담은 주소는 IPv4 규칙으로
+    if let Some(ipv4) = ip.to_ipv4_mapped() {
+        return is_public_ipv4(ipv4);
+    }
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // fc00::/7 (unique local)
+        || (first & 0xfe00) == 0xfc00
+        // fe80::/10 (link-local)
+        || (first & 0xffc0) == 0xfe80
+        // 2001:db8::/32 (문서용)
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+pub fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+// 웹훅 URL의 호스트를 조회해 보낼 주소를 돌려줌
+// 내부망(사설, 루프백, 링크 로컬) 주소가 하나라도 있으면 거절 (WEBHOOK_ALLOW_PRIVATE면 허용)
+pub async fn resolve(url: &Url) -> Result<Vec<SocketAddr>, String> {
+    let host = url.host_str().ok_or("URL has no host")?;
+    let port = url
+        .port_or_known_default()
+        .ok_or("URL has no port for its scheme")?;
+    // [::1] 같은 IPv6 주소는 괄호를 벗겨야 조회됨
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|err| format!("Failed to resolve {}: {}", host, err))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("No address found for {}", host));
+    }
+    if !allow_private() && addrs.iter().any(|addr| !is_public(addr.ip())) {
+        return Err(format!("{} resolves to a private address", host));
+    }
+    Ok(addrs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_internal_addresses() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fe80::1",
+            "fd00::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn accepts_public_addresses() {
+        for ip in ["8.8.8.8", "1.1.1.1", "172.32.0.1", "2606:4700::1111"] {
+            assert!(is_public(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::env;
+use std::time::Duration;
+
+use super::model::{AttemptResult, DeliveryStatus, Webhook, WebhookDelivery};
+use super::target;
+use crate::database::PgPool;
+use actix_web::web::Data;
+use chrono::{NaiveDateTime, Utc};
+use hmac::{Hmac, Mac};
+use log::{error, info};
+use reqwest::{header, redirect, Client, Response, Url};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// 보낼 전송 확인 주기 (초)
+const DEFAULT_WEBHOOK_INTERVAL: u64 = 5;
+// 첫 재시도까지 기다리는 시간 (초), 실패할 때마다 두 배
+const DEFAULT_RETRY_BASE: i64 = 30;
+const MAX_RETRY_DELAY: i64 = 60 * 60;
+// 이만큼 실패하면 더 보내지 않음 (다시 보내기로만 재전송)
+const MAX_ATTEMPTS: i32 = 8;
+const DELIVERY_BATCH: i64 = 50;
+const REQUEST_TIMEOUT: u64 = 10;
+// 기록에 남기는 응답 본문 길이
+const RESPONSE_BODY_LIMIT: usize = 1024;
+
+// 보낼 시각은 DB에 있으므로 재시작하면 밀린 전송부터 바로 처리
+pub fn spawn_worker(pool: Data<PgPool>) {
+    let interval = env::var("WEBHOOK_INTERVAL")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_WEBHOOK_INTERVAL);
+    let retry_base = env::var("WEBHOOK_RETRY_BASE")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_RETRY_BASE);
+
+    actix_web::rt::spawn(async move {
+        loop {
+            run_due(&pool, retry_base).await;
+            actix_web::rt::time::sleep(Duration::from_secs(interval)).await;
+        }
+    });
+}
+
+// 서명: HMAC-SHA256(secret, "{timestamp}.{payload}")의 hex
+pub fn sign(secret: &str, timestamp: i64, payload: &str) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(format!("{}.{}", timestamp, payload).as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+// attempts번 실패한 뒤 다음 시도 시각, 더 보내지 않으면 None
+fn next_attempt(attempts: i32, retry_base: i64) -> Option<NaiveDateTime> {
+    if attempts >= MAX_ATTEMPTS {
+        return None;
+    }
+    let delay = retry_base
+        .saturating_mul(1 << (attempts - 1).clamp(0, 20))
+        .min(MAX_RETRY_DELAY);
+    Some(Utc::now().naive_utc() + chrono::Duration::seconds(delay))
+}
+
+// 조회해서 확인한 주소로만 접속하도록 고정한 클라이언트
+// 확인한 뒤 DNS 응답이 내부망 주소로 바뀌어도 그쪽으로 보내지 않음
+async fn pinned_client(url: &str) -> Result<Client, String> {
+    let url = Url::parse(url).map_err(|err| err.to_string())?;
+    let addrs = target::resolve(&url).await?;
+
+    let mut builder = Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT))
+        // 등록한 URL로만 보냄
+        .redirect(redirect::Policy::none());
+    if let Some(domain) = url.domain() {
+        builder = builder.resolve_to_addrs(domain, &addrs);
+    }
+    builder.build().map_err(|err| err.to_string())
+}
+
+// 응답 본문은 기록할 만큼만 읽음
+async fn read_body(mut response: Response) -> String {
+    let mut body = Vec::new();
+    while body.len() < RESPONSE_BODY_LIMIT * 4 {
+        match response.chunk().await {
+            Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+            _ => break,
+        }
+    }
+    String::from_utf8_lossy(&body)
+        .chars()
+        .take(RESPONSE_BODY_LIMIT)
+        .collect()
+}
+
+async fn run_due(pool: &Data<PgPool>, retry_base: i64) {
+    let due = WebhookDelivery::get_due_deliveries(Utc::now().naive_utc(), DELIVERY_BATCH, pool)
+        .await
+        .unwrap_or_else(|err| {
+            error!("Error get due webhook deliveries: {:?}", err);
+            Vec::new()
+        });
+
+    let mut delivered = 0;
+    let mut failed = 0;
+    for (delivery, webhook) in &due {
+        match deliver(delivery, webhook, pool, retry_base).await {
+            Some(true) => delivered += 1,
+            Some(false) => failed += 1,
+            None => {}
+        }
+    }
+    if delivered > 0 || failed > 0 {
+        info!("Webhooks: {} delivered, {} failed", delivered, failed);
+    }
+}
+
+// 보냈으면 성공 여부, 다른 곳에서 먼저 가져갔으면 None
+async fn deliver(
+    delivery: &WebhookDelivery,
+    webhook: &Webhook,
+    pool: &Data<PgPool>,
+    retry_base: i64,
+) -> Option<bool> {
+    // 응답을 기다리는 동안 다시 가져가지 않도록
+    let lease_until =
+        Utc::now().naive_utc() + chrono::Duration::seconds(REQUEST_TIMEOUT as i64 * 2);
+    match WebhookDelivery::claim_delivery(delivery, lease_until, pool).await {
+        Ok(true) => {}
+        Ok(false) => return None,
+        Err(err) => {
+            error!("Error claiming webhook delivery: {:?}", err);
+            return None;
+        }
+    }
+
+    let timestamp = Utc::now().timestamp();
+    // 내부망 주소로 바뀐 URL은 보내지 않고 실패로 기록
+    let response = match pinned_client(&webhook.url).await {
+        Ok(client) => client
+            .post(&webhook.url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::USER_AGENT, "all-note-webhook")
+            .header("X-AllNote-Event", &delivery.event)
+            .header("X-AllNote-Delivery", &delivery.id)
+            .header("X-AllNote-Timestamp", timestamp.to_string())
+            .header(
+                "X-AllNote-Signature",
+                format!(
+                    "sha256={}",
+                    sign(&webhook.secret, timestamp, &delivery.payload)
+                ),
+            )
+            .body(delivery.payload.clone())
+            .send()
+            .await
+            .map_err(|err| err.to_string()),
+        Err(err) => Err(err),
+    };
+
+    let (succeeded, result) = match response {
+        Ok(response) => {
+            let status = response.status();
+            let body = read_body(response).await;
+            (
+                status.is_success(),
+                AttemptResult {
+                    response_status: Some(status.as_u16() as i32),
+                    response_body: Some(body),
+                    error: None,
+                },
+            )
+        }
+        Err(err) => (
+            false,
+            AttemptResult {
+                response_status: None,
+                response_body: None,
+                error: Some(err),
+            },
+        ),
+    };
+
+    let (status, next) = if succeeded {
+        (DeliveryStatus::Succeeded, None)
+    } else {
+        match next_attempt(delivery.attempts + 1, retry_base) {
+            Some(next) => (DeliveryStatus::Pending, Some(next)),
+            None => (DeliveryStatus::Failed, None),
+        }
+    };
+    if let Err(err) =
+        WebhookDelivery::record_attempt(&delivery.id, status, next, result, pool).await
+    {
+        error!("Error recording webhook delivery attempt: {:?}", err);
+    }
+
+    Some(succeeded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_timestamp_and_payload() {
+        // echo -n '1700000000.{"a":1}' | openssl dgst -sha256 -hmac secret
+        assert_eq!(
+            sign("secret", 1_700_000_000, r#"{"a":1}"#),
+            "49f24e537407743fa4a0242bb63b94b9a47ee99cbbe071ccd8a22550ae411686"
+        );
+        assert_ne!(
+            sign("secret", 1_700_000_001, r#"{"a":1}"#),
+            sign("secret", 1_700_000_000, r#"{"a":1}"#)
+        );
+    }
+
+    #[test]
+    fn backs_off_exponentially_up_to_the_cap() {
+        let delay = |attempts, retry_base| {
+            next_attempt(attempts, retry_base)
+                .map(|next| (next - Utc::now().naive_utc()).num_seconds())
+        };
+
+        // 실행 중 흐른 시간만큼 1초 줄어들 수 있음
+        assert!((29..=30).contains(&delay(1, 30).unwrap()));
+        assert!((59..=60).contains(&delay(2, 30).unwrap()));
+        assert!((1919..=1920).contains(&delay(7, 30).unwrap()));
+        assert!((MAX_RETRY_DELAY - 1..=MAX_RETRY_DELAY).contains(&delay(7, 120).unwrap()));
+        assert_eq!(delay(MAX_ATTEMPTS, 30), None);
+    }
+}
@@ -0,0 +1,78 @@
+use actix_web::{
+    error::ResponseError,
+    http::{header::ContentType, StatusCode},
+    HttpResponse,
+};
+use derive_more::{Display, Error};
+use serde::Serialize;
+
+use crate::api::permission::authorize::AuthorizeError;
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    code: u16,
+    message: String,
+}
+
+#[derive(Debug, Display, Error)]
+pub enum WorkspaceError {
+    #[display(fmt = "internal error")]
+    InternalError,
+
+    #[display(fmt = "bad request")]
+    BadClientData,
+
+    #[display(fmt = "not found")]
+    NotFound,
+
+    #[display(fmt = "forbidden")]
+    Forbidden,
+
+    #[display(fmt = "invitation expired")]
+    InvitationExpired,
+
+    #[display(fmt = "invitation already accepted")]
+    InvitationAccepted,
+
+    #[display(fmt = "a workspace must keep at least one owner")]
+    LastOwner,
+
+    #[display(fmt = "Validation error on field: {}", field)]
+    ValidationError { field: String },
+}
+
+impl ResponseError for WorkspaceError {
+    fn error_response(&self) -> HttpResponse {
+        let error_response = ErrorResponse {
+            code: self.status_code().as_u16(),
+            message: self.to_string(),
+        };
+
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .json(error_response)
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match *self {
+            WorkspaceError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            WorkspaceError::BadClientData => StatusCode::BAD_REQUEST,
+            WorkspaceError::NotFound => StatusCode::NOT_FOUND,
+            WorkspaceError::Forbidden => StatusCode::FORBIDDEN,
+            WorkspaceError::InvitationExpired => StatusCode::GONE,
+            WorkspaceError::InvitationAccepted => StatusCode::CONFLICT,
+            WorkspaceError::LastOwner => StatusCode::CONFLICT,
+            WorkspaceError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<AuthorizeError> for WorkspaceError {
+    fn from(err: AuthorizeError) -> Self {
+        match err {
+            AuthorizeError::NotFound => WorkspaceError::NotFound,
+            AuthorizeError::Forbidden => WorkspaceError::Forbidden,
+            AuthorizeError::InternalError => WorkspaceError::InternalError,
+        }
+    }
+}
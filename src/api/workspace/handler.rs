@@ -0,0 +1,582 @@
+use std::env;
+
+use super::error::WorkspaceError;
+use super::model::{
+    InviteMember, UpdateMember, Workspace, WorkspaceData, WorkspaceInvitation,
+    WorkspaceInvitationData, WorkspaceMember, WorkspaceRequest, WorkspaceRole,
+};
+use crate::api::auth::authenticate_token::AuthenticationGuard;
+use crate::api::event::bus::EventBus;
+use crate::api::event::model::{PostEvent, PostEventKind};
+use crate::api::permission::authorize::authorize_workspace;
+use crate::api::post::model::Post;
+use crate::api::sync::model::PostTombstone;
+use crate::api::user::model::User;
+use crate::database::PgPool;
+use crate::mailer::Mailer;
+use actix_web::{
+    http::header::{ContentType, LOCATION},
+    web::{self, Data},
+    HttpRequest, HttpResponse, Result,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use log::error;
+use rand::{rngs::OsRng, RngCore};
+use serde_json::to_vec;
+use uuid::Uuid;
+
+// 초대 링크 유효기간 (일)
+const INVITATION_EXPIRES_DAYS: i64 = 7;
+
+fn path_param<'a>(req: &'a HttpRequest, name: &str) -> Result<&'a str, WorkspaceError> {
+    req.match_info().get(name).ok_or_else(|| {
+        error!("Missing path parameter: {}", name);
+        WorkspaceError::BadClientData
+    })
+}
+
+fn validate_name(name: &str) -> Result<String, WorkspaceError> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > 255 {
+        return Err(WorkspaceError::ValidationError {
+            field: "name".to_string(),
+        });
+    }
+
+    Ok(name.to_string())
+}
+
+fn json_response<T: serde::Serialize>(data: &T) -> Result<HttpResponse, WorkspaceError> {
+    let json_bytes = to_vec(data).map_err(|err| {
+        error!("Failed to serialize workspace data to JSON: {:?}", err);
+        WorkspaceError::InternalError
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(json_bytes))
+}
+
+// owner 권한은 owner만 부여할 수 있음
+fn check_grantable(granter: WorkspaceRole, role: WorkspaceRole) -> Result<(), WorkspaceError> {
+    if role == WorkspaceRole::Owner && granter != WorkspaceRole::Owner {
+        return Err(WorkspaceError::Forbidden);
+    }
+
+    Ok(())
+}
+
+async fn get_member_role(
+    workspace_id: &str,
+    user_id: &str,
+    pool: &Data<PgPool>,
+) -> Result<Option<WorkspaceRole>, WorkspaceError> {
+    WorkspaceMember::get_member(workspace_id, user_id, pool)
+        .await
+        .map(|member| member.and_then(|member| member.role()))
+        .map_err(|err| {
+            error!("Error get workspace member data: {:?}", err);
+            WorkspaceError::InternalError
+        })
+}
+
+pub async fn get_workspaces(
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WorkspaceError> {
+    match Workspace::get_workspaces(&auth_guard.user_id, &pool).await {
+        Ok(workspaces) => json_response(&workspaces),
+        Err(err) => {
+            error!("Error get workspaces data: {:?}", err);
+            Err(WorkspaceError::InternalError)
+        }
+    }
+}
+
+pub async fn create_workspace(
+    auth_guard: AuthenticationGuard,
+    _body: web::Json<WorkspaceRequest>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WorkspaceError> {
+    let workspace_data = WorkspaceData {
+        id: Uuid::new_v4().to_string(),
+        name: validate_name(&_body.name)?,
+        created_by: auth_guard.user_id,
+    };
+
+    match WorkspaceData::create_workspace(workspace_data, &pool).await {
+        Ok(workspace) => Ok(HttpResponse::Created().json(workspace)),
+        Err(err) => {
+            error!("Error created new workspace data: {:?}", err);
+            Err(WorkspaceError::InternalError)
+        }
+    }
+}
+
+pub async fn get_workspace_by_id(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WorkspaceError> {
+    let workspace_id = path_param(&req, "id")?;
+    authorize_workspace(
+        workspace_id,
+        &auth_guard.user_id,
+        WorkspaceRole::Viewer,
+        &pool,
+    )
+    .await?;
+
+    match Workspace::get_workspace_by_id(workspace_id, &pool).await {
+        Ok(workspace) => json_response(&workspace),
+        Err(err) => {
+            error!("Error get workspace by id data: {:?}", err);
+            Err(WorkspaceError::NotFound)
+        }
+    }
+}
+
+pub async fn update_workspace(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: web::Json<WorkspaceRequest>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WorkspaceError> {
+    let workspace_id = path_param(&req, "id")?;
+    let name = validate_name(&_body.name)?;
+    authorize_workspace(
+        workspace_id,
+        &auth_guard.user_id,
+        WorkspaceRole::Admin,
+        &pool,
+    )
+    .await?;
+
+    match WorkspaceData::update_workspace(workspace_id, &name, &pool).await {
+        Ok(0) => {
+            error!("Update failed");
+            Err(WorkspaceError::NotFound)
+        }
+        Err(err) => {
+            error!("Error updated workspace data: {:?}", err);
+            Err(WorkspaceError::InternalError)
+        }
+        Ok(_) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("updated workspace")),
+    }
+}
+
+pub async fn delete_workspace(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+    events: Data<EventBus>,
+) -> Result<HttpResponse, WorkspaceError> {
+    let workspace_id = path_param(&req, "id")?;
+    authorize_workspace(
+        workspace_id,
+        &auth_guard.user_id,
+        WorkspaceRole::Owner,
+        &pool,
+    )
+    .await?;
+
+    // 워크스페이스와 함께 지워지는 노트도 하나씩 삭제해 삭제 기록, 이벤트, 웹훅을 남김
+    let posts = Post::get_posts_load_by_workspace(workspace_id, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get workspace posts data: {:?}", err);
+            WorkspaceError::InternalError
+        })?;
+    for post in posts {
+        let recipients = PostEvent::get_recipients(&post.id, &pool)
+            .await
+            .map_err(|err| {
+                error!("Error get post event recipients: {:?}", err);
+                WorkspaceError::InternalError
+            })?;
+        match PostTombstone::delete_post(&post.id, None, &recipients, &pool).await {
+            Ok(0) => {}
+            Ok(_) => {
+                events
+                    .publish_to(&post, PostEventKind::Deleted, recipients, &pool)
+                    .await
+            }
+            Err(err) => {
+                error!("Error deleted post data: {:?}", err);
+                return Err(WorkspaceError::InternalError);
+            }
+        }
+    }
+
+    match Workspace::delete_workspace_by_id(workspace_id, &pool).await {
+        Ok(0) => {
+            error!("Delete failed");
+            Err(WorkspaceError::NotFound)
+        }
+        Err(err) => {
+            error!("Error deleted workspace data: {:?}", err);
+            Err(WorkspaceError::InternalError)
+        }
+        Ok(_) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("deleted workspace")),
+    }
+}
+
+pub async fn get_members(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WorkspaceError> {
+    let workspace_id = path_param(&req, "id")?;
+    authorize_workspace(
+        workspace_id,
+        &auth_guard.user_id,
+        WorkspaceRole::Viewer,
+        &pool,
+    )
+    .await?;
+
+    match WorkspaceMember::get_members(workspace_id, &pool).await {
+        Ok(members) => json_response(&members),
+        Err(err) => {
+            error!("Error get workspace members data: {:?}", err);
+            Err(WorkspaceError::InternalError)
+        }
+    }
+}
+
+pub async fn update_member(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: web::Json<UpdateMember>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WorkspaceError> {
+    let workspace_id = path_param(&req, "id")?;
+    let user_id = path_param(&req, "user_id")?;
+    let role = _body.into_inner().role;
+
+    let granter = authorize_workspace(
+        workspace_id,
+        &auth_guard.user_id,
+        WorkspaceRole::Admin,
+        &pool,
+    )
+    .await?;
+    check_grantable(granter, role)?;
+
+    let current = get_member_role(workspace_id, user_id, &pool).await?;
+
+    // admin은 owner의 권한을 바꿀 수 없음
+    match current {
+        None => return Err(WorkspaceError::NotFound),
+        Some(WorkspaceRole::Owner) if granter != WorkspaceRole::Owner => {
+            return Err(WorkspaceError::Forbidden)
+        }
+        Some(_) => {}
+    }
+
+    match WorkspaceMember::update_role(workspace_id, user_id, role, &pool).await {
+        Ok(None) => Err(WorkspaceError::LastOwner),
+        Ok(Some(0)) => {
+            error!("Update failed");
+            Err(WorkspaceError::NotFound)
+        }
+        Err(err) => {
+            error!("Error updated workspace member data: {:?}", err);
+            Err(WorkspaceError::InternalError)
+        }
+        Ok(Some(_)) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("updated workspace member")),
+    }
+}
+
+pub async fn delete_member(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WorkspaceError> {
+    let workspace_id = path_param(&req, "id")?;
+    let user_id = path_param(&req, "user_id")?;
+
+    // 본인은 스스로 나갈 수 있고, 다른 멤버를 내보내려면 admin 권한 필요
+    let required = if user_id == auth_guard.user_id {
+        WorkspaceRole::Viewer
+    } else {
+        WorkspaceRole::Admin
+    };
+    let remover = authorize_workspace(workspace_id, &auth_guard.user_id, required, &pool).await?;
+
+    let current = get_member_role(workspace_id, user_id, &pool).await?;
+    if current == Some(WorkspaceRole::Owner)
+        && remover != WorkspaceRole::Owner
+        && user_id != auth_guard.user_id
+    {
+        return Err(WorkspaceError::Forbidden);
+    }
+
+    let result = WorkspaceMember::delete_member(workspace_id, user_id, &pool).await;
+    if let Ok(Some(1)) = result {
+        // 협업자로 따로 초대된 노트는 계속 볼 수 있으므로 남기지 않음
+        let lost = Post::get_posts_load_by_workspace(workspace_id, &pool)
+            .await
+            .map(|posts| posts.into_iter().map(|post| post.id).collect::<Vec<_>>());
+        let recorded = match lost {
+            Ok(lost) => PostTombstone::record_lost_access(user_id, &lost, &pool).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = recorded {
+            error!("Error created post tombstone data: {:?}", err);
+        }
+    }
+
+    match result {
+        Ok(None) => Err(WorkspaceError::LastOwner),
+        Ok(Some(0)) => {
+            error!("Delete failed");
+            Err(WorkspaceError::NotFound)
+        }
+        Err(err) => {
+            error!("Error deleted workspace member data: {:?}", err);
+            Err(WorkspaceError::InternalError)
+        }
+        Ok(Some(_)) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("deleted workspace member")),
+    }
+}
+
+pub async fn get_invitations(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WorkspaceError> {
+    let workspace_id = path_param(&req, "id")?;
+    authorize_workspace(
+        workspace_id,
+        &auth_guard.user_id,
+        WorkspaceRole::Admin,
+        &pool,
+    )
+    .await?;
+
+    match WorkspaceInvitation::get_invitations(workspace_id, &pool).await {
+        Ok(invitations) => json_response(&invitations),
+        Err(err) => {
+            error!("Error get workspace invitations data: {:?}", err);
+            Err(WorkspaceError::InternalError)
+        }
+    }
+}
+
+pub async fn invite_member(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    _body: web::Json<InviteMember>,
+    pool: Data<PgPool>,
+    mailer: Data<Mailer>,
+) -> Result<HttpResponse, WorkspaceError> {
+    let workspace_id = path_param(&req, "id")?;
+    let invite = _body.into_inner();
+
+    let granter = authorize_workspace(
+        workspace_id,
+        &auth_guard.user_id,
+        WorkspaceRole::Admin,
+        &pool,
+    )
+    .await?;
+    check_grantable(granter, invite.role)?;
+
+    let email = invite.email.trim().to_lowercase();
+    if !email.contains('@') {
+        return Err(WorkspaceError::ValidationError {
+            field: "email".to_string(),
+        });
+    }
+
+    let workspace = Workspace::get_workspace_by_id(workspace_id, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get workspace by id data: {:?}", err);
+            WorkspaceError::NotFound
+        })?;
+
+    let mut token = [0u8; 32];
+    OsRng.fill_bytes(&mut token);
+
+    let invitation_data = WorkspaceInvitationData {
+        id: Uuid::new_v4().to_string(),
+        workspace_id: workspace_id.to_string(),
+        email: email.clone(),
+        role: invite.role.as_str().to_string(),
+        token: URL_SAFE_NO_PAD.encode(token),
+        invited_by: auth_guard.user_id.clone(),
+        expires_at: (Utc::now() + Duration::days(INVITATION_EXPIRES_DAYS)).naive_utc(),
+    };
+
+    let invitation = WorkspaceInvitationData::create_invitation(invitation_data, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error created new workspace invitation data: {:?}", err);
+            WorkspaceError::InternalError
+        })?;
+
+    let inviter = User::get_users_by_id(&auth_guard.user_id, &pool)
+        .await
+        .map(|user| user.user_name)
+        .unwrap_or_else(|_| "Someone".to_string());
+    let api_origin = env::var("API_ORIGIN").unwrap_or_else(|_| "http://localhost:8080".to_string());
+    let accept_url = format!(
+        "{}/api/workspace-invitations/{}/accept",
+        api_origin, invitation.token
+    );
+
+    let subject = format!("{} invited you to {} on all-note", inviter, workspace.name);
+    let body = format!(
+        "{} invited you to join the workspace \"{}\" as {}.\n\nAccept the invitation:\n{}\n\nThis link expires in {} days.",
+        inviter,
+        workspace.name,
+        invite.role.as_str(),
+        accept_url,
+        INVITATION_EXPIRES_DAYS
+    );
+
+    if let Err(err) = mailer.send(&email, &subject, &body).await {
+        error!("Error sending workspace invitation mail: {:?}", err);
+        if let Err(err) =
+            WorkspaceInvitation::delete_invitation(workspace_id, &invitation.id, &pool).await
+        {
+            error!("Error deleted workspace invitation data: {:?}", err);
+        }
+        return Err(WorkspaceError::InternalError);
+    }
+
+    Ok(HttpResponse::Created().json(invitation))
+}
+
+pub async fn delete_invitation(
+    req: HttpRequest,
+    auth_guard: AuthenticationGuard,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WorkspaceError> {
+    let workspace_id = path_param(&req, "id")?;
+    let invitation_id = path_param(&req, "invitation_id")?;
+    authorize_workspace(
+        workspace_id,
+        &auth_guard.user_id,
+        WorkspaceRole::Admin,
+        &pool,
+    )
+    .await?;
+
+    match WorkspaceInvitation::delete_invitation(workspace_id, invitation_id, &pool).await {
+        Ok(0) => {
+            error!("Delete failed");
+            Err(WorkspaceError::NotFound)
+        }
+        Err(err) => {
+            error!("Error deleted workspace invitation data: {:?}", err);
+            Err(WorkspaceError::InternalError)
+        }
+        Ok(_) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body("deleted workspace invitation")),
+    }
+}
+
+// 메일의 초대 링크: 로그인되어 있으면 수락 후 프론트엔드 워크스페이스 화면으로 이동
+pub async fn accept_invitation(
+    req: HttpRequest,
+    auth_guard: Option<AuthenticationGuard>,
+    pool: Data<PgPool>,
+) -> Result<HttpResponse, WorkspaceError> {
+    let token = path_param(&req, "token")?;
+    let frontend_origin = env::var("CLIENT_ORIGIN").map_err(|err| {
+        error!("CLIENT_ORIGIN must be set: {:?}", err);
+        WorkspaceError::InternalError
+    })?;
+
+    // 로그인하지 않았으면 로그인 후 다시 이 링크로 돌아오도록 프론트엔드로 보냄
+    let auth_guard = match auth_guard {
+        Some(auth_guard) => auth_guard,
+        None => {
+            return Ok(HttpResponse::Found()
+                .append_header((
+                    LOCATION,
+                    format!(
+                        "{}/login?redirect=/api/workspace-invitations/{}/accept",
+                        frontend_origin, token
+                    ),
+                ))
+                .finish());
+        }
+    };
+
+    let invitation = WorkspaceInvitation::get_invitation_by_token(token, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get workspace invitation by token: {:?}", err);
+            WorkspaceError::NotFound
+        })?;
+
+    if invitation.accepted_at.is_some() {
+        return Err(WorkspaceError::InvitationAccepted);
+    }
+    if invitation.expires_at <= Utc::now().naive_utc() {
+        return Err(WorkspaceError::InvitationExpired);
+    }
+
+    // 초대받은 이메일의 계정으로만 수락 가능
+    let user = User::get_users_by_id(&auth_guard.user_id, &pool)
+        .await
+        .map_err(|err| {
+            error!("Error get users by id data: {:?}", err);
+            WorkspaceError::InternalError
+        })?;
+    if user.email.to_lowercase() != invitation.email {
+        return Err(WorkspaceError::Forbidden);
+    }
+
+    let role = invitation
+        .role
+        .parse::<WorkspaceRole>()
+        .map_err(|_| WorkspaceError::InternalError)?;
+
+    let current = get_member_role(&invitation.workspace_id, &user.id, &pool).await?;
+
+    match WorkspaceInvitation::accept_invitation(&invitation, &user.id, role, &pool).await {
+        Ok(_) => {
+            // 새로 가입했으면 워크스페이스 노트를 다음 동기화에서 받도록
+            if current.is_none() {
+                let gained = Post::get_posts_load_by_workspace(&invitation.workspace_id, &pool)
+                    .await
+                    .map(|posts| posts.into_iter().map(|post| post.id).collect::<Vec<_>>());
+                let recorded = match gained {
+                    Ok(gained) => {
+                        PostTombstone::record_gained_access(&user.id, &gained, &pool).await
+                    }
+                    Err(err) => Err(err),
+                };
+                if let Err(err) = recorded {
+                    error!("Error deleted post tombstone data: {:?}", err);
+                }
+            }
+
+            Ok(HttpResponse::Found()
+                .append_header((
+                    LOCATION,
+                    format!("{}/workspaces/{}", frontend_origin, invitation.workspace_id),
+                ))
+                .finish())
+        }
+        Err(err) => {
+            error!("Error accepted workspace invitation data: {:?}", err);
+            Err(WorkspaceError::InternalError)
+        }
+    }
+}
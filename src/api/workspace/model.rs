@@ -0,0 +1,440 @@
+use std::str::FromStr;
+
+use crate::api::permission::model::PostRole;
+use crate::database::PgPool;
+use crate::schema::{posts, users, workspace_invitations, workspace_members, workspaces};
+use actix_web::web::Data;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::{result::Error, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+// 워크스페이스 권한 (선언 순서가 권한 크기: viewer < editor < admin < owner)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkspaceRole {
+    Viewer,
+    Editor,
+    Admin,
+    Owner,
+}
+
+impl WorkspaceRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkspaceRole::Viewer => "viewer",
+            WorkspaceRole::Editor => "editor",
+            WorkspaceRole::Admin => "admin",
+            WorkspaceRole::Owner => "owner",
+        }
+    }
+
+    // 워크스페이스 멤버가 그 워크스페이스의 노트에 갖는 권한
+    pub fn post_role(&self) -> PostRole {
+        match self {
+            WorkspaceRole::Viewer => PostRole::Viewer,
+            WorkspaceRole::Editor => PostRole::Editor,
+            WorkspaceRole::Admin | WorkspaceRole::Owner => PostRole::Owner,
+        }
+    }
+}
+
+impl FromStr for WorkspaceRole {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "viewer" => Ok(WorkspaceRole::Viewer),
+            "editor" => Ok(WorkspaceRole::Editor),
+            "admin" => Ok(WorkspaceRole::Admin),
+            "owner" => Ok(WorkspaceRole::Owner),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::workspaces)]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    pub created_by: String,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::workspaces)]
+pub struct WorkspaceData {
+    pub id: String,
+    pub name: String,
+    pub created_by: String,
+}
+
+// 워크스페이스 생성/이름 변경 요청
+#[derive(Deserialize, Debug)]
+pub struct WorkspaceRequest {
+    pub name: String,
+}
+
+// 내 워크스페이스 목록 응답
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+pub struct WorkspaceSummary {
+    pub id: String,
+    pub name: String,
+    pub role: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::workspace_members)]
+pub struct WorkspaceMember {
+    pub workspace_id: String,
+    pub user_id: String,
+    pub role: String,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::workspace_members)]
+pub struct WorkspaceMemberData {
+    pub workspace_id: String,
+    pub user_id: String,
+    pub role: String,
+}
+
+// 멤버 목록 응답 (권한 + 사용자 정보)
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+pub struct MemberInfo {
+    pub user_id: String,
+    pub email: String,
+    pub user_name: String,
+    pub photo: String,
+    pub role: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable)]
+#[diesel(table_name = crate::schema::workspace_invitations)]
+pub struct WorkspaceInvitation {
+    pub id: String,
+    pub workspace_id: String,
+    pub email: String,
+    pub role: String,
+    #[serde(skip_serializing)]
+    pub token: String,
+    pub invited_by: String,
+    pub expires_at: chrono::NaiveDateTime,
+    pub accepted_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Insertable)]
+#[diesel(table_name = crate::schema::workspace_invitations)]
+pub struct WorkspaceInvitationData {
+    pub id: String,
+    pub workspace_id: String,
+    pub email: String,
+    pub role: String,
+    pub token: String,
+    pub invited_by: String,
+    pub expires_at: chrono::NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct InviteMember {
+    pub email: String,
+    pub role: WorkspaceRole,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdateMember {
+    pub role: WorkspaceRole,
+}
+
+impl Workspace {
+    pub async fn get_workspaces(
+        user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<WorkspaceSummary>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        workspaces::table
+            .inner_join(workspace_members::table)
+            .filter(workspace_members::user_id.eq(user_id))
+            .order(workspaces::name.asc())
+            .select((workspaces::id, workspaces::name, workspace_members::role))
+            .load::<WorkspaceSummary>(conn)
+    }
+
+    pub async fn get_workspace_by_id(
+        workspace_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Workspace, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        workspaces::table
+            .find(workspace_id)
+            .get_result::<Workspace>(conn)
+    }
+
+    pub async fn delete_workspace_by_id(
+        workspace_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::delete(workspaces::table.find(workspace_id)).execute(conn)
+    }
+}
+
+impl WorkspaceData {
+    // 워크스페이스 생성과 동시에 생성자를 owner 멤버로 등록
+    pub async fn create_workspace(
+        workspace_data: WorkspaceData,
+        pool: &Data<PgPool>,
+    ) -> Result<Workspace, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            let workspace = diesel::insert_into(workspaces::table)
+                .values(&workspace_data)
+                .get_result::<Workspace>(conn)?;
+
+            diesel::insert_into(workspace_members::table)
+                .values(WorkspaceMemberData {
+                    workspace_id: workspace.id.clone(),
+                    user_id: workspace.created_by.clone(),
+                    role: WorkspaceRole::Owner.as_str().to_string(),
+                })
+                .execute(conn)?;
+
+            Ok(workspace)
+        })
+    }
+
+    pub async fn update_workspace(
+        workspace_id: &str,
+        name: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::update(workspaces::table.find(workspace_id))
+            .set((
+                workspaces::name.eq(name),
+                workspaces::updated_at.eq(Some(Utc::now().naive_utc())),
+            ))
+            .execute(conn)
+    }
+}
+
+impl WorkspaceMember {
+    pub fn role(&self) -> Option<WorkspaceRole> {
+        self.role.parse::<WorkspaceRole>().ok()
+    }
+
+    pub async fn get_member(
+        workspace_id: &str,
+        user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Option<WorkspaceMember>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        workspace_members::table
+            .find((workspace_id, user_id))
+            .get_result::<WorkspaceMember>(conn)
+            .optional()
+    }
+
+    // 노트가 속한 워크스페이스에서 사용자의 권한
+    pub async fn get_role_by_post(
+        post_id: &str,
+        user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Option<WorkspaceRole>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        posts::table
+            .inner_join(
+                workspace_members::table.on(workspace_members::workspace_id
+                    .nullable()
+                    .eq(posts::workspace_id)),
+            )
+            .filter(posts::id.eq(post_id))
+            .filter(workspace_members::user_id.eq(user_id))
+            .select(workspace_members::role)
+            .get_result::<String>(conn)
+            .optional()
+            .map(|role| role.and_then(|role| role.parse::<WorkspaceRole>().ok()))
+    }
+
+    pub async fn get_members(
+        workspace_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<MemberInfo>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        workspace_members::table
+            .inner_join(users::table)
+            .filter(workspace_members::workspace_id.eq(workspace_id))
+            .order(workspace_members::created_at.asc())
+            .select((
+                users::id,
+                users::email,
+                users::user_name,
+                users::photo,
+                workspace_members::role,
+            ))
+            .load::<MemberInfo>(conn)
+    }
+
+    // 마지막 owner의 권한을 낮추면 None
+    pub async fn update_role(
+        workspace_id: &str,
+        user_id: &str,
+        role: WorkspaceRole,
+        pool: &Data<PgPool>,
+    ) -> Result<Option<usize>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            if role != WorkspaceRole::Owner && !keeps_other_owner(conn, workspace_id, user_id)? {
+                return Ok(None);
+            }
+
+            diesel::update(workspace_members::table.find((workspace_id, user_id)))
+                .set((
+                    workspace_members::role.eq(role.as_str()),
+                    workspace_members::updated_at.eq(Some(Utc::now().naive_utc())),
+                ))
+                .execute(conn)
+                .map(Some)
+        })
+    }
+
+    // 마지막 owner를 내보내면 None
+    pub async fn delete_member(
+        workspace_id: &str,
+        user_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Option<usize>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            if !keeps_other_owner(conn, workspace_id, user_id)? {
+                return Ok(None);
+            }
+
+            diesel::delete(workspace_members::table.find((workspace_id, user_id)))
+                .execute(conn)
+                .map(Some)
+        })
+    }
+}
+
+// user_id의 owner 권한이 없어져도 다른 owner가 남는지 확인 (owner 행을 잠가 동시 변경을 막음)
+fn keeps_other_owner(
+    conn: &mut PgConnection,
+    workspace_id: &str,
+    user_id: &str,
+) -> Result<bool, Error> {
+    let owners = workspace_members::table
+        .filter(workspace_members::workspace_id.eq(workspace_id))
+        .filter(workspace_members::role.eq(WorkspaceRole::Owner.as_str()))
+        .select(workspace_members::user_id)
+        .for_update()
+        .load::<String>(conn)?;
+
+    Ok(!owners.iter().any(|owner| owner == user_id) || owners.len() > 1)
+}
+
+impl WorkspaceInvitation {
+    pub async fn get_invitations(
+        workspace_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<Vec<WorkspaceInvitation>, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        workspace_invitations::table
+            .filter(workspace_invitations::workspace_id.eq(workspace_id))
+            .filter(workspace_invitations::accepted_at.is_null())
+            .order(workspace_invitations::created_at.desc())
+            .load::<WorkspaceInvitation>(conn)
+    }
+
+    pub async fn get_invitation_by_token(
+        token: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<WorkspaceInvitation, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        workspace_invitations::table
+            .filter(workspace_invitations::token.eq(token))
+            .get_result::<WorkspaceInvitation>(conn)
+    }
+
+    // 초대 수락: 멤버 추가(이미 멤버면 더 높은 권한으로) + 수락 시각 기록
+    pub async fn accept_invitation(
+        invitation: &WorkspaceInvitation,
+        user_id: &str,
+        role: WorkspaceRole,
+        pool: &Data<PgPool>,
+    ) -> Result<(), Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+
+        conn.transaction(|conn| {
+            let current = workspace_members::table
+                .find((&invitation.workspace_id, user_id))
+                .select(workspace_members::role)
+                .get_result::<String>(conn)
+                .optional()?
+                .and_then(|role| role.parse::<WorkspaceRole>().ok());
+
+            match current {
+                Some(current) if current >= role => {}
+                Some(_) => {
+                    diesel::update(
+                        workspace_members::table.find((&invitation.workspace_id, user_id)),
+                    )
+                    .set((
+                        workspace_members::role.eq(role.as_str()),
+                        workspace_members::updated_at.eq(Some(Utc::now().naive_utc())),
+                    ))
+                    .execute(conn)?;
+                }
+                None => {
+                    diesel::insert_into(workspace_members::table)
+                        .values(WorkspaceMemberData {
+                            workspace_id: invitation.workspace_id.clone(),
+                            user_id: user_id.to_string(),
+                            role: role.as_str().to_string(),
+                        })
+                        .execute(conn)?;
+                }
+            }
+
+            diesel::update(workspace_invitations::table.find(&invitation.id))
+                .set(workspace_invitations::accepted_at.eq(Some(Utc::now().naive_utc())))
+                .execute(conn)?;
+
+            Ok(())
+        })
+    }
+
+    pub async fn delete_invitation(
+        workspace_id: &str,
+        invitation_id: &str,
+        pool: &Data<PgPool>,
+    ) -> Result<usize, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::delete(
+            workspace_invitations::table
+                .find(invitation_id)
+                .filter(workspace_invitations::workspace_id.eq(workspace_id)),
+        )
+        .execute(conn)
+    }
+}
+
+impl WorkspaceInvitationData {
+    pub async fn create_invitation(
+        invitation_data: WorkspaceInvitationData,
+        pool: &Data<PgPool>,
+    ) -> Result<WorkspaceInvitation, Error> {
+        let conn = &mut pool.get().expect("Couldn't get DB connection from pool");
+        diesel::insert_into(workspace_invitations::table)
+            .values(invitation_data)
+            .get_result::<WorkspaceInvitation>(conn)
+    }
+}
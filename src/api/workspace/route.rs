@@ -0,0 +1,40 @@
+use crate::api::workspace::handler;
+use actix_web::web;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(handler::get_workspaces))
+            .route(web::post().to(handler::create_workspace)),
+    );
+
+    cfg.service(
+        web::resource("/{id}")
+            .route(web::get().to(handler::get_workspace_by_id))
+            .route(web::put().to(handler::update_workspace))
+            .route(web::delete().to(handler::delete_workspace)),
+    );
+
+    cfg.service(web::resource("/{id}/members").route(web::get().to(handler::get_members)));
+
+    cfg.service(
+        web::resource("/{id}/members/{user_id}")
+            .route(web::put().to(handler::update_member))
+            .route(web::delete().to(handler::delete_member)),
+    );
+
+    cfg.service(
+        web::resource("/{id}/invitations")
+            .route(web::get().to(handler::get_invitations))
+            .route(web::post().to(handler::invite_member)),
+    );
+
+    cfg.service(
+        web::resource("/{id}/invitations/{invitation_id}")
+            .route(web::delete().to(handler::delete_invitation)),
+    );
+}
+
+pub fn configure_invitations(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/{token}/accept").route(web::get().to(handler::accept_invitation)));
+}
@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+use std::future::{ready, Ready};
+
+use actix_web::{
+    dev::Payload, error::Error as ActixWebError, web::Query, FromRequest, HttpRequest,
+};
+
+// 노트 API에서 대상 워크스페이스 선택 (X-Workspace-Id 헤더 또는 ?workspace_id= 쿼리)
+// 선택하지 않으면 개인 노트 공간
+#[derive(Debug, Clone)]
+pub struct WorkspaceSelector(pub Option<String>);
+
+pub const WORKSPACE_HEADER: &str = "X-Workspace-Id";
+
+impl FromRequest for WorkspaceSelector {
+    type Error = ActixWebError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let workspace_id = req
+            .headers()
+            .get(WORKSPACE_HEADER)
+            .and_then(|h| h.to_str().ok())
+            .map(str::to_string)
+            .or_else(|| {
+                Query::<HashMap<String, String>>::from_query(req.query_string())
+                    .ok()
+                    .and_then(|query| query.get("workspace_id").cloned())
+            })
+            .filter(|workspace_id| !workspace_id.is_empty());
+
+        ready(Ok(WorkspaceSelector(workspace_id)))
+    }
+}
@@ -1,5 +1,7 @@
 pub mod api;
 pub mod database;
+pub mod mailer;
 pub mod middleware;
 pub mod routes;
 pub mod schema;
+pub mod storage;
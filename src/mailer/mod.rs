@@ -0,0 +1,89 @@
+use std::env;
+
+use derive_more::{Display, Error};
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use log::info;
+
+#[derive(Debug, Display, Error)]
+pub enum MailError {
+    #[display(fmt = "invalid mail address: {}", source)]
+    Address {
+        source: lettre::address::AddressError,
+    },
+
+    #[display(fmt = "failed to build mail: {}", source)]
+    Build { source: lettre::error::Error },
+
+    #[display(fmt = "failed to send mail: {}", source)]
+    Smtp {
+        source: lettre::transport::smtp::Error,
+    },
+}
+
+// SMTP_HOST가 없으면(로컬 개발) 메일을 보내지 않고 로그로만 남김
+pub struct Mailer {
+    transport: Option<AsyncSmtpTransport<Tokio1Executor>>,
+    from: String,
+}
+
+impl Mailer {
+    pub fn from_env() -> Self {
+        let from =
+            env::var("MAIL_FROM").unwrap_or_else(|_| "all-note <no-reply@localhost>".to_string());
+
+        let transport = env::var("SMTP_HOST").ok().map(|host| {
+            let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)
+                .expect("SMTP_HOST must be a valid host");
+
+            if let Some(port) = env::var("SMTP_PORT")
+                .ok()
+                .and_then(|v| v.parse::<u16>().ok())
+            {
+                builder = builder.port(port);
+            }
+
+            if let (Ok(username), Ok(password)) =
+                (env::var("SMTP_USERNAME"), env::var("SMTP_PASSWORD"))
+            {
+                builder = builder.credentials(Credentials::new(username, password));
+            }
+
+            builder.build()
+        });
+
+        Mailer { transport, from }
+    }
+
+    pub async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), MailError> {
+        let transport = match &self.transport {
+            Some(transport) => transport,
+            None => {
+                info!("Mail to {} (SMTP disabled): {}\n{}", to, subject, body);
+                return Ok(());
+            }
+        };
+
+        let message = Message::builder()
+            .from(
+                self.from
+                    .parse::<Mailbox>()
+                    .map_err(|source| MailError::Address { source })?,
+            )
+            .to(to
+                .parse::<Mailbox>()
+                .map_err(|source| MailError::Address { source })?)
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(body.to_string())
+            .map_err(|source| MailError::Build { source })?;
+
+        transport
+            .send(message)
+            .await
+            .map_err(|source| MailError::Smtp { source })?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use actix_cors::Cors;
 use actix_web::{
-    http::{self, StatusCode},
+    http::{self, header::HeaderName, StatusCode},
     middleware::{ErrorHandlers, Logger},
     web::{get, scope, Data},
     App, HttpResponse, HttpServer,
@@ -23,6 +23,19 @@ async fn main() -> std::io::Result<()> {
         Err(err) => eprintln!("Error connecting to the database: {:?}", err),
     }
 
+    let store = Data::from(storage::establish_storage());
+    let mailer = Data::new(mailer::Mailer::from_env());
+    let events = api::event::bus::EventBus::default();
+    let rooms = Data::new(api::collab::room::Rooms::new(events.clone()));
+    let tickets = Data::new(api::collab::ticket::Tickets::default());
+    let events = Data::new(events);
+    // 예약 공개/비공개
+    api::post::scheduler::spawn_scheduler(Data::new(pool.clone()), events.clone());
+    // 리마인더 알림
+    api::reminder::scheduler::spawn_scheduler(Data::new(pool.clone()), mailer.clone());
+    // 웹훅 전송
+    api::webhook::worker::spawn_worker(Data::new(pool.clone()));
+
     HttpServer::new(move || {
         App::new()
             // 에러 핸들러 미들웨어
@@ -37,13 +50,45 @@ async fn main() -> std::io::Result<()> {
                 Cors::default()
                     .allow_any_origin()
                     .supports_credentials()
-                    .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
-                    .allowed_headers(vec![http::header::AUTHORIZATION, http::header::ACCEPT])
+                    .allowed_methods(vec!["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD"])
+                    .allowed_headers(vec![
+                        http::header::AUTHORIZATION,
+                        http::header::ACCEPT,
+                        http::header::RANGE,
+                        http::header::CONTENT_TYPE,
+                        HeaderName::from_static("x-share-password"),
+                        HeaderName::from_static("x-workspace-id"),
+                        HeaderName::from_static("last-event-id"),
+                        HeaderName::from_static("tus-resumable"),
+                        HeaderName::from_static("upload-length"),
+                        HeaderName::from_static("upload-offset"),
+                        HeaderName::from_static("upload-metadata"),
+                    ])
+                    // tus 클라이언트가 읽어야 하는 응답 헤더
+                    .expose_headers(vec![
+                        http::header::LOCATION,
+                        HeaderName::from_static("tus-resumable"),
+                        HeaderName::from_static("tus-version"),
+                        HeaderName::from_static("tus-extension"),
+                        HeaderName::from_static("tus-max-size"),
+                        HeaderName::from_static("upload-length"),
+                        HeaderName::from_static("upload-offset"),
+                        HeaderName::from_static("attachment-id"),
+                    ])
                     .max_age(3600),
             )
             .wrap(middleware::req_res_middleware::SayHi)
             .app_data(Data::new(pool.clone()))
+            .app_data(store.clone())
+            .app_data(mailer.clone())
+            .app_data(rooms.clone())
+            .app_data(tickets.clone())
+            .app_data(events.clone())
             .service(scope("/api").configure(routes::configure))
+            // 공개된 노트 페이지
+            .service(scope("/p").configure(api::page::route::configure))
+            // 공개된 노트의 RSS/Atom 피드
+            .service(scope("/feeds").configure(api::feed::route::configure))
             .route(
                 "/",
                 get().to(|| async { HttpResponse::Ok().body("Hello, Actix!") }),
@@ -1,12 +1,46 @@
 use actix_web::{http::header::ContentType, web, HttpResponse, Responder};
 
-use crate::api::{auth, post, user};
+use crate::api::{
+    attachment, auth, collab, comment, daily, embed, event, inbox, link, notification, permission,
+    post, reminder, share, sync, task, template, upload, user, webhook, workspace,
+};
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(web::resource("/").route(web::get().to(my_handler)));
+    cfg.service(web::scope("/posts/{id}/attachments").configure(attachment::route::configure));
+    cfg.service(web::scope("/posts/{id}/share").configure(share::route::configure));
+    cfg.service(web::scope("/posts/{id}/permissions").configure(permission::route::configure));
+    cfg.service(web::scope("/posts/{id}/ws").configure(collab::route::configure));
+    cfg.service(web::scope("/posts/{id}/presence").configure(collab::route::configure_presence));
+    cfg.service(web::scope("/posts/{id}/backlinks").configure(link::route::configure));
+    cfg.service(web::scope("/posts/{id}/render").configure(embed::route::configure));
+    cfg.service(web::scope("/posts/{id}/comments").configure(comment::route::configure));
+    cfg.service(web::scope("/posts/{id}/reminders").configure(reminder::route::configure_post));
+    cfg.service(
+        web::scope("/posts/from-template/{id}").configure(template::route::configure_instantiate),
+    );
     cfg.service(web::scope("/posts").configure(post::route::configure));
+    cfg.service(web::scope("/attachments").configure(attachment::route::configure_attachments));
     cfg.service(web::scope("/auth").configure(auth::route::configure));
     cfg.service(web::scope("/users").configure(user::route::configure));
+    cfg.service(web::scope("/uploads").configure(upload::route::configure));
+    cfg.service(web::scope("/shared").configure(share::route::configure_shared));
+    cfg.service(web::scope("/events").configure(event::route::configure));
+    cfg.service(web::scope("/sync").configure(sync::route::configure));
+    cfg.service(web::scope("/graph").configure(link::route::configure_graph));
+    cfg.service(web::scope("/templates").configure(template::route::configure));
+    cfg.service(web::scope("/daily").configure(daily::route::configure));
+    cfg.service(web::scope("/tasks").configure(task::route::configure));
+    cfg.service(web::scope("/reminders").configure(reminder::route::configure));
+    cfg.service(web::scope("/notifications").configure(notification::route::configure));
+    cfg.service(web::scope("/webhooks").configure(webhook::route::configure));
+    cfg.service(web::scope("/inboxes").configure(inbox::route::configure));
+    // 로그인 없이 토큰으로 노트 만들기
+    cfg.service(web::scope("/inbox").configure(inbox::route::configure_capture));
+    cfg.service(web::scope("/workspaces").configure(workspace::route::configure));
+    cfg.service(
+        web::scope("/workspace-invitations").configure(workspace::route::configure_invitations),
+    );
 }
 
 async fn my_handler() -> impl Responder {
@@ -1,5 +1,186 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    attachments (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        post_id -> Varchar,
+        #[max_length = 255]
+        user_id -> Varchar,
+        #[max_length = 255]
+        file_name -> Varchar,
+        #[max_length = 255]
+        content_type -> Varchar,
+        size -> Int8,
+        #[max_length = 255]
+        storage_key -> Varchar,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    attachment_thumbnails (attachment_id, size) {
+        #[max_length = 255]
+        attachment_id -> Varchar,
+        #[max_length = 32]
+        size -> Varchar,
+        width -> Int4,
+        height -> Int4,
+        #[max_length = 255]
+        content_type -> Varchar,
+        #[max_length = 255]
+        storage_key -> Varchar,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    comments (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        post_id -> Varchar,
+        #[max_length = 255]
+        user_id -> Varchar,
+        #[max_length = 255]
+        parent_id -> Nullable<Varchar>,
+        body -> Text,
+        anchor_start -> Nullable<Int4>,
+        anchor_end -> Nullable<Int4>,
+        anchor_text -> Nullable<Text>,
+        resolved_at -> Nullable<Timestamp>,
+        #[max_length = 255]
+        resolved_by -> Nullable<Varchar>,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    daily_notes (user_id, day) {
+        #[max_length = 255]
+        user_id -> Varchar,
+        day -> Date,
+        #[max_length = 255]
+        post_id -> Varchar,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    inboxes (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        user_id -> Varchar,
+        #[max_length = 255]
+        workspace_id -> Nullable<Varchar>,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 255]
+        token -> Varchar,
+        published -> Bool,
+        active -> Bool,
+        last_used_at -> Nullable<Timestamp>,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    mentions (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        post_id -> Varchar,
+        #[max_length = 255]
+        comment_id -> Nullable<Varchar>,
+        #[max_length = 255]
+        user_id -> Varchar,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    notifications (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        user_id -> Varchar,
+        #[max_length = 32]
+        kind -> Varchar,
+        #[max_length = 255]
+        post_id -> Nullable<Varchar>,
+        message -> Text,
+        read_at -> Nullable<Timestamp>,
+        created_at -> Nullable<Timestamp>,
+        #[max_length = 255]
+        actor_id -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    post_documents (post_id) {
+        #[max_length = 255]
+        post_id -> Varchar,
+        state -> Bytea,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    post_events (id) {
+        id -> Int8,
+        #[max_length = 255]
+        user_id -> Varchar,
+        #[max_length = 255]
+        post_id -> Varchar,
+        #[max_length = 32]
+        kind -> Varchar,
+        xid -> Int8,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    post_links (source_id, target_title) {
+        #[max_length = 255]
+        source_id -> Varchar,
+        #[max_length = 255]
+        target_title -> Varchar,
+        #[max_length = 255]
+        target_id -> Nullable<Varchar>,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    post_permissions (post_id, user_id) {
+        #[max_length = 255]
+        post_id -> Varchar,
+        #[max_length = 255]
+        user_id -> Varchar,
+        #[max_length = 32]
+        role -> Varchar,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    post_tombstones (post_id, user_id) {
+        #[max_length = 255]
+        post_id -> Varchar,
+        #[max_length = 255]
+        user_id -> Varchar,
+        sync_version -> Int8,
+        sync_xid -> Int8,
+        deleted_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     posts (id) {
         #[max_length = 255]
@@ -10,6 +191,122 @@ diesel::table! {
         published -> Bool,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        #[max_length = 255]
+        workspace_id -> Nullable<Varchar>,
+        sync_version -> Int8,
+        sync_xid -> Int8,
+        #[max_length = 255]
+        author_id -> Nullable<Varchar>,
+        #[max_length = 255]
+        slug -> Nullable<Varchar>,
+        publish_at -> Nullable<Timestamp>,
+        unpublish_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    reminders (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        post_id -> Varchar,
+        #[max_length = 255]
+        user_id -> Varchar,
+        message -> Nullable<Text>,
+        starts_at -> Timestamp,
+        #[max_length = 255]
+        rrule -> Nullable<Varchar>,
+        #[max_length = 64]
+        timezone -> Varchar,
+        fire_at -> Timestamp,
+        email -> Bool,
+        active -> Bool,
+        last_fired_at -> Nullable<Timestamp>,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    share_links (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        post_id -> Varchar,
+        #[max_length = 255]
+        user_id -> Varchar,
+        #[max_length = 255]
+        token -> Varchar,
+        #[max_length = 255]
+        password_hash -> Nullable<Varchar>,
+        expires_at -> Nullable<Timestamp>,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    tasks (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        post_id -> Varchar,
+        line -> Int4,
+        text -> Text,
+        done -> Bool,
+        due_date -> Nullable<Date>,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    templates (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 255]
+        title -> Varchar,
+        body -> Text,
+        #[max_length = 255]
+        user_id -> Varchar,
+        #[max_length = 255]
+        workspace_id -> Nullable<Varchar>,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    upload_chunks (upload_id, chunk_offset) {
+        #[max_length = 255]
+        upload_id -> Varchar,
+        chunk_offset -> Int8,
+        size -> Int8,
+        #[max_length = 255]
+        storage_key -> Varchar,
+    }
+}
+
+diesel::table! {
+    upload_sessions (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        user_id -> Varchar,
+        #[max_length = 255]
+        post_id -> Varchar,
+        #[max_length = 255]
+        file_name -> Varchar,
+        #[max_length = 255]
+        content_type -> Varchar,
+        upload_length -> Int8,
+        upload_offset -> Int8,
+        #[max_length = 255]
+        attachment_id -> Nullable<Varchar>,
+        completed -> Bool,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
     }
 }
 
@@ -30,10 +327,168 @@ diesel::table! {
         photo -> Varchar,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        #[max_length = 64]
+        timezone -> Varchar,
+        #[max_length = 255]
+        daily_template_id -> Nullable<Varchar>,
+        #[max_length = 32]
+        handle -> Varchar,
+    }
+}
+
+diesel::table! {
+    workspace_invitations (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        workspace_id -> Varchar,
+        #[max_length = 255]
+        email -> Varchar,
+        #[max_length = 32]
+        role -> Varchar,
+        #[max_length = 255]
+        token -> Varchar,
+        #[max_length = 255]
+        invited_by -> Varchar,
+        expires_at -> Timestamp,
+        accepted_at -> Nullable<Timestamp>,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        webhook_id -> Varchar,
+        #[max_length = 32]
+        event -> Varchar,
+        payload -> Text,
+        #[max_length = 16]
+        status -> Varchar,
+        attempts -> Int4,
+        next_attempt_at -> Nullable<Timestamp>,
+        last_attempt_at -> Nullable<Timestamp>,
+        response_status -> Nullable<Int4>,
+        response_body -> Nullable<Text>,
+        error -> Nullable<Text>,
+        #[max_length = 255]
+        redelivery_of -> Nullable<Varchar>,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    webhooks (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        user_id -> Varchar,
+        #[max_length = 255]
+        workspace_id -> Nullable<Varchar>,
+        url -> Text,
+        #[max_length = 255]
+        secret -> Varchar,
+        events -> Array<Text>,
+        active -> Bool,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    workspace_members (workspace_id, user_id) {
+        #[max_length = 255]
+        workspace_id -> Varchar,
+        #[max_length = 255]
+        user_id -> Varchar,
+        #[max_length = 32]
+        role -> Varchar,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    workspaces (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 255]
+        created_by -> Varchar,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
     }
 }
 
+diesel::joinable!(attachment_thumbnails -> attachments (attachment_id));
+diesel::joinable!(attachments -> posts (post_id));
+diesel::joinable!(attachments -> users (user_id));
+diesel::joinable!(comments -> posts (post_id));
+diesel::joinable!(comments -> users (user_id));
+diesel::joinable!(daily_notes -> posts (post_id));
+diesel::joinable!(daily_notes -> users (user_id));
+diesel::joinable!(inboxes -> users (user_id));
+diesel::joinable!(inboxes -> workspaces (workspace_id));
+diesel::joinable!(mentions -> comments (comment_id));
+diesel::joinable!(mentions -> posts (post_id));
+diesel::joinable!(mentions -> users (user_id));
+diesel::joinable!(notifications -> posts (post_id));
+diesel::joinable!(notifications -> users (user_id));
+diesel::joinable!(post_documents -> posts (post_id));
+diesel::joinable!(post_events -> users (user_id));
+diesel::joinable!(post_links -> posts (source_id));
+diesel::joinable!(post_permissions -> posts (post_id));
+diesel::joinable!(post_permissions -> users (user_id));
+diesel::joinable!(post_tombstones -> users (user_id));
+diesel::joinable!(posts -> users (author_id));
+diesel::joinable!(posts -> workspaces (workspace_id));
+diesel::joinable!(reminders -> posts (post_id));
+diesel::joinable!(reminders -> users (user_id));
+diesel::joinable!(share_links -> posts (post_id));
+diesel::joinable!(share_links -> users (user_id));
+diesel::joinable!(tasks -> posts (post_id));
+diesel::joinable!(templates -> users (user_id));
+diesel::joinable!(templates -> workspaces (workspace_id));
+diesel::joinable!(upload_chunks -> upload_sessions (upload_id));
+diesel::joinable!(upload_sessions -> attachments (attachment_id));
+diesel::joinable!(upload_sessions -> posts (post_id));
+diesel::joinable!(upload_sessions -> users (user_id));
+diesel::joinable!(webhook_deliveries -> webhooks (webhook_id));
+diesel::joinable!(webhooks -> users (user_id));
+diesel::joinable!(webhooks -> workspaces (workspace_id));
+diesel::joinable!(workspace_invitations -> users (invited_by));
+diesel::joinable!(workspace_invitations -> workspaces (workspace_id));
+diesel::joinable!(workspace_members -> users (user_id));
+diesel::joinable!(workspace_members -> workspaces (workspace_id));
+diesel::joinable!(workspaces -> users (created_by));
+
 diesel::allow_tables_to_appear_in_same_query!(
+    attachment_thumbnails,
+    attachments,
+    comments,
+    daily_notes,
+    inboxes,
+    mentions,
+    notifications,
+    post_documents,
+    post_events,
+    post_links,
+    post_permissions,
+    post_tombstones,
     posts,
+    reminders,
+    share_links,
+    tasks,
+    templates,
+    upload_chunks,
+    upload_sessions,
     users,
+    webhook_deliveries,
+    webhooks,
+    workspace_invitations,
+    workspace_members,
+    workspaces,
 );
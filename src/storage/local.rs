@@ -0,0 +1,84 @@
+use std::io::SeekFrom;
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use tokio::fs;
+use tokio::io::{self, AsyncReadExt, AsyncSeekExt};
+
+use super::{BlobStore, StorageError};
+
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalBlobStore { root: root.into() }
+    }
+
+    // key에 "..", 절대경로가 섞여 root 밖으로 나가는 것을 막음
+    fn path_for(&self, key: &str) -> Result<PathBuf, StorageError> {
+        let relative = Path::new(key);
+        if key.is_empty()
+            || relative
+                .components()
+                .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            return Err(StorageError::InvalidKey);
+        }
+
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, data: Bytes) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        fs::write(path, data).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str, range: Option<(u64, u64)>) -> Result<Bytes, StorageError> {
+        let path = self.path_for(key)?;
+
+        match range {
+            Some((start, end)) => {
+                let mut file = fs::File::open(path).await?;
+                file.seek(SeekFrom::Start(start)).await?;
+
+                let mut buf = Vec::with_capacity((end - start + 1) as usize);
+                file.take(end - start + 1).read_to_end(&mut buf).await?;
+                Ok(Bytes::from(buf))
+            }
+            None => Ok(Bytes::from(fs::read(path).await?)),
+        }
+    }
+
+    // 메모리에 올리지 않고 파일 단위로 이어 붙임
+    async fn concat(&self, parts: &[String], key: &str) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let mut output = fs::File::create(path).await?;
+        for part in parts {
+            let mut input = fs::File::open(self.path_for(part)?).await?;
+            io::copy(&mut input, &mut output).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        fs::remove_file(path).await?;
+        Ok(())
+    }
+}
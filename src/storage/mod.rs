@@ -0,0 +1,92 @@
+use std::env;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use derive_more::{Display, Error};
+
+pub mod local;
+pub mod s3;
+
+pub type SharedBlobStore = Arc<dyn BlobStore>;
+
+#[derive(Debug, Display, Error)]
+pub enum StorageError {
+    #[display(fmt = "blob not found")]
+    NotFound,
+
+    #[display(fmt = "invalid blob key")]
+    InvalidKey,
+
+    #[display(fmt = "storage io error: {}", source)]
+    Io { source: std::io::Error },
+
+    #[display(fmt = "storage http error: {}", source)]
+    Http { source: reqwest::Error },
+
+    #[display(fmt = "storage backend responded with status {}", status)]
+    Remote { status: u16 },
+
+    #[display(fmt = "unexpected storage backend response")]
+    InvalidResponse,
+}
+
+impl From<std::io::Error> for StorageError {
+    fn from(source: std::io::Error) -> Self {
+        match source.kind() {
+            std::io::ErrorKind::NotFound => StorageError::NotFound,
+            _ => StorageError::Io { source },
+        }
+    }
+}
+
+impl From<reqwest::Error> for StorageError {
+    fn from(source: reqwest::Error) -> Self {
+        StorageError::Http { source }
+    }
+}
+
+// 첨부파일 바이너리 저장소 추상화 (로컬 파일시스템 등 백엔드 교체 가능)
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    async fn put(&self, key: &str, data: Bytes) -> Result<(), StorageError>;
+
+    // range: (start, end) 양 끝 포함(inclusive) 바이트 범위
+    async fn get(&self, key: &str, range: Option<(u64, u64)>) -> Result<Bytes, StorageError>;
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+
+    // 여러 blob을 순서대로 이어 붙여 key에 저장 (이어 올리기 업로드 조립용)
+    async fn concat(&self, parts: &[String], key: &str) -> Result<(), StorageError> {
+        let mut data = bytes::BytesMut::new();
+        for part in parts {
+            data.extend_from_slice(&self.get(part, None).await?);
+        }
+
+        self.put(key, data.freeze()).await
+    }
+
+    // 클라이언트가 저장소에서 직접 내려받을 수 있는 서명 URL (지원하지 않는 백엔드는 None)
+    fn presigned_download_url(
+        &self,
+        _key: &str,
+        _file_name: &str,
+        _content_type: &str,
+    ) -> Option<String> {
+        None
+    }
+}
+
+// STORAGE_BACKEND: "local"(기본값) 또는 "s3"
+pub fn establish_storage() -> SharedBlobStore {
+    let backend = env::var("STORAGE_BACKEND").unwrap_or_else(|_| "local".to_string());
+
+    match backend.as_str() {
+        "s3" => Arc::new(s3::S3BlobStore::from_env()),
+        "local" => {
+            let root = env::var("STORAGE_PATH").unwrap_or_else(|_| "./storage".to_string());
+            Arc::new(local::LocalBlobStore::new(root))
+        }
+        other => panic!("Unknown STORAGE_BACKEND: {}", other),
+    }
+}
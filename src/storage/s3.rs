@@ -0,0 +1,502 @@
+use std::env;
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use log::error;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::{header, Client, StatusCode, Url};
+use sha2::{Digest, Sha256};
+
+use super::{BlobStore, StorageError};
+
+type HmacSha256 = Hmac<Sha256>;
+
+// SigV4 URI 인코딩: unreserved 문자(A-Z a-z 0-9 - _ . ~)만 그대로 둠
+const URI_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+// 서버가 직접 사용하는 서명 URL 유효시간 (초)
+const INTERNAL_EXPIRES: u64 = 300;
+
+// multipart 업로드에서 마지막 part를 뺀 최소 크기 (S3 제한)
+const MIN_PART_SIZE: u64 = 5 * 1024 * 1024;
+// UploadPartCopy 한 번에 복사할 수 있는 최대 크기 (S3 제한)
+const MAX_COPY_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+// S3 호환 스토리지 (AWS S3, MinIO 등). 모든 요청은 SigV4 pre-signed URL로 보냄
+pub struct S3BlobStore {
+    client: Client,
+    endpoint: Url,
+    region: String,
+    bucket: String,
+    access_key: String,
+    secret_key: String,
+    path_style: bool,
+    presign_expires: u64,
+}
+
+impl S3BlobStore {
+    pub fn from_env() -> Self {
+        let endpoint = env::var("S3_ENDPOINT").expect("S3_ENDPOINT must be set");
+
+        S3BlobStore {
+            client: Client::new(),
+            endpoint: Url::parse(&endpoint).expect("S3_ENDPOINT must be a valid URL"),
+            region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+            bucket: env::var("S3_BUCKET").expect("S3_BUCKET must be set"),
+            access_key: env::var("S3_ACCESS_KEY").expect("S3_ACCESS_KEY must be set"),
+            secret_key: env::var("S3_SECRET_KEY").expect("S3_SECRET_KEY must be set"),
+            // MinIO 등은 path-style(endpoint/bucket/key)만 지원하는 경우가 많아 기본값 true
+            path_style: env::var("S3_PATH_STYLE")
+                .map(|v| v != "false")
+                .unwrap_or(true),
+            presign_expires: env::var("S3_PRESIGN_EXPIRES")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(900),
+        }
+    }
+
+    fn host(&self) -> String {
+        let host = self.endpoint.host_str().unwrap_or_default();
+        let host = match self.endpoint.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        };
+
+        if self.path_style {
+            host
+        } else {
+            format!("{}.{}", self.bucket, host)
+        }
+    }
+
+    fn canonical_uri(&self, key: &str) -> String {
+        let key = encode_key(key);
+
+        if self.path_style {
+            format!("/{}/{}", self.bucket, key)
+        } else {
+            format!("/{}", key)
+        }
+    }
+
+    // AWS Signature Version 4 (query string 방식) 서명 URL 생성
+    fn presign(&self, method: &str, key: &str, expires: u64, extra: &[(&str, String)]) -> String {
+        self.presign_at(Utc::now(), method, key, expires, extra, &[])
+    }
+
+    // x-amz-copy-source처럼 요청에 함께 보내는 x-amz-* 헤더는 서명에 포함해야 함
+    fn presign_with_headers(
+        &self,
+        method: &str,
+        key: &str,
+        extra: &[(&str, String)],
+        headers: &[(&str, String)],
+    ) -> String {
+        self.presign_at(Utc::now(), method, key, INTERNAL_EXPIRES, extra, headers)
+    }
+
+    fn presign_at(
+        &self,
+        now: DateTime<Utc>,
+        method: &str,
+        key: &str,
+        expires: u64,
+        extra: &[(&str, String)],
+        headers: &[(&str, String)],
+    ) -> String {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let host = self.host();
+        let uri = self.canonical_uri(key);
+
+        let mut signed: Vec<(String, String)> = vec![("host".to_string(), host.clone())];
+        signed.extend(
+            headers
+                .iter()
+                .map(|(k, v)| (k.to_lowercase(), v.trim().to_string())),
+        );
+        signed.sort();
+        let canonical_headers: String = signed
+            .iter()
+            .map(|(k, v)| format!("{}:{}\n", k, v))
+            .collect();
+        let signed_headers = signed
+            .iter()
+            .map(|(k, _)| k.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+
+        let mut params: Vec<(String, String)> = vec![
+            (
+                "X-Amz-Algorithm".to_string(),
+                "AWS4-HMAC-SHA256".to_string(),
+            ),
+            (
+                "X-Amz-Credential".to_string(),
+                format!("{}/{}", self.access_key, scope),
+            ),
+            ("X-Amz-Date".to_string(), amz_date.clone()),
+            ("X-Amz-Expires".to_string(), expires.to_string()),
+            ("X-Amz-SignedHeaders".to_string(), signed_headers.clone()),
+        ];
+        params.extend(extra.iter().map(|(k, v)| (k.to_string(), v.clone())));
+        params.sort();
+
+        let query = params
+            .iter()
+            .map(|(k, v)| {
+                format!(
+                    "{}={}",
+                    utf8_percent_encode(k, URI_ENCODE_SET),
+                    utf8_percent_encode(v, URI_ENCODE_SET)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\nUNSIGNED-PAYLOAD",
+            method, uri, query, canonical_headers, signed_headers
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let signing_key = [self.region.as_str(), "s3", "aws4_request"].iter().fold(
+            hmac_sha256(
+                format!("AWS4{}", self.secret_key).as_bytes(),
+                date.as_bytes(),
+            ),
+            |key, part| hmac_sha256(&key, part.as_bytes()),
+        );
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        format!(
+            "{}://{}{}?{}&X-Amz-Signature={}",
+            self.endpoint.scheme(),
+            host,
+            uri,
+            query,
+            signature
+        )
+    }
+
+    async fn head_size(&self, key: &str) -> Result<u64, StorageError> {
+        let url = self.presign("HEAD", key, INTERNAL_EXPIRES, &[]);
+        let response = self.client.head(url).send().await?;
+        check_status(response.status())?;
+
+        response
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+            .ok_or(StorageError::InvalidResponse)
+    }
+
+    async fn create_multipart_upload(&self, key: &str) -> Result<String, StorageError> {
+        let url = self.presign("POST", key, INTERNAL_EXPIRES, &[("uploads", String::new())]);
+        let response = self.client.post(url).send().await?;
+        check_status(response.status())?;
+
+        let body = response.text().await?;
+        xml_tag(&body, "UploadId").ok_or(StorageError::InvalidResponse)
+    }
+
+    async fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: usize,
+        data: Bytes,
+    ) -> Result<String, StorageError> {
+        let url = self.presign(
+            "PUT",
+            key,
+            INTERNAL_EXPIRES,
+            &[
+                ("partNumber", part_number.to_string()),
+                ("uploadId", upload_id.to_string()),
+            ],
+        );
+        let response = self.client.put(url).body(data).send().await?;
+        check_status(response.status())?;
+
+        response
+            .headers()
+            .get(header::ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+            .ok_or(StorageError::InvalidResponse)
+    }
+
+    // source의 start..=end 범위를 저장소 안에서 part로 복사
+    async fn upload_part_copy(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: usize,
+        source: &str,
+        (start, end): (u64, u64),
+    ) -> Result<String, StorageError> {
+        let headers = [
+            (
+                "x-amz-copy-source",
+                format!("/{}/{}", self.bucket, encode_key(source)),
+            ),
+            (
+                "x-amz-copy-source-range",
+                format!("bytes={}-{}", start, end),
+            ),
+        ];
+        let url = self.presign_with_headers(
+            "PUT",
+            key,
+            &[
+                ("partNumber", part_number.to_string()),
+                ("uploadId", upload_id.to_string()),
+            ],
+            &headers,
+        );
+
+        let mut request = self.client.put(url);
+        for (name, value) in &headers {
+            request = request.header(*name, value);
+        }
+        let response = request.send().await?;
+        check_status(response.status())?;
+
+        // 복사 중 실패해도 200과 함께 <Error> 본문이 올 수 있음
+        let body = response.text().await?;
+        if body.contains("<Error>") {
+            return Err(StorageError::InvalidResponse);
+        }
+        xml_tag(&body, "ETag").ok_or(StorageError::InvalidResponse)
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        key: &str,
+        upload_id: &str,
+        etags: &[String],
+    ) -> Result<(), StorageError> {
+        let parts: String = etags
+            .iter()
+            .enumerate()
+            .map(|(index, etag)| {
+                format!(
+                    "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+                    index + 1,
+                    etag.replace('"', "&quot;")
+                )
+            })
+            .collect();
+        let body = format!(
+            "<CompleteMultipartUpload>{}</CompleteMultipartUpload>",
+            parts
+        );
+
+        let url = self.presign(
+            "POST",
+            key,
+            INTERNAL_EXPIRES,
+            &[("uploadId", upload_id.to_string())],
+        );
+        let response = self.client.post(url).body(body).send().await?;
+        check_status(response.status())?;
+
+        let body = response.text().await?;
+        if body.contains("<Error>") {
+            return Err(StorageError::InvalidResponse);
+        }
+        Ok(())
+    }
+
+    // 올린 part가 저장소에 남지 않도록, 실패는 로그만 남김
+    async fn abort_multipart_upload(&self, key: &str, upload_id: &str) {
+        let url = self.presign(
+            "DELETE",
+            key,
+            INTERNAL_EXPIRES,
+            &[("uploadId", upload_id.to_string())],
+        );
+        let result = match self.client.delete(url).send().await {
+            Ok(response) => check_status(response.status()),
+            Err(err) => Err(err.into()),
+        };
+        if let Err(err) = result {
+            error!("Error aborting multipart upload: {:?}", err);
+        }
+    }
+
+    // 5MB 이상 남은 부분은 저장소 안에서 복사하고, 작은 chunk만 MIN_PART_SIZE까지 모아 올림
+    // 서버 메모리에는 최대 MIN_PART_SIZE만 올라감
+    async fn upload_parts(
+        &self,
+        parts: &[String],
+        key: &str,
+        upload_id: &str,
+    ) -> Result<Vec<String>, StorageError> {
+        let mut etags = Vec::new();
+        let mut buffer = BytesMut::new();
+
+        for part in parts {
+            let size = self.head_size(part).await?;
+            let mut start = 0;
+
+            while start < size {
+                let remaining = size - start;
+                if buffer.is_empty() && remaining >= MIN_PART_SIZE {
+                    let end = start + remaining.min(MAX_COPY_PART_SIZE) - 1;
+                    let etag = self
+                        .upload_part_copy(key, upload_id, etags.len() + 1, part, (start, end))
+                        .await?;
+                    etags.push(etag);
+                    start = end + 1;
+                    continue;
+                }
+
+                let end = start + remaining.min(MIN_PART_SIZE - buffer.len() as u64) - 1;
+                buffer.extend_from_slice(&self.get(part, Some((start, end))).await?);
+                start = end + 1;
+
+                if buffer.len() as u64 == MIN_PART_SIZE {
+                    let etag = self
+                        .upload_part(key, upload_id, etags.len() + 1, buffer.split().freeze())
+                        .await?;
+                    etags.push(etag);
+                }
+            }
+        }
+
+        // 마지막 part는 5MB보다 작아도 됨
+        if !buffer.is_empty() {
+            let etag = self
+                .upload_part(key, upload_id, etags.len() + 1, buffer.freeze())
+                .await?;
+            etags.push(etag);
+        }
+
+        Ok(etags)
+    }
+}
+
+fn encode_key(key: &str) -> String {
+    key.split('/')
+        .map(|segment| utf8_percent_encode(segment, URI_ENCODE_SET).to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// S3 응답 XML에서 첫 번째 <tag> 값 (ETag의 &quot; 포함)
+fn xml_tag(body: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = body.find(&open)? + open.len();
+    let end = start + body[start..].find(&close)?;
+
+    Some(
+        body[start..end]
+            .replace("&quot;", "\"")
+            .replace("&#34;", "\""),
+    )
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn check_status(status: StatusCode) -> Result<(), StorageError> {
+    match status {
+        status if status.is_success() => Ok(()),
+        StatusCode::NOT_FOUND => Err(StorageError::NotFound),
+        status => Err(StorageError::Remote {
+            status: status.as_u16(),
+        }),
+    }
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn put(&self, key: &str, data: Bytes) -> Result<(), StorageError> {
+        let url = self.presign("PUT", key, INTERNAL_EXPIRES, &[]);
+        let response = self.client.put(url).body(data).send().await?;
+        check_status(response.status())
+    }
+
+    async fn get(&self, key: &str, range: Option<(u64, u64)>) -> Result<Bytes, StorageError> {
+        let url = self.presign("GET", key, INTERNAL_EXPIRES, &[]);
+        let mut request = self.client.get(url);
+        if let Some((start, end)) = range {
+            request = request.header(header::RANGE, format!("bytes={}-{}", start, end));
+        }
+
+        let response = request.send().await?;
+        check_status(response.status())?;
+        Ok(response.bytes().await?)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let url = self.presign("DELETE", key, INTERNAL_EXPIRES, &[]);
+        let response = self.client.delete(url).send().await?;
+        check_status(response.status())
+    }
+
+    // 서버로 내려받아 다시 올리지 않고 multipart 업로드로 저장소 안에서 조립
+    async fn concat(&self, parts: &[String], key: &str) -> Result<(), StorageError> {
+        let upload_id = self.create_multipart_upload(key).await?;
+
+        let result = match self.upload_parts(parts, key, &upload_id).await {
+            // part가 없으면 multipart를 완료할 수 없으므로 빈 객체로 저장
+            Ok(etags) if etags.is_empty() => {
+                self.abort_multipart_upload(key, &upload_id).await;
+                return self.put(key, Bytes::new()).await;
+            }
+            Ok(etags) => {
+                self.complete_multipart_upload(key, &upload_id, &etags)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+
+        if result.is_err() {
+            self.abort_multipart_upload(key, &upload_id).await;
+        }
+        result
+    }
+
+    fn presigned_download_url(
+        &self,
+        key: &str,
+        file_name: &str,
+        content_type: &str,
+    ) -> Option<String> {
+        let disposition = format!(
+            "attachment; filename*=UTF-8''{}",
+            utf8_percent_encode(file_name, URI_ENCODE_SET)
+        );
+
+        Some(self.presign(
+            "GET",
+            key,
+            self.presign_expires,
+            &[
+                ("response-content-disposition", disposition),
+                ("response-content-type", content_type.to_string()),
+            ],
+        ))
+    }
+}